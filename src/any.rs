@@ -0,0 +1,444 @@
+//! 来源未知的版本号: [`AnyVersion`] 与自动识别
+//!
+//! [`detect`] 依次尝试各种版本号方案, 返回所有解析成功的结果, 每个结果带有 0 到 1 之间的置信度,
+//! 按置信度从高到低排列。置信度由各方案特有的写法决定, 例如 `~` 与 `ubuntu` 修订号倾向于 Debian,
+//! `.post1` 倾向于 PEP 440, 4 个以上的纯数字部分倾向于 [`numeric`],
+//! `r12.g3abc7d` 倾向于 pacman, `-dev` 倾向于 Composer; Gentoo 与 Alpine 的写法相同时优先 apk
+//!
+//! 不同方案的版本号之间没有可靠的顺序, 因此 [`AnyVersion`] 只实现了 [`PartialOrd`],
+//! 方案不同时比较结果为 `None`, [`AnyVersion::try_cmp`] 则返回 [`SchemeMismatch`]
+//!
+//! ```
+//! use version::any::{detect, AnyVersion, Scheme};
+//!
+//! let candidates = detect("1:2.3~rc1-1ubuntu2");
+//! assert_eq!(candidates[0].version().scheme(), Scheme::Debian);
+//!
+//! let a = AnyVersion::detect("2.0.post1").unwrap();
+//! let b = AnyVersion::detect("2.0.0-rc.1").unwrap();
+//! assert_eq!((a.scheme(), b.scheme()), (Scheme::Pep440, Scheme::Semver));
+//! assert!(a.try_cmp(&b).is_err());
+//! assert_eq!(a.partial_cmp(&b), None);
+//! ```
+
+use crate::{apk, calver, composer, debian, go, maven, nuget, numeric, pacman, pep440, portage, rpm, rubygems};
+use std::cmp::Ordering;
+use std::fmt;
+use thiserror::Error;
+
+///
+/// 版本号方案
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Scheme {
+    /// 本库的语义化版本号 [`crate::Version`]
+    Semver,
+    /// Go 模块版本号
+    Go,
+    /// Python 的 PEP 440 版本号
+    Pep440,
+    /// Maven 版本号
+    Maven,
+    /// NuGet 版本号
+    NuGet,
+    /// RubyGems 版本号
+    RubyGems,
+    /// Debian 软件包版本号
+    Debian,
+    /// RPM 软件包版本号
+    Rpm,
+    /// Alpine 软件包版本号
+    Apk,
+    /// 日历版本号
+    CalVer,
+    /// 任意长度的纯数字版本号
+    Numeric,
+    /// Arch Linux 软件包版本号
+    Pacman,
+    /// Gentoo 软件包版本号
+    Portage,
+    /// Composer 版本号
+    Composer,
+}
+
+///
+/// 某一种方案下的版本号
+#[derive(Debug, Clone)]
+pub enum AnyVersion {
+    Semver(crate::Version),
+    Go(go::Version),
+    Pep440(pep440::Version),
+    Maven(maven::Version),
+    NuGet(nuget::Version),
+    RubyGems(rubygems::Version),
+    Debian(debian::Version),
+    Rpm(rpm::Version),
+    Apk(apk::Version),
+    CalVer(calver::Version),
+    Numeric(numeric::Version),
+    Pacman(pacman::Version),
+    Portage(portage::Version),
+    Composer(composer::Version),
+}
+
+///
+/// 识别得到的一种解释及其置信度
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    version: AnyVersion,
+    confidence: f64,
+}
+
+///
+/// 比较不同方案的版本号时返回的错误
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("无法比较 {left} 版本号与 {right} 版本号")]
+pub struct SchemeMismatch {
+    /// 左侧版本号的方案
+    pub left: Scheme,
+    /// 右侧版本号的方案
+    pub right: Scheme,
+}
+
+/// 尝试的日历版本号格式, 排在前面的优先
+const CALVER_FORMATS: [&str; 7] = [
+    "YYYY.0M.0D",
+    "YYYY.0M.MICRO",
+    "YYYY.MM.MICRO",
+    "YYYY.0M",
+    "YYYY.MM",
+    "0Y.0M.MICRO",
+    "0Y.0M",
+];
+
+impl Scheme {
+
+    /// 方案的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            Scheme::Semver => "semver",
+            Scheme::Go => "go",
+            Scheme::Pep440 => "pep440",
+            Scheme::Maven => "maven",
+            Scheme::NuGet => "nuget",
+            Scheme::RubyGems => "rubygems",
+            Scheme::Debian => "debian",
+            Scheme::Rpm => "rpm",
+            Scheme::Apk => "apk",
+            Scheme::CalVer => "calver",
+            Scheme::Numeric => "numeric",
+            Scheme::Pacman => "pacman",
+            Scheme::Portage => "portage",
+            Scheme::Composer => "composer",
+        }
+    }
+}
+
+impl AnyVersion {
+
+    /// 版本号所属的方案
+    pub fn scheme(&self) -> Scheme {
+        match self {
+            AnyVersion::Semver(_) => Scheme::Semver,
+            AnyVersion::Go(_) => Scheme::Go,
+            AnyVersion::Pep440(_) => Scheme::Pep440,
+            AnyVersion::Maven(_) => Scheme::Maven,
+            AnyVersion::NuGet(_) => Scheme::NuGet,
+            AnyVersion::RubyGems(_) => Scheme::RubyGems,
+            AnyVersion::Debian(_) => Scheme::Debian,
+            AnyVersion::Rpm(_) => Scheme::Rpm,
+            AnyVersion::Apk(_) => Scheme::Apk,
+            AnyVersion::CalVer(_) => Scheme::CalVer,
+            AnyVersion::Numeric(_) => Scheme::Numeric,
+            AnyVersion::Pacman(_) => Scheme::Pacman,
+            AnyVersion::Portage(_) => Scheme::Portage,
+            AnyVersion::Composer(_) => Scheme::Composer,
+        }
+    }
+
+    /// 取置信度最高的解释, 所有方案都无法解析时返回 `None`
+    pub fn detect(s: &str) -> Option<AnyVersion> {
+        detect(s).into_iter().next().map(Candidate::into_version)
+    }
+
+    /// 比较两个版本号, 方案不同时返回错误
+    pub fn try_cmp(&self, other: &AnyVersion) -> Result<Ordering, SchemeMismatch> {
+        Ok(match (self, other) {
+            (AnyVersion::Semver(a), AnyVersion::Semver(b)) => a.cmp(b),
+            (AnyVersion::Go(a), AnyVersion::Go(b)) => a.cmp(b),
+            (AnyVersion::Pep440(a), AnyVersion::Pep440(b)) => a.cmp(b),
+            (AnyVersion::Maven(a), AnyVersion::Maven(b)) => a.cmp(b),
+            (AnyVersion::NuGet(a), AnyVersion::NuGet(b)) => a.cmp(b),
+            (AnyVersion::RubyGems(a), AnyVersion::RubyGems(b)) => a.cmp(b),
+            (AnyVersion::Debian(a), AnyVersion::Debian(b)) => a.cmp(b),
+            (AnyVersion::Rpm(a), AnyVersion::Rpm(b)) => a.cmp(b),
+            (AnyVersion::Apk(a), AnyVersion::Apk(b)) => a.cmp(b),
+            (AnyVersion::CalVer(a), AnyVersion::CalVer(b)) => a.cmp(b),
+            (AnyVersion::Numeric(a), AnyVersion::Numeric(b)) => a.cmp(b),
+            (AnyVersion::Pacman(a), AnyVersion::Pacman(b)) => a.cmp(b),
+            (AnyVersion::Portage(a), AnyVersion::Portage(b)) => a.cmp(b),
+            (AnyVersion::Composer(a), AnyVersion::Composer(b)) => a.cmp(b),
+            _ => return Err(SchemeMismatch { left: self.scheme(), right: other.scheme() }),
+        })
+    }
+}
+
+impl Candidate {
+
+    /// 解析得到的版本号
+    pub fn version(&self) -> &AnyVersion {
+        &self.version
+    }
+
+    /// 置信度, 范围为 0 到 1
+    pub fn confidence(&self) -> f64 {
+        self.confidence
+    }
+
+    /// 取出版本号
+    pub fn into_version(self) -> AnyVersion {
+        self.version
+    }
+}
+
+/// 方案相同且比较结果相等时才相等
+impl PartialEq for AnyVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.try_cmp(other) == Ok(Ordering::Equal)
+    }
+}
+
+/// 方案不同时返回 `None`, 因此 `<`、`>` 等运算符都为 `false`
+impl PartialOrd for AnyVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.try_cmp(other).ok()
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Display for AnyVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AnyVersion::Semver(v) => v.fmt(f),
+            AnyVersion::Go(v) => v.fmt(f),
+            AnyVersion::Pep440(v) => v.fmt(f),
+            AnyVersion::Maven(v) => v.fmt(f),
+            AnyVersion::NuGet(v) => v.fmt(f),
+            AnyVersion::RubyGems(v) => v.fmt(f),
+            AnyVersion::Debian(v) => v.fmt(f),
+            AnyVersion::Rpm(v) => v.fmt(f),
+            AnyVersion::Apk(v) => v.fmt(f),
+            AnyVersion::CalVer(v) => v.fmt(f),
+            AnyVersion::Numeric(v) => v.fmt(f),
+            AnyVersion::Pacman(v) => v.fmt(f),
+            AnyVersion::Portage(v) => v.fmt(f),
+            AnyVersion::Composer(v) => v.fmt(f),
+        }
+    }
+}
+
+/// 识别版本号可能属于的方案, 按置信度从高到低返回所有解析成功的解释
+///
+/// 置信度相同时按 [`Scheme`] 的声明顺序排列。输入为空或所有方案都无法解析时返回空列表
+///
+/// ```
+/// use version::any::{detect, Scheme};
+///
+/// let candidates = detect("130.0.6723.91");
+/// assert_eq!(candidates[0].version().scheme(), Scheme::Numeric);
+/// assert!(candidates.iter().any(|c| c.version().scheme() == Scheme::NuGet));
+/// assert!(detect("").is_empty());
+/// ```
+pub fn detect(s: &str) -> Vec<Candidate> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Vec::new()
+    }
+    // 纯数字时的部分数, 不是纯数字时为 0
+    let numeric_parts = match s.split('.').all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit())) {
+        true => s.split('.').count(),
+        false => 0,
+    };
+
+    let mut candidates = Vec::new();
+    let mut push = |version: AnyVersion, confidence: f64| candidates.push(Candidate { version, confidence });
+
+    if let Ok(v) = s.parse::<crate::Version>() {
+        let full = s.split(['-', '+']).next().unwrap_or(s).split('.').count() == 3;
+        let labeled = !v.pre.is_empty() || !v.build.is_empty();
+        let confidence = match (full, labeled) {
+            (true, true) => 0.9,
+            (true, false) => 0.7,
+            (false, true) => 0.6,
+            (false, false) => 0.4,
+        };
+        push(AnyVersion::Semver(v), confidence);
+    }
+    if let Ok(v) = s.parse::<go::Version>() {
+        push(AnyVersion::Go(v), 0.95);
+    }
+    if let Ok(v) = s.parse::<pep440::Version>() {
+        // 先行版本号在其他方案中也很常见, 只略微提高置信度
+        let marked = v.epoch() > 0 || v.post().is_some() || v.dev().is_some() || !v.local().is_empty();
+        let confidence = match numeric_parts {
+            _ if marked => 0.85,
+            _ if v.pre().is_some() => 0.65,
+            3 => 0.6,
+            0 => 0.3,
+            _ => 0.5,
+        };
+        push(AnyVersion::Pep440(v), confidence);
+    }
+    // Maven 几乎接受任何字符串, 只考虑以数字开头的输入
+    if s.starts_with(|c: char| c.is_ascii_digit()) && let Ok(v) = s.parse::<maven::Version>() {
+        let upper = s.to_ascii_uppercase();
+        let marked = upper.contains("SNAPSHOT") || [".FINAL", ".RELEASE", ".GA"].iter().any(|q| upper.ends_with(q));
+        push(AnyVersion::Maven(v), if marked { 0.9 } else { 0.2 });
+    }
+    if let Ok(v) = s.parse::<nuget::Version>() {
+        push(AnyVersion::NuGet(v), if numeric_parts == 4 { 0.6 } else { 0.3 });
+    }
+    if let Ok(v) = s.parse::<rubygems::Version>() {
+        let lower = s.to_ascii_lowercase();
+        let marked = [".pre", ".rc", ".beta", ".alpha"].iter().any(|q| lower.contains(q));
+        push(AnyVersion::RubyGems(v), if marked { 0.7 } else { 0.3 });
+    }
+    if let Ok(v) = s.parse::<debian::Version>() {
+        let marked = s.contains('~') || v.epoch() > 0
+            || ["ubuntu", "deb", "dfsg"].iter().any(|q| v.revision().contains(q));
+        let confidence = match v.revision() {
+            _ if marked => 0.95,
+            "" => 0.3,
+            _ => 0.5,
+        };
+        push(AnyVersion::Debian(v), confidence);
+    }
+    if let Ok(v) = s.parse::<rpm::Version>() {
+        let marked = s.contains('^') || [".el", ".fc", ".mga", ".amzn", ".suse"].iter().any(|q| v.release().contains(q));
+        let confidence = match v.release() {
+            _ if marked => 0.95,
+            "" => 0.25,
+            _ => 0.4,
+        };
+        push(AnyVersion::Rpm(v), confidence);
+    }
+    if let Ok(v) = s.parse::<apk::Version>() {
+        let marked = v.revision().is_some() || !v.suffixes().is_empty();
+        push(AnyVersion::Apk(v), if marked { 0.9 } else { 0.3 });
+    }
+    // 年份检查放在格式搜索之内, 某个格式的年份不合理时继续尝试之后的格式
+    if let Some((format, v)) = CALVER_FORMATS.iter().find_map(|f| {
+        let format: calver::Format = f.parse().ok()?;
+        let v = calver::Version::parse(s, &format).ok()?;
+        (1990..=2100).contains(&v.year()).then_some((*f, v))
+    }) {
+        let confidence = match format {
+            "YYYY.0M.0D" => 0.9,
+            _ if format.starts_with("YYYY") => 0.8,
+            _ => 0.55,
+        };
+        push(AnyVersion::CalVer(v), confidence);
+    }
+    if let Ok(v) = s.parse::<numeric::Version>() {
+        push(AnyVersion::Numeric(v), if numeric_parts >= 4 { 0.75 } else { 0.45 });
+    }
+    // pacman 几乎接受任何字符串, 只有 `1.0.r12.g3abc` 这样的 VCS 版本号才是明显的标志
+    if let Ok(v) = s.parse::<pacman::Version>() {
+        let confidence = if is_vcs_pkgver(v.pkgver()) { 0.9 } else { 0.2 };
+        push(AnyVersion::Pacman(v), confidence);
+    }
+    // Gentoo 的后缀与修订号写法与 Alpine 相同, 置信度略低于 apk
+    if let Ok(v) = s.parse::<portage::Version>() {
+        let marked = v.revision() > 0 || !v.suffixes().is_empty();
+        push(AnyVersion::Portage(v), if marked { 0.85 } else { 0.25 });
+    }
+    if let Ok(v) = s.parse::<composer::Version>() {
+        let marked = v.is_dev_branch() || s.to_ascii_lowercase().ends_with("-dev");
+        push(AnyVersion::Composer(v), if marked { 0.9 } else { 0.2 });
+    }
+
+    // sort_by 是稳定排序, 置信度相同的保持声明顺序
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    candidates
+}
+
+/// pkgver 中是否有 makepkg 为 git 源生成的 `r<提交数>.g<哈希>` 两段
+fn is_vcs_pkgver(pkgver: &str) -> bool {
+    let parts: Vec<&str> = pkgver.split('.').collect();
+    parts.windows(2).any(|w| {
+        let count = w[0].strip_prefix('r').is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+        let hash = w[1].strip_prefix('g').is_some_and(|h| h.len() >= 4 && h.bytes().all(|b| b.is_ascii_hexdigit()));
+        count && hash
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{detect, AnyVersion, Scheme, SchemeMismatch};
+    use std::cmp::Ordering;
+
+    /// 测试各方案特有的写法被识别为最可能的解释
+    #[test]
+    fn test_detect() {
+        let cases = [
+            ("1.2.3", Scheme::Semver),
+            ("1.2.3-rc.1+build.5", Scheme::Semver),
+            ("v1.2.4-0.20191109021931-daa7c04131f5", Scheme::Go),
+            ("1!2.0.post1.dev3", Scheme::Pep440),
+            ("2.0a1", Scheme::Pep440),
+            ("1.0-SNAPSHOT", Scheme::Maven),
+            ("2.7.0.rc1", Scheme::RubyGems),
+            ("1:2.3~rc1-1ubuntu2", Scheme::Debian),
+            ("2.36-9+deb12u4", Scheme::Debian),
+            ("1.0.0-1.el9", Scheme::Rpm),
+            ("1.0^git3-1.fc40", Scheme::Rpm),
+            ("1.2.3_rc1-r4", Scheme::Apk),
+            ("2024.05.17", Scheme::CalVer),
+            ("2026.10.3", Scheme::CalVer),
+            ("130.0.6723.91", Scheme::Numeric),
+            ("1.0.r12.g3abc7d-1", Scheme::Pacman),
+            ("dev-master", Scheme::Composer),
+            ("2.1.x-dev", Scheme::Composer),
+        ];
+        for (input, expected) in cases {
+            let candidates = detect(input);
+            assert_eq!(candidates[0].version().scheme(), expected, "{input}: {candidates:?}");
+            assert!(candidates.windows(2).all(|w| w[0].confidence() >= w[1].confidence()), "{input}");
+            assert!(candidates.iter().all(|c| (0.0..=1.0).contains(&c.confidence())), "{input}");
+        }
+        assert!(detect("  ").is_empty());
+        assert!(AnyVersion::detect("not a version!").is_none());
+
+        // Gentoo 与 Alpine 的写法相同时优先 apk, 但 portage 仍作为候选
+        let candidates = detect("1.2.3_rc1-r4");
+        assert!(candidates.iter().any(|c| c.version().scheme() == Scheme::Portage));
+
+        // 超出年份范围的纯数字输入不能使 CalVer 的识别溢出
+        for input in ["18446744073709551615.1", "18446744073709551615.01"] {
+            let candidates = detect(input);
+            assert!(!candidates.is_empty(), "{input}");
+            assert!(candidates.iter().all(|c| c.version().scheme() != Scheme::CalVer), "{input}: {candidates:?}");
+            assert!(AnyVersion::detect(input).is_some(), "{input}");
+        }
+    }
+
+    /// 测试不同方案的版本号拒绝比较
+    #[test]
+    fn test_compare() {
+        let v = |s: &str| AnyVersion::detect(s).unwrap();
+        assert_eq!(v("1.2.3").try_cmp(&v("1.10.0")), Ok(Ordering::Less));
+        assert_eq!(v("1.0~rc1-1").try_cmp(&v("1.0-1ubuntu1")), Ok(Ordering::Less));
+        assert_eq!(v("1.0.0"), v("1.0.0"));
+
+        let (semver, calver) = (v("1.2.3"), v("2024.05.17"));
+        assert_eq!(semver.try_cmp(&calver), Err(SchemeMismatch { left: Scheme::Semver, right: Scheme::CalVer }));
+        assert_eq!(semver.partial_cmp(&calver), None);
+        assert!(semver != calver && !semver.lt(&calver) && !semver.gt(&calver));
+        assert_eq!(semver.try_cmp(&calver).unwrap_err().to_string(), "无法比较 semver 版本号与 calver 版本号");
+        assert_eq!(calver.to_string(), "2024.05.17");
+    }
+}
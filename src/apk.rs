@@ -0,0 +1,420 @@
+//! Alpine Linux `apk` 软件包版本号, 格式为 `数字{.数字}[字母]{_后缀[数字]}[~提交哈希][-r修订号]`
+//!
+//! 比较规则与 apk-tools 一致: 数字段按数值比较, 第一段之后以 `0` 开头的数字段先比较前导零的个数(越多越旧);
+//! `_alpha`、`_beta`、`_pre`、`_rc` 早于正式版本, `_cvs`、`_svn`、`_git`、`_hg`、`_p` 晚于正式版本;
+//! 提交哈希不参与比较
+//!
+//! ```
+//! use version::apk::Version;
+//!
+//! let rc: Version = "1.2.3_rc1-r4".parse().unwrap();
+//! assert_eq!(rc.revision(), Some(4));
+//! assert!(rc < "1.2.3-r0".parse().unwrap());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 apk 版本号
+#[derive(Debug, Clone)]
+pub struct Version {
+    numbers: Vec<String>,
+    letter: Option<char>,
+    suffixes: Vec<(Suffix, Option<u64>)>,
+    commit: Option<String>,
+    revision: Option<u64>,
+}
+
+///
+/// 版本号后缀, 按照从旧到新的顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suffix {
+    /// `_alpha`
+    Alpha,
+    /// `_beta`
+    Beta,
+    /// `_pre`
+    Pre,
+    /// `_rc`
+    Rc,
+    /// `_cvs`
+    Cvs,
+    /// `_svn`
+    Svn,
+    /// `_git`
+    Git,
+    /// `_hg`
+    Hg,
+    /// `_p`
+    P,
+}
+
+///
+/// 解析 apk 版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("版本号及 `.` 之后必须是数字")]
+    ExpectedDigit,
+
+    #[error("未知的后缀 \"_{0}\"")]
+    InvalidSuffix(String),
+
+    #[error("提交哈希不能为空, 且只能包含小写十六进制数字")]
+    InvalidCommitHash,
+
+    #[error("修订号必须是 `-r` 之后的数字")]
+    InvalidRevision,
+
+    #[error("数字过大, 不能超过 {}", u64::MAX)]
+    Overflow,
+
+    #[error("版本号中存在意外的字符 '{0}'")]
+    UnexpectedCharacter(char),
+}
+
+/// 参与比较的记号, 变体的顺序即 apk-tools 中记号类型的顺序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Token {
+    /// 数字段; 以 `0` 开头的数字段拆为前导零个数的相反数与剩余的数值两个记号
+    Digit(i128),
+    Letter(char),
+    Suffix(Suffix),
+    SuffixNumber(u64),
+    Revision(u64),
+    End,
+}
+
+impl Suffix {
+
+    /// 是否早于正式版本
+    pub fn is_prerelease(self) -> bool {
+        self <= Suffix::Rc
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Suffix::Alpha => "alpha",
+            Suffix::Beta => "beta",
+            Suffix::Pre => "pre",
+            Suffix::Rc => "rc",
+            Suffix::Cvs => "cvs",
+            Suffix::Svn => "svn",
+            Suffix::Git => "git",
+            Suffix::Hg => "hg",
+            Suffix::P => "p",
+        }
+    }
+}
+
+impl Version {
+
+    /// 以 `.` 分隔的数字段, 保留书写时的前导零
+    pub fn numbers(&self) -> &[String] {
+        &self.numbers
+    }
+
+    /// 数字段之后的单个字母
+    pub fn letter(&self) -> Option<char> {
+        self.letter
+    }
+
+    /// 后缀及其数字, 未写数字时为 `None`, 比较时视为 0
+    pub fn suffixes(&self) -> &[(Suffix, Option<u64>)] {
+        &self.suffixes
+    }
+
+    /// `~` 之后的提交哈希
+    pub fn commit_hash(&self) -> Option<&str> {
+        self.commit.as_deref()
+    }
+
+    /// `-r` 之后的修订号
+    pub fn revision(&self) -> Option<u64> {
+        self.revision
+    }
+
+    /// 是否带有早于正式版本的后缀
+    pub fn is_prerelease(&self) -> bool {
+        self.suffixes.iter().any(|(suffix, _)| suffix.is_prerelease())
+    }
+
+    fn tokens(&self) -> Vec<Token> {
+        let mut tokens = Vec::new();
+        for (i, number) in self.numbers.iter().enumerate() {
+            let zeros = number.bytes().take_while(|b| *b == b'0').count();
+            if i > 0 && zeros > 0 {
+                tokens.push(Token::Digit(-(zeros as i128)));
+                if zeros == number.len() {
+                    continue;
+                }
+            }
+            // 解析时已经检查过不会溢出
+            tokens.push(Token::Digit(number[zeros.min(number.len() - 1)..].parse().unwrap_or_default()));
+        }
+        tokens.extend(self.letter.map(Token::Letter));
+        for (suffix, number) in &self.suffixes {
+            tokens.push(Token::Suffix(*suffix));
+            tokens.push(Token::SuffixNumber(number.unwrap_or(0)));
+        }
+        tokens.extend(self.revision.map(Token::Revision));
+        tokens.push(Token::End);
+        tokens
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 与 apk-tools 的 `apk_version_compare` 一致
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        for (a, b) in self.tokens().iter().zip(&other.tokens()) {
+            if a == b {
+                if *a == Token::End {
+                    return Ordering::Equal
+                }
+                continue;
+            }
+            if mem::discriminant(a) == mem::discriminant(b) {
+                return a.cmp(b)
+            }
+            // 记号类型不同时, 先行版本后缀更旧, 否则先结束的一方更旧
+            if matches!(a, Token::Suffix(s) if s.is_prerelease()) {
+                return Ordering::Less
+            }
+            if matches!(b, Token::Suffix(s) if s.is_prerelease()) {
+                return Ordering::Greater
+            }
+            return b.cmp(a)
+        }
+        Ordering::Equal
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 记号序列相同时才相等
+        self.tokens().hash(state);
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.numbers.join("."))?;
+        if let Some(letter) = self.letter {
+            write!(f, "{letter}")?;
+        }
+        for (suffix, number) in &self.suffixes {
+            write!(f, "_{}", suffix.as_str())?;
+            if let Some(number) = number {
+                write!(f, "{number}")?;
+            }
+        }
+        if let Some(commit) = &self.commit {
+            write!(f, "~{commit}")?;
+        }
+        if let Some(revision) = self.revision {
+            write!(f, "-r{revision}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 apk 版本号, 与 apk-tools 的 `apk_version_validate` 接受的格式一致
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+
+        let (rest, revision) = match s.rsplit_once("-r") {
+            Some((rest, revision)) => {
+                if revision.is_empty() || !revision.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseError::InvalidRevision)
+                }
+                (rest, Some(revision.parse().map_err(|_| ParseError::Overflow)?))
+            }
+            None => (s, None),
+        };
+        let (rest, commit) = match rest.split_once('~') {
+            Some((rest, commit)) => {
+                if commit.is_empty() || !commit.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b)) {
+                    return Err(ParseError::InvalidCommitHash)
+                }
+                (rest, Some(commit.to_string()))
+            }
+            None => (rest, None),
+        };
+
+        let mut rest = rest;
+        let mut numbers = Vec::new();
+        loop {
+            let len = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+            if len == 0 {
+                return Err(ParseError::ExpectedDigit)
+            }
+            let number = &rest[..len];
+            number.trim_start_matches('0').parse::<u64>().or_else(|_| {
+                if number.bytes().all(|b| b == b'0') { Ok(0) } else { Err(ParseError::Overflow) }
+            })?;
+            numbers.push(number.to_string());
+            rest = &rest[len..];
+            match rest.strip_prefix('.') {
+                Some(next) => rest = next,
+                None => break,
+            }
+        }
+
+        let letter = rest.chars().next().filter(char::is_ascii_lowercase);
+        if letter.is_some() {
+            rest = &rest[1..];
+        }
+
+        let mut suffixes = Vec::new();
+        while let Some(next) = rest.strip_prefix('_') {
+            let name_len = next.bytes().take_while(u8::is_ascii_lowercase).count();
+            let suffix = match &next[..name_len] {
+                "alpha" => Suffix::Alpha,
+                "beta" => Suffix::Beta,
+                "pre" => Suffix::Pre,
+                "rc" => Suffix::Rc,
+                "cvs" => Suffix::Cvs,
+                "svn" => Suffix::Svn,
+                "git" => Suffix::Git,
+                "hg" => Suffix::Hg,
+                "p" => Suffix::P,
+                name => return Err(ParseError::InvalidSuffix(name.to_string())),
+            };
+            let next = &next[name_len..];
+            let digits = next.bytes().take_while(|b| b.is_ascii_digit()).count();
+            let number = match digits {
+                0 => None,
+                _ => Some(next[..digits].parse().map_err(|_| ParseError::Overflow)?),
+            };
+            suffixes.push((suffix, number));
+            rest = &next[digits..];
+        }
+
+        if let Some(c) = rest.chars().next() {
+            return Err(ParseError::UnexpectedCharacter(c))
+        }
+        Ok(Version { numbers, letter, suffixes, commit, revision })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Suffix, Version};
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 比较结果参照 apk-tools 的 test/version.data
+    #[test]
+    fn test_compare_table() {
+        let cases = [
+            ("2.34", "0.1.0_alpha", Ordering::Greater),
+            ("0.1.0_alpha", "0.1.0_alpha", Ordering::Equal),
+            ("0.1.0_alpha", "0.1.3_alpha", Ordering::Less),
+            ("0.1.0_alpha2", "0.1.0_alpha", Ordering::Greater),
+            ("0.1.0_alpha", "1.2.3", Ordering::Less),
+            ("1.2", "1.2.3", Ordering::Less),
+            ("1.2.3", "1.2.4", Ordering::Less),
+            ("1.2.10", "1.2.9", Ordering::Greater),
+            ("1.2.3_rc1", "1.2.3", Ordering::Less),
+            ("1.2.3_p1", "1.2.3", Ordering::Greater),
+            ("1.2.3-r1", "1.2.3", Ordering::Greater),
+            ("1.2.3-r1", "1.2.3-r10", Ordering::Less),
+            ("1.2.3a", "1.2.3", Ordering::Greater),
+            ("1.2.3a", "1.2.3b", Ordering::Less),
+            ("1.2.3a", "1.2.3.1", Ordering::Less),
+            ("1.2.3_rc", "1.2.3_rc0", Ordering::Equal),
+            ("1.2.3_rc1-r4", "1.2.3-r0", Ordering::Less),
+            ("1.2.3_alpha_p1", "1.2.3_alpha", Ordering::Greater),
+            ("1.2.3_alpha_beta", "1.2.3_alpha", Ordering::Less),
+            ("1.01", "1.1", Ordering::Less),
+            ("1.001", "1.01", Ordering::Less),
+            ("01.1", "1.1", Ordering::Equal),
+            ("1.0~abc123", "1.0~def", Ordering::Equal),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(v(a).cmp(&v(b)), expected, "{a} <=> {b}");
+            assert_eq!(v(b).cmp(&v(a)), expected.reverse(), "{b} <=> {a}");
+        }
+    }
+
+    /// 测试后缀的顺序
+    #[test]
+    fn test_suffix_order() {
+        let ordered = ["1.0_alpha", "1.0_beta", "1.0_pre", "1.0_rc", "1.0", "1.0_cvs", "1.0_svn", "1.0_git", "1.0_hg", "1.0_p"];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        let version = v("1.2.3b_alpha4_p~0f3a-r2");
+        assert_eq!(version.numbers(), ["1", "2", "3"]);
+        assert_eq!(version.letter(), Some('b'));
+        assert_eq!(version.suffixes(), [(Suffix::Alpha, Some(4)), (Suffix::P, None)]);
+        assert_eq!((version.commit_hash(), version.revision()), (Some("0f3a"), Some(2)));
+        assert!(version.is_prerelease());
+        assert_eq!(version.to_string(), "1.2.3b_alpha4_p~0f3a-r2");
+
+        let set: HashSet<Version> = ["1.0_rc-r1", "01.0_rc0-r01", "1.0_rc~ff-r1"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    /// 测试解析错误
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            ("a1", ParseError::ExpectedDigit),
+            ("1..2", ParseError::ExpectedDigit),
+            ("1.2.", ParseError::ExpectedDigit),
+            ("1.2_foo", ParseError::InvalidSuffix("foo".to_string())),
+            ("1.2~", ParseError::InvalidCommitHash),
+            ("1.2~XYZ", ParseError::InvalidCommitHash),
+            ("1.2-r", ParseError::InvalidRevision),
+            ("1.2-rc1", ParseError::InvalidRevision),
+            ("1.99999999999999999999", ParseError::Overflow),
+            ("1.2ab", ParseError::UnexpectedCharacter('b')),
+            ("1.2-1", ParseError::UnexpectedCharacter('-')),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+    }
+}
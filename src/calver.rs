@@ -0,0 +1,687 @@
+//! 日历版本号([CalVer](https://calver.org/)), 例如 `2026.10.3`、`26.04`、`2026.10.18-hotfix`
+//!
+//! 版本号的格式由 [`Format`] 描述, 支持以下标记, 标记之间用 `.`、`-` 或 `_` 分隔:
+//!
+//! | 标记 | 含义 | 示例 |
+//! |------|------|------|
+//! | `YYYY` | 完整年份 | 2006, 2026 |
+//! | `YY` | 年份减去 2000, 不补零 | 6, 26, 106 |
+//! | `0Y` | 年份减去 2000, 补零到两位 | 06, 26, 106 |
+//! | `MM` / `0M` | 月份, 不补零 / 补零 | 1, 12 / 01, 12 |
+//! | `WW` / `0W` | 一年中的第几周(从 1 月 1 日起每 7 天为一周), 不补零 / 补零 | 1, 53 / 01, 53 |
+//! | `DD` / `0D` | 日期, 不补零 / 补零 | 1, 31 / 01, 31 |
+//! | `MICRO` | 同一日期内递增的序号, 从 0 开始 | 0, 3 |
+//! | `MODIFIER` | 可选的后缀, 只能位于末尾 | hotfix, rc1 |
+//!
+//! ```
+//! use version::calver::{Date, Format, Version};
+//!
+//! let format: Format = "YYYY.0M.MICRO".parse().unwrap();
+//! let v = Version::parse("2026.10.3", &format).unwrap();
+//! assert_eq!(v.date(), Date::new(2026, 10, 1).unwrap());
+//!
+//! // 同一个月内递增 MICRO, 进入下个月后重新从 0 开始
+//! assert_eq!(v.next(Date::new(2026, 10, 18).unwrap()).unwrap().to_string(), "2026.10.4");
+//! assert_eq!(v.next(Date::new(2026, 11, 2).unwrap()).unwrap().to_string(), "2026.11.0");
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 日历版本号的格式, 例如 `YYYY.0M.MICRO`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Format {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Part {
+    Token(Token),
+    Separator(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Token {
+    FullYear,
+    ShortYear,
+    PaddedYear,
+    Month,
+    PaddedMonth,
+    Week,
+    PaddedWeek,
+    Day,
+    PaddedDay,
+    Micro,
+    Modifier,
+}
+
+///
+/// 公历日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+///
+/// 表示一个日历版本号
+///
+/// 版本号之间依次按年、月、周、日、`MICRO` 比较, 最后比较后缀,
+/// 没有后缀的版本排在有后缀的版本之前。比较时不考虑格式, 因此 `26.04` 与 `2026.04` 相等
+#[derive(Debug, Clone)]
+pub struct Version {
+    format: Format,
+    year: u16,
+    month: Option<u8>,
+    week: Option<u8>,
+    day: Option<u8>,
+    micro: Option<u64>,
+    modifier: Option<String>,
+}
+
+///
+/// 解析格式或日历版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("无法识别的格式标记 \"{0}\"")]
+    UnknownToken(String),
+
+    #[error("格式标记 \"{0}\" 重复出现")]
+    DuplicateToken(String),
+
+    #[error("格式中缺少年份")]
+    MissingYear,
+
+    #[error("格式中的标记之间缺少分隔符")]
+    MissingSeparator,
+
+    #[error("格式的首尾不能是分隔符, 分隔符也不能连续出现")]
+    InvalidSeparator,
+
+    #[error("MODIFIER 只能位于格式末尾")]
+    ModifierNotLast,
+
+    #[error("格式中的日期必须与月份一起使用")]
+    DayWithoutMonth,
+
+    #[error("格式中的周不能与月份或日期一起使用")]
+    WeekWithMonthOrDay,
+
+    #[error("{token} 处应为 {expected}, 实际为 \"{found}\"")]
+    Mismatch { token: String, expected: &'static str, found: String },
+
+    #[error("日期 {0} 不存在")]
+    InvalidDate(String),
+}
+
+///
+/// 计算下一个版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum NextError {
+
+    #[error("日期 {0} 早于当前版本号的日期")]
+    DateBeforeVersion(Date),
+
+    #[error("日期没有变化, 且格式中没有 MICRO, 无法生成新的版本号")]
+    NoMicro,
+
+    #[error("MICRO 已达到上限 {}, 无法生成新的版本号", u64::MAX)]
+    MicroOverflow,
+}
+
+impl Token {
+
+    /// 按最长匹配的顺序排列, 使 `YYYY` 先于 `YY` 被识别
+    const ALL: [Token; 11] = [
+        Token::FullYear, Token::Modifier, Token::Micro, Token::ShortYear, Token::PaddedYear,
+        Token::Month, Token::PaddedMonth, Token::Week, Token::PaddedWeek, Token::Day, Token::PaddedDay,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Token::FullYear => "YYYY",
+            Token::ShortYear => "YY",
+            Token::PaddedYear => "0Y",
+            Token::Month => "MM",
+            Token::PaddedMonth => "0M",
+            Token::Week => "WW",
+            Token::PaddedWeek => "0W",
+            Token::Day => "DD",
+            Token::PaddedDay => "0D",
+            Token::Micro => "MICRO",
+            Token::Modifier => "MODIFIER",
+        }
+    }
+
+    fn is_year(self) -> bool {
+        matches!(self, Token::FullYear | Token::ShortYear | Token::PaddedYear)
+    }
+
+    fn is_month(self) -> bool {
+        matches!(self, Token::Month | Token::PaddedMonth)
+    }
+
+    fn is_week(self) -> bool {
+        matches!(self, Token::Week | Token::PaddedWeek)
+    }
+
+    fn is_day(self) -> bool {
+        matches!(self, Token::Day | Token::PaddedDay)
+    }
+}
+
+impl Format {
+
+    fn tokens(&self) -> impl Iterator<Item = Token> + '_ {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Token(token) => Some(*token),
+            Part::Separator(_) => None,
+        })
+    }
+
+    fn has(&self, f: fn(Token) -> bool) -> bool {
+        self.tokens().any(f)
+    }
+}
+
+/// 解析格式字符串, 例如 `YYYY.0M.0D-MODIFIER`
+impl FromStr for Format {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Format, ParseError> {
+        let mut parts = Vec::new();
+        let mut rest = s;
+        while !rest.is_empty() {
+            if let Some(c) = rest.chars().next().filter(|c| ".-_".contains(*c)) {
+                if !matches!(parts.last(), Some(Part::Token(_))) {
+                    return Err(ParseError::InvalidSeparator)
+                }
+                parts.push(Part::Separator(c));
+                rest = &rest[1..];
+                continue;
+            }
+            let token = Token::ALL.into_iter().find(|t| rest.starts_with(t.name()))
+                .ok_or_else(|| ParseError::UnknownToken(rest.split(['.', '-', '_']).next().unwrap_or(rest).to_string()))?;
+            if matches!(parts.last(), Some(Part::Token(_))) {
+                return Err(ParseError::MissingSeparator)
+            }
+            parts.push(Part::Token(token));
+            rest = &rest[token.name().len()..];
+        }
+
+        let format = Format { parts };
+        let tokens: Vec<Token> = format.tokens().collect();
+        for (i, token) in tokens.iter().enumerate() {
+            let same_kind = |t: &Token| t == token
+                || (t.is_year() && token.is_year()) || (t.is_month() && token.is_month())
+                || (t.is_week() && token.is_week()) || (t.is_day() && token.is_day());
+            if tokens[..i].iter().any(same_kind) {
+                return Err(ParseError::DuplicateToken(token.name().to_string()))
+            }
+        }
+        if !format.has(Token::is_year) {
+            return Err(ParseError::MissingYear)
+        }
+        if format.has(Token::is_day) && !format.has(Token::is_month) {
+            return Err(ParseError::DayWithoutMonth)
+        }
+        if format.has(Token::is_week) && (format.has(Token::is_month) || format.has(Token::is_day)) {
+            return Err(ParseError::WeekWithMonthOrDay)
+        }
+        let modifier = format.parts.iter().position(|p| *p == Part::Token(Token::Modifier));
+        if modifier.is_some_and(|i| i + 1 != format.parts.len()) {
+            return Err(ParseError::ModifierNotLast)
+        }
+        if matches!(format.parts.last(), Some(Part::Separator(_))) {
+            return Err(ParseError::InvalidSeparator)
+        }
+        Ok(format)
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.parts {
+            match part {
+                Part::Token(token) => f.write_str(token.name())?,
+                Part::Separator(c) => write!(f, "{c}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Date {
+
+    /// 创建日期, 日期不存在时返回 `None`
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Date> {
+        if (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month) {
+            Some(Date { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// 年
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// 月, 1 到 12
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// 日, 从 1 开始
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// 一年中的第几周, 从 1 月 1 日起每 7 天为一周, 取值为 1 到 53
+    pub fn week(&self) -> u8 {
+        ((self.ordinal() - 1) / 7 + 1) as u8
+    }
+
+    /// 一年中的第几天, 从 1 开始
+    fn ordinal(&self) -> u16 {
+        (1..self.month).map(|m| days_in_month(self.year, m) as u16).sum::<u16>() + self.day as u16
+    }
+
+    fn from_ordinal(year: u16, ordinal: u16) -> Option<Date> {
+        let mut rest = ordinal;
+        for month in 1..=12 {
+            let days = days_in_month(year, month) as u16;
+            if rest <= days {
+                return Date::new(year, month, rest as u8)
+            }
+            rest -= days;
+        }
+        None
+    }
+}
+
+/// 以 `YYYY-MM-DD` 形式输出
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    (year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Version {
+
+    /// 按照给定的格式解析日历版本号
+    ///
+    /// 补零的标记必须恰好两位, 不补零的标记不能有前导零, 日期必须真实存在。
+    /// 格式末尾的 `MODIFIER` 可以省略
+    ///
+    /// ```
+    /// use version::calver::{Format, Version};
+    ///
+    /// let format: Format = "YYYY.0M.0D-MODIFIER".parse().unwrap();
+    /// let v = Version::parse("2026.10.18-hotfix", &format).unwrap();
+    /// assert_eq!(v.modifier(), Some("hotfix"));
+    /// assert!(Version::parse("2026.10.18", &format).is_ok());
+    /// assert!(Version::parse("2025.02.29", &format).is_err());
+    /// ```
+    pub fn parse(s: &str, format: &Format) -> Result<Version, ParseError> {
+        let mut version = Version {
+            format: format.clone(),
+            year: 0,
+            month: None,
+            week: None,
+            day: None,
+            micro: None,
+            modifier: None,
+        };
+
+        let mut rest = s.trim();
+        for (i, part) in format.parts.iter().enumerate() {
+            match *part {
+                Part::Separator(c) => {
+                    // 省略末尾的 MODIFIER
+                    if rest.is_empty() && format.parts.get(i + 1) == Some(&Part::Token(Token::Modifier)) {
+                        break;
+                    }
+                    rest = rest.strip_prefix(c).ok_or_else(|| ParseError::Mismatch {
+                        token: format!("'{c}'"),
+                        expected: "分隔符",
+                        found: rest.to_string(),
+                    })?;
+                }
+                Part::Token(Token::Modifier) => {
+                    let valid = !rest.is_empty() && rest.chars().all(|c| c.is_ascii_alphanumeric() || ".-_".contains(c));
+                    if !valid {
+                        return Err(mismatch(Token::Modifier, "由字母数字组成的后缀", rest))
+                    }
+                    version.modifier = Some(rest.to_string());
+                    rest = "";
+                }
+                Part::Token(token) => {
+                    let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+                    let digits = &rest[..len];
+                    rest = &rest[len..];
+                    let (padded, expected) = match token {
+                        Token::FullYear => (len == 4, "四位数字"),
+                        Token::PaddedYear => (len == 2 || (len > 2 && !digits.starts_with('0')), "补零到两位的数字"),
+                        Token::PaddedMonth | Token::PaddedWeek | Token::PaddedDay => (len == 2, "两位数字"),
+                        _ => (len == 1 || (len > 1 && !digits.starts_with('0')), "没有前导零的数字"),
+                    };
+                    let value = digits.parse::<u64>().ok().filter(|_| padded).ok_or_else(|| mismatch(token, expected, digits))?;
+                    let small = |limit: u64| u8::try_from(value).ok().filter(|v| (1..=limit).contains(&(*v as u64)));
+                    match token {
+                        Token::FullYear => version.year = value as u16,
+                        Token::ShortYear | Token::PaddedYear => {
+                            let year = value.checked_add(2000).and_then(|year| u16::try_from(year).ok());
+                            version.year = year.ok_or_else(|| ParseError::InvalidDate(s.to_string()))?;
+                        }
+                        Token::Month | Token::PaddedMonth => {
+                            version.month = Some(small(12).ok_or_else(|| ParseError::InvalidDate(s.to_string()))?);
+                        }
+                        Token::Week | Token::PaddedWeek => {
+                            version.week = Some(small(53).ok_or_else(|| ParseError::InvalidDate(s.to_string()))?);
+                        }
+                        Token::Day | Token::PaddedDay => {
+                            version.day = Some(small(31).ok_or_else(|| ParseError::InvalidDate(s.to_string()))?);
+                        }
+                        Token::Micro => version.micro = Some(value),
+                        Token::Modifier => unreachable!(),
+                    }
+                }
+            }
+        }
+        if !rest.is_empty() {
+            return Err(ParseError::Mismatch { token: "末尾".to_string(), expected: "结束", found: rest.to_string() })
+        }
+
+        if version.try_date().is_none() {
+            return Err(ParseError::InvalidDate(s.to_string()))
+        }
+        Ok(version)
+    }
+
+    /// 版本号的格式
+    pub fn format(&self) -> &Format {
+        &self.format
+    }
+
+    /// 完整年份
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// 月份, 格式中没有月份时为 `None`
+    pub fn month(&self) -> Option<u8> {
+        self.month
+    }
+
+    /// 周, 格式中没有周时为 `None`
+    pub fn week(&self) -> Option<u8> {
+        self.week
+    }
+
+    /// 日期, 格式中没有日期时为 `None`
+    pub fn day(&self) -> Option<u8> {
+        self.day
+    }
+
+    /// `MICRO` 部分, 格式中没有时为 `None`
+    pub fn micro(&self) -> Option<u64> {
+        self.micro
+    }
+
+    /// 后缀
+    pub fn modifier(&self) -> Option<&str> {
+        self.modifier.as_deref()
+    }
+
+    /// 版本号对应的日期
+    ///
+    /// 格式中没有的部分取最早的值: 没有日期时为当月 1 日, 只有周时为该周的第一天,
+    /// 只有年份时为 1 月 1 日
+    pub fn date(&self) -> Date {
+        self.try_date().expect("解析时已经检查过日期")
+    }
+
+    fn try_date(&self) -> Option<Date> {
+        match self.week {
+            Some(week) => Date::from_ordinal(self.year, (week as u16 - 1) * 7 + 1),
+            None => Date::new(self.year, self.month.unwrap_or(1), self.day.unwrap_or(1)),
+        }
+    }
+
+    /// 计算在给定日期发布时的下一个版本号
+    ///
+    /// 日期部分取自 `date`, 如果日期部分与当前版本相同则 `MICRO` 加一, 否则 `MICRO` 归零;
+    /// 新版本不带后缀
+    pub fn next(&self, date: Date) -> Result<Version, NextError> {
+        let mut next = Version {
+            format: self.format.clone(),
+            year: date.year(),
+            month: self.month.map(|_| date.month()),
+            week: self.week.map(|_| date.week()),
+            day: self.day.map(|_| date.day()),
+            micro: self.micro.map(|_| 0),
+            modifier: None,
+        };
+        match cmp_date(&next, self) {
+            Ordering::Less => Err(NextError::DateBeforeVersion(date)),
+            Ordering::Greater => Ok(next),
+            Ordering::Equal => {
+                let micro = self.micro.ok_or(NextError::NoMicro)?;
+                next.micro = Some(micro.checked_add(1).ok_or(NextError::MicroOverflow)?);
+                Ok(next)
+            }
+        }
+    }
+}
+
+fn mismatch(token: Token, expected: &'static str, found: &str) -> ParseError {
+    ParseError::Mismatch { token: token.name().to_string(), expected, found: found.to_string() }
+}
+
+fn cmp_date(a: &Version, b: &Version) -> Ordering {
+    (a.year, a.month, a.week, a.day).cmp(&(b.year, b.month, b.week, b.day))
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_date(self, other)
+            .then_with(|| self.micro.cmp(&other.micro))
+            .then_with(|| self.modifier.cmp(&other.modifier))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.year, self.month, self.week, self.day, self.micro, &self.modifier).hash(state);
+    }
+}
+
+/// 按照版本号自身的格式输出
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, part) in self.format.parts.iter().enumerate() {
+            match *part {
+                Part::Separator(c) => {
+                    if self.modifier.is_none() && self.format.parts.get(i + 1) == Some(&Part::Token(Token::Modifier)) {
+                        break;
+                    }
+                    write!(f, "{c}")?;
+                }
+                Part::Token(token) => {
+                    let short_year = self.year.saturating_sub(2000);
+                    let value = |v: Option<u8>| v.unwrap_or_default();
+                    match token {
+                        Token::FullYear => write!(f, "{:04}", self.year)?,
+                        Token::ShortYear => write!(f, "{short_year}")?,
+                        Token::PaddedYear => write!(f, "{short_year:02}")?,
+                        Token::Month => write!(f, "{}", value(self.month))?,
+                        Token::PaddedMonth => write!(f, "{:02}", value(self.month))?,
+                        Token::Week => write!(f, "{}", value(self.week))?,
+                        Token::PaddedWeek => write!(f, "{:02}", value(self.week))?,
+                        Token::Day => write!(f, "{}", value(self.day))?,
+                        Token::PaddedDay => write!(f, "{:02}", value(self.day))?,
+                        Token::Micro => write!(f, "{}", self.micro.unwrap_or_default())?,
+                        Token::Modifier => f.write_str(self.modifier.as_deref().unwrap_or_default())?,
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, Format, NextError, ParseError, Version};
+
+    fn format(s: &str) -> Format {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    fn v(s: &str, f: &str) -> Version {
+        Version::parse(s, &format(f)).unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    fn date(y: u16, m: u8, d: u8) -> Date {
+        Date::new(y, m, d).unwrap()
+    }
+
+    /// 测试按格式解析与输出
+    #[test]
+    fn test_parse() {
+        let cases = [
+            ("2026.10.3", "YYYY.0M.MICRO", date(2026, 10, 1)),
+            ("26.04", "YY.0M", date(2026, 4, 1)),
+            ("26.4.1", "YY.MM.MICRO", date(2026, 4, 1)),
+            ("06.1", "0Y.MM", date(2006, 1, 1)),
+            ("2026.10.18-hotfix", "YYYY.0M.0D-MODIFIER", date(2026, 10, 18)),
+            ("2026.10.18", "YYYY.0M.0D-MODIFIER", date(2026, 10, 18)),
+            ("2024.02.29", "YYYY.0M.0D", date(2024, 2, 29)),
+            ("2026.42", "YYYY.WW", date(2026, 10, 15)),
+            ("2026.01_2", "YYYY.0W_MICRO", date(2026, 1, 1)),
+            ("2026", "YYYY", date(2026, 1, 1)),
+        ];
+        for (input, f, expected) in cases {
+            let version = v(input, f);
+            assert_eq!(version.date(), expected, "{input}");
+            assert_eq!(version.to_string(), input);
+            assert_eq!(version.format().to_string(), f);
+        }
+
+        let version = v("2026.10.18-rc.1", "YYYY.MM.DD-MODIFIER");
+        assert_eq!((version.year(), version.month(), version.day()), (2026, Some(10), Some(18)));
+        assert_eq!((version.week(), version.micro(), version.modifier()), (None, None, Some("rc.1")));
+        assert_eq!(v("26.04", "YY.0M"), v("2026.4", "YYYY.MM"));
+
+        // 输出的版本号可以按照同一格式重新解析
+        for (input, f) in [("0999.01.01", "YYYY.0M.0D"), ("0042.7", "YYYY.MICRO"), ("06.01", "0Y.0M")] {
+            let version = v(input, f);
+            assert_eq!(version.to_string(), input);
+            assert_eq!(v(&version.to_string(), f), version, "{input}");
+        }
+    }
+
+    /// 测试日期与补零检查
+    #[test]
+    fn test_invalid_versions() {
+        let cases = [
+            ("2026.13.1", "YYYY.MM.MICRO"),
+            ("2026.0.1", "YYYY.MM.MICRO"),
+            ("2025.02.29", "YYYY.0M.0D"),
+            ("2026.04.31", "YYYY.0M.0D"),
+            ("2026.54", "YYYY.WW"),
+            ("2026.1.3", "YYYY.0M.MICRO"),
+            ("2026.01.3", "YYYY.MM.MICRO"),
+            ("26.04", "YYYY.0M"),
+            ("2026.10.03", "YYYY.0M.MICRO"),
+            ("2026.10", "YYYY.0M.MICRO"),
+            ("2026.10.3.1", "YYYY.0M.MICRO"),
+            ("2026.10-", "YYYY.0M-MODIFIER"),
+            ("2026-10", "YYYY.0M"),
+        ];
+        for (input, f) in cases {
+            assert!(Version::parse(input, &format(f)).is_err(), "{input} ~ {f}");
+        }
+        assert_eq!(Version::parse("2025.02.29", &format("YYYY.0M.0D")).unwrap_err(), ParseError::InvalidDate("2025.02.29".to_string()));
+        let huge = "18446744073709551615.1";
+        assert_eq!(Version::parse(huge, &format("YY.MM")).unwrap_err(), ParseError::InvalidDate(huge.to_string()));
+    }
+
+    /// 测试格式检查
+    #[test]
+    fn test_invalid_formats() {
+        let cases = [
+            ("YYYY.MMM", ParseError::UnknownToken("M".to_string())),
+            ("YYYY.0M.0M", ParseError::DuplicateToken("0M".to_string())),
+            ("YYYY.YY", ParseError::DuplicateToken("YY".to_string())),
+            ("0M.MICRO", ParseError::MissingYear),
+            ("YYYY0M", ParseError::MissingSeparator),
+            ("YYYY-MODIFIER.MICRO", ParseError::ModifierNotLast),
+            ("YYYY.0D", ParseError::DayWithoutMonth),
+            ("YYYY.0M.WW", ParseError::WeekWithMonthOrDay),
+            ("YYYY.", ParseError::InvalidSeparator),
+            (".YYYY", ParseError::InvalidSeparator),
+            ("YYYY..0M", ParseError::InvalidSeparator),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Format>().unwrap_err(), expected, "{input}");
+        }
+    }
+
+    /// 测试计算下一个版本号
+    #[test]
+    fn test_next() {
+        let current = v("2026.10.3", "YYYY.0M.MICRO");
+        assert_eq!(current.next(date(2026, 10, 18)).unwrap().to_string(), "2026.10.4");
+        assert_eq!(current.next(date(2027, 1, 1)).unwrap().to_string(), "2027.01.0");
+        assert_eq!(current.next(date(2026, 9, 30)).unwrap_err(), NextError::DateBeforeVersion(date(2026, 9, 30)));
+
+        let current = v("26.04", "YY.0M");
+        assert_eq!(current.next(date(2026, 10, 18)).unwrap().to_string(), "26.10");
+        assert_eq!(current.next(date(2026, 4, 30)).unwrap_err(), NextError::NoMicro);
+
+        let current = v("2026.10.18446744073709551615", "YYYY.0M.MICRO");
+        assert_eq!(current.next(date(2026, 10, 18)).unwrap_err(), NextError::MicroOverflow);
+
+        let current = v("2026.10.18-hotfix", "YYYY.0M.0D-MODIFIER");
+        assert_eq!(current.next(date(2026, 10, 19)).unwrap().to_string(), "2026.10.19");
+
+        let current = v("2026.41.0", "YYYY.WW.MICRO");
+        assert_eq!(current.next(date(2026, 10, 18)).unwrap().to_string(), "2026.42.0");
+        assert!(current.next(date(2026, 10, 18)).unwrap() > current);
+    }
+}
@@ -0,0 +1,524 @@
+//! Composer 版本约束, 例如 `^1.2 || ~2.0.3`、`>=1.0 <1.1`、`1.0.*`、`dev-main` 与 `1.2.3@beta`
+
+use super::{normalize, parse_stability, ParseError, Stability, Version};
+use std::fmt;
+use std::str::FromStr;
+
+///
+/// 由 `||` 连接的若干组约束, 每组内以空格或 `,` 分隔的约束需要同时满足,
+/// 规则与 `VersionParser::parseConstraints` 一致
+///
+/// 约束在解析时展开为比较运算, 例如 `^1.2` 展开为 `>=1.2.0.0-dev <2.0.0.0-dev`。
+/// 挑选候选版本时还会考虑稳定性: `@beta` 这样的显式标记覆盖 `minimum-stability`,
+/// 约束中直接引用的不稳定版本 (例如 `dev-main`、`>=1.0-beta`) 会放宽 `minimum-stability`
+///
+/// ```
+/// use version::composer::{Constraint, Stability, Version};
+///
+/// let candidates: Vec<Version> = ["1.1.0", "1.2.0", "1.3.0-beta1", "2.0.3", "2.1.0"]
+///     .iter().map(|s| s.parse().unwrap()).collect();
+/// let constraint: Constraint = "^1.2 || ~2.0.3".parse().unwrap();
+/// assert_eq!(constraint.best_match(&candidates, Stability::Stable).unwrap().to_string(), "2.0.3.0");
+///
+/// let constraint: Constraint = "^1.2@beta".parse().unwrap();
+/// assert_eq!(constraint.best_match(&candidates, Stability::Stable).unwrap().to_string(), "1.3.0.0-beta1");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    alternatives: Vec<Vec<Comparator>>,
+    explicit_stability: Option<Stability>,
+    inferred_stability: Option<Stability>,
+}
+
+///
+/// 单个比较运算, 例如 `>=1.2.0.0-dev`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    op: Op,
+    version: Version,
+}
+
+///
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `==`, 也写作 `=` 或省略
+    Equal,
+    /// `!=`, 也写作 `<>`
+    NotEqual,
+    /// `<`
+    Less,
+    /// `<=`
+    LessEqual,
+    /// `>`
+    Greater,
+    /// `>=`
+    GreaterEqual,
+}
+
+impl Constraint {
+
+    /// 以 `||` 连接的各组比较运算, 空的一组表示 `*`, 接受任何版本
+    pub fn alternatives(&self) -> &[Vec<Comparator>] {
+        &self.alternatives
+    }
+
+    /// 约束带有的稳定性标记, 显式的 `@flag` 优先, 其次是从引用的不稳定版本推断出的稳定性
+    pub fn stability_flag(&self) -> Option<Stability> {
+        self.explicit_stability.or(self.inferred_stability)
+    }
+
+    /// 结合项目的 `minimum-stability` 计算实际允许的最低稳定性
+    ///
+    /// 显式标记直接覆盖 `minimum-stability`; 推断出的稳定性只会放宽它
+    pub fn minimum_stability(&self, minimum_stability: Stability) -> Stability {
+        match (self.explicit_stability, self.inferred_stability) {
+            (Some(explicit), _) => explicit,
+            (None, Some(inferred)) => inferred.min(minimum_stability),
+            (None, None) => minimum_stability,
+        }
+    }
+
+    /// 判断版本号是否满足约束, 不考虑稳定性
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|group| group.iter().all(|c| c.matches(version)))
+    }
+
+    /// 满足约束且稳定性不低于要求的候选版本, 保持原有顺序
+    pub fn filter<'a, I>(&self, candidates: I, minimum_stability: Stability) -> impl Iterator<Item = &'a Version> + use<'a, '_, I>
+    where
+        I: IntoIterator<Item = &'a Version>,
+    {
+        let minimum_stability = self.minimum_stability(minimum_stability);
+        candidates.into_iter().filter(move |v| v.stability() >= minimum_stability && self.matches(v))
+    }
+
+    /// 满足约束且稳定性不低于要求的最高版本
+    pub fn best_match<'a, I>(&self, candidates: I, minimum_stability: Stability) -> Option<&'a Version>
+    where
+        I: IntoIterator<Item = &'a Version>,
+    {
+        self.filter(candidates, minimum_stability).max()
+    }
+}
+
+impl Comparator {
+
+    /// 运算符
+    pub fn op(&self) -> Op {
+        self.op
+    }
+
+    /// 规范化后的版本号
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// 判断版本号是否满足比较运算, 与 `Constraint::versionCompare` 一致
+    ///
+    /// 分支版本只能用 `==` 与 `!=` 和同名分支比较, 与数字版本比较时除 `!=` 外都不满足
+    pub fn matches(&self, version: &Version) -> bool {
+        let (a_branch, b_branch) = (version.is_dev_branch(), self.version.is_dev_branch());
+        if self.op == Op::NotEqual && (a_branch || b_branch) {
+            return version.normalized != self.version.normalized
+        }
+        if a_branch || b_branch {
+            return a_branch && b_branch && self.op == Op::Equal && version.normalized == self.version.normalized
+        }
+        let ordering = version.cmp(&self.version);
+        match self.op {
+            Op::Equal => ordering.is_eq(),
+            Op::NotEqual => ordering.is_ne(),
+            Op::Less => ordering.is_lt(),
+            Op::LessEqual => ordering.is_le(),
+            Op::Greater => ordering.is_gt(),
+            Op::GreaterEqual => ordering.is_ge(),
+        }
+    }
+
+    fn new(op: Op, version: String) -> Comparator {
+        Comparator { op, version: Version::from_normalized(version) }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, group) in self.alternatives.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" || ")?;
+            }
+            if group.is_empty() {
+                f.write_str("*")?;
+            }
+            for (j, comparator) in group.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{comparator}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.op, self.version)
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::Equal => "==",
+            Op::NotEqual => "!=",
+            Op::Less => "<",
+            Op::LessEqual => "<=",
+            Op::Greater => ">",
+            Op::GreaterEqual => ">=",
+        })
+    }
+}
+
+/// 解析约束
+///
+/// 支持 `*`、`~1.2`、`^1.2`、`1.0.*`、`1.0 - 2.0`、`>=1.0`、`!=1.5`、`dev-main` 以及 `@beta` 稳定性标记
+impl FromStr for Constraint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Constraint, ParseError> {
+        let mut explicit_stability: Option<Stability> = None;
+        let mut inferred_stability: Option<Stability> = None;
+        let mut alternatives = Vec::new();
+
+        for group in s.split("||").flat_map(|g| g.split('|')) {
+            if group.trim().is_empty() {
+                return Err(ParseError::InvalidConstraint(s.to_string()))
+            }
+            let mut comparators = Vec::new();
+            for token in split_and(group) {
+                let (constraint, flag) = match token.rsplit_once('@') {
+                    Some((head, flag)) if !head.contains(char::is_whitespace) => match flag.parse::<Stability>() {
+                        Ok(flag) => (if head.is_empty() { "*" } else { head }, Some(flag)),
+                        Err(_) => (token.as_str(), None),
+                    },
+                    _ => (token.as_str(), None),
+                };
+                match flag {
+                    Some(flag) => explicit_stability = Some(explicit_stability.map_or(flag, |f| f.min(flag))),
+                    None if !constraint.contains(char::is_whitespace) => {
+                        let stability = parse_stability(constraint);
+                        if stability != Stability::Stable {
+                            inferred_stability = Some(inferred_stability.map_or(stability, |f| f.min(stability)));
+                        }
+                    }
+                    None => {}
+                }
+                let modifier = flag.filter(|f| *f != Stability::Stable);
+                parse_single(constraint, modifier, &mut comparators).map_err(|_| ParseError::InvalidConstraint(s.to_string()))?;
+            }
+            alternatives.push(comparators);
+        }
+        Ok(Constraint { alternatives, explicit_stability, inferred_stability })
+    }
+}
+
+impl TryFrom<&str> for Constraint {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Constraint, ParseError> {
+        s.parse()
+    }
+}
+
+/// 以空格或 `,` 拆分一组约束; 运算符与版本号之间的空格不拆开, `1.0 - 2.0` 保持为一个约束
+fn split_and(group: &str) -> Vec<String> {
+    let raw: Vec<&str> = group.split(|c: char| c == ',' || c.is_whitespace()).filter(|t| !t.is_empty()).collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < raw.len() {
+        let mut token = raw[i].to_string();
+        i += 1;
+        while token.ends_with(['=', '<', '>']) && i < raw.len() {
+            token.push_str(raw[i]);
+            i += 1;
+        }
+        if i + 1 < raw.len() && raw[i] == "-" {
+            token = format!("{token} - {}", raw[i + 1]);
+            i += 2;
+        }
+        tokens.push(token);
+    }
+    tokens
+}
+
+///
+/// `v?(\d+)(\.\d+)?(\.\d+)?(\.\d+)?` 加修饰部分与元数据的拆分结果
+struct Parts<'a> {
+    numbers: Vec<&'a str>,
+    has_modifier: bool,
+    has_build: bool,
+}
+
+impl Parts<'_> {
+
+    fn parse(s: &str) -> Option<Parts<'_>> {
+        let (s, has_build) = match s.split_once('+') {
+            Some((head, build)) if !build.is_empty() && !build.contains(char::is_whitespace) => (head, true),
+            Some(_) => return None,
+            None => (s, false),
+        };
+        let mut rest = s.strip_prefix(['v', 'V']).unwrap_or(s);
+        let mut numbers = Vec::new();
+        while numbers.len() < 4 {
+            let body = if numbers.is_empty() { Some(rest) } else { rest.strip_prefix('.') };
+            let Some(body) = body else { break };
+            let len = body.bytes().take_while(u8::is_ascii_digit).count();
+            if len == 0 {
+                break;
+            }
+            numbers.push(&body[..len]);
+            rest = &body[len..];
+        }
+        if numbers.is_empty() {
+            return None
+        }
+        let modifier = super::Modifier::parse(rest)?;
+        Some(Parts { numbers, has_modifier: modifier.stability.is_some() || modifier.dev, has_build })
+    }
+}
+
+/// 将 `position` (从 1 开始) 处的数字加上 `increment`, 之后的数字置为 0, 与 `manipulateVersionString` 一致;
+/// 该数字超出 `u64` 范围或加上 `increment` 后溢出时返回 `None`
+fn manipulate(numbers: &[&str], position: usize, increment: u64) -> Option<String> {
+    let parts = (1..=4)
+        .map(|i| {
+            let n = numbers.get(i - 1).copied().unwrap_or("0");
+            if i > position {
+                Some("0".to_string())
+            } else if i == position {
+                n.parse::<u64>().ok()?.checked_add(increment).map(|n| n.to_string())
+            } else {
+                Some(n.to_string())
+            }
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(parts.join("."))
+}
+
+/// 解析单个约束, 与 `VersionParser::parseConstraint` 一致
+fn parse_single(constraint: &str, modifier: Option<Stability>, out: &mut Vec<Comparator>) -> Result<(), ParseError> {
+    let invalid = || ParseError::InvalidConstraint(constraint.to_string());
+
+    // `*`、`x.*` 接受任何版本
+    let bare = constraint.strip_prefix(['v', 'V']).unwrap_or(constraint);
+    if !bare.is_empty() && bare.split('.').all(|p| matches!(p, "*" | "x" | "X")) {
+        return Ok(())
+    }
+
+    if constraint.starts_with("~>") {
+        return Err(invalid())
+    }
+    if let Some(rest) = constraint.strip_prefix('~') {
+        let parts = Parts::parse(rest).ok_or_else(invalid)?;
+        let position = parts.numbers.len();
+        let suffix = if parts.has_modifier { "" } else { "-dev" };
+        let low = normalize(&format!("{rest}{suffix}"))?;
+        let high = manipulate(&parts.numbers, (position - 1).max(1), 1).ok_or_else(invalid)?;
+        out.push(Comparator::new(Op::GreaterEqual, low));
+        out.push(Comparator::new(Op::Less, format!("{high}-dev")));
+        return Ok(())
+    }
+    if let Some(rest) = constraint.strip_prefix('^') {
+        let parts = Parts::parse(rest).ok_or_else(invalid)?;
+        let n = &parts.numbers;
+        let position = if n[0] != "0" || n.len() < 2 {
+            1
+        } else if n[1] != "0" || n.len() < 3 {
+            2
+        } else {
+            3
+        };
+        let suffix = if parts.has_modifier { "" } else { "-dev" };
+        let low = normalize(&format!("{rest}{suffix}"))?;
+        let high = manipulate(n, position, 1).ok_or_else(invalid)?;
+        out.push(Comparator::new(Op::GreaterEqual, low));
+        out.push(Comparator::new(Op::Less, format!("{high}-dev")));
+        return Ok(())
+    }
+
+    // `1.0.*` 这样的通配符
+    let segments: Vec<&str> = bare.split('.').collect();
+    if let Some(first_wildcard) = segments.iter().position(|p| matches!(*p, "*" | "x" | "X"))
+        && (1..=3).contains(&first_wildcard)
+        && segments[..first_wildcard].iter().all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+        && segments[first_wildcard..].iter().all(|p| matches!(*p, "*" | "x" | "X"))
+    {
+        let numbers = &segments[..first_wildcard];
+        let low = format!("{}-dev", manipulate(numbers, first_wildcard, 0).ok_or_else(invalid)?);
+        let high = format!("{}-dev", manipulate(numbers, first_wildcard, 1).ok_or_else(invalid)?);
+        if low != "0.0.0.0-dev" {
+            out.push(Comparator::new(Op::GreaterEqual, low));
+        }
+        out.push(Comparator::new(Op::Less, high));
+        return Ok(())
+    }
+
+    // `1.0 - 2.0` 这样的连字符范围
+    if let Some((from, to)) = constraint.split_once(" - ") {
+        let (from, to) = (from.trim(), to.trim());
+        let from_parts = Parts::parse(from).ok_or_else(invalid)?;
+        let to_parts = Parts::parse(to).ok_or_else(invalid)?;
+        let suffix = if from_parts.has_modifier { "" } else { "-dev" };
+        out.push(Comparator::new(Op::GreaterEqual, format!("{}{suffix}", normalize(from)?)));
+        if to_parts.numbers.len() >= 3 || to_parts.has_modifier || to_parts.has_build {
+            out.push(Comparator::new(Op::LessEqual, normalize(to)?));
+        } else {
+            normalize(to)?;
+            let position = if to_parts.numbers.len() < 2 { 1 } else { 2 };
+            let high = manipulate(&to_parts.numbers, position, 1).ok_or_else(invalid)?;
+            out.push(Comparator::new(Op::Less, format!("{high}-dev")));
+        }
+        return Ok(())
+    }
+
+    // 普通的比较运算
+    let (op_str, op) = [
+        ("<>", Op::NotEqual),
+        ("!=", Op::NotEqual),
+        (">=", Op::GreaterEqual),
+        ("<=", Op::LessEqual),
+        ("==", Op::Equal),
+        (">", Op::Greater),
+        ("<", Op::Less),
+        ("=", Op::Equal),
+    ]
+    .into_iter()
+    .find(|(prefix, _)| constraint.starts_with(prefix))
+    .unwrap_or(("", Op::Equal));
+    let text = constraint[op_str.len()..].trim_start();
+    let mut version = match normalize(text) {
+        Ok(version) => version,
+        // `foobar-dev` 这样的写法视为 `dev-foobar`
+        Err(e) => match text.strip_suffix("-dev") {
+            Some(branch) if text.bytes().all(|b| b.is_ascii_alphanumeric() || b"-./".contains(&b)) => normalize(&format!("dev-{branch}"))?,
+            _ => return Err(e),
+        },
+    };
+    if op != Op::Equal && let Some(modifier) = modifier && parse_stability(&version) == Stability::Stable {
+        version = format!("{version}-{modifier}");
+    } else if matches!(op_str, "<" | ">=") {
+        let lower = text.to_ascii_lowercase();
+        let has_modifier = lower.match_indices('-').any(|(i, _)| super::Modifier::parse(&lower[i + 1..]).is_some());
+        if !has_modifier && !text.starts_with("dev-") {
+            version.push_str("-dev");
+        }
+    }
+    out.push(Comparator::new(op, version));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Constraint;
+    use crate::composer::{ParseError, Stability, Version};
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    /// 展开结果取自 composer/semver 的 VersionParserTest
+    #[test]
+    fn test_parse() {
+        let cases = [
+            ("*", "*"),
+            ("*@dev", "*"),
+            ("1.0.0", "==1.0.0.0"),
+            ("dev-main", "==dev-main"),
+            ("foo-dev", "==dev-foo"),
+            (">=1.0", ">=1.0.0.0-dev"),
+            ("<1.2", "<1.2.0.0-dev"),
+            (">1.0", ">1.0.0.0"),
+            ("<=1.2.3-beta", "<=1.2.3.0-beta"),
+            ("<> 1.0", "!=1.0.0.0"),
+            (">=1.0@beta", ">=1.0.0.0-beta"),
+            ("~1.2", ">=1.2.0.0-dev <2.0.0.0-dev"),
+            ("~1.2.3", ">=1.2.3.0-dev <1.3.0.0-dev"),
+            ("~1.2.3.4", ">=1.2.3.4-dev <1.2.4.0-dev"),
+            ("~1.2-beta", ">=1.2.0.0-beta <2.0.0.0-dev"),
+            ("~1.2.2-dev", ">=1.2.2.0-dev <1.3.0.0-dev"),
+            ("~1.2.2-stable", ">=1.2.2.0 <1.3.0.0-dev"),
+            ("^1.2", ">=1.2.0.0-dev <2.0.0.0-dev"),
+            ("^0.3", ">=0.3.0.0-dev <0.4.0.0-dev"),
+            ("^0.0.3", ">=0.0.3.0-dev <0.0.4.0-dev"),
+            ("^0.0.3-alpha", ">=0.0.3.0-alpha <0.0.4.0-dev"),
+            ("1.0.*", ">=1.0.0.0-dev <1.1.0.0-dev"),
+            ("2.x", ">=2.0.0.0-dev <3.0.0.0-dev"),
+            ("0.*", "<1.0.0.0-dev"),
+            ("1 - 2", ">=1.0.0.0-dev <3.0.0.0-dev"),
+            ("1.2 - 2.3", ">=1.2.0.0-dev <2.4.0.0-dev"),
+            ("1.2.3 - 2.3.4.5", ">=1.2.3.0-dev <=2.3.4.5"),
+            (">=1.0 <1.1", ">=1.0.0.0-dev <1.1.0.0-dev"),
+            (">= 1.0, < 1.1", ">=1.0.0.0-dev <1.1.0.0-dev"),
+            ("^1.2 || ~2.0.3", ">=1.2.0.0-dev <2.0.0.0-dev || >=2.0.3.0-dev <2.1.0.0-dev"),
+            ("^1.0 | ^2.0", ">=1.0.0.0-dev <2.0.0.0-dev || >=2.0.0.0-dev <3.0.0.0-dev"),
+            ("1.2.3@beta", "==1.2.3.0"),
+        ];
+        for (input, expected) in cases {
+            let constraint: Constraint = input.parse().unwrap_or_else(|e| panic!("{input}: {e}"));
+            assert_eq!(constraint.to_string(), expected, "{input}");
+        }
+        let overflows = [
+            "^18446744073709551615",
+            "~1.18446744073709551615.0",
+            "1.18446744073709551615.*",
+            "~1.99999999999999999999999.0",
+            "1.0 - 18446744073709551615",
+        ];
+        for input in ["", "^1.0 ||", "~>1.2", ">=foo", "1.0 -", "^a", "1.*.1", "~1.2@sta"].into_iter().chain(overflows) {
+            assert_eq!(input.parse::<Constraint>().unwrap_err(), ParseError::InvalidConstraint(input.to_string()), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let cases = [
+            ("^1.2", "1.2.0", true),
+            ("^1.2", "1.9.9-beta", true),
+            ("^1.2", "2.0.0-beta", false),
+            (">=1.0 <1.1", "1.0.5", true),
+            (">=1.0 <1.1", "1.1.0-RC1", false),
+            ("1.0.*", "1.0.9-p2", true),
+            ("dev-main", "dev-main", true),
+            ("dev-main", "dev-feature", false),
+            ("!=dev-main", "1.0.0", true),
+            (">=1.0", "dev-main", false),
+            ("*", "dev-main", true),
+            ("~2.0.3", "2.0.99", true),
+            ("~2.0.3", "2.1.0", false),
+        ];
+        for (constraint, version, expected) in cases {
+            assert_eq!(constraint.parse::<Constraint>().unwrap().matches(&v(version)), expected, "{constraint} {version}");
+        }
+    }
+
+    #[test]
+    fn test_minimum_stability() {
+        let candidates: Vec<Version> = ["1.0.0", "1.1.0-beta1", "1.1.0", "1.2.0-RC1", "1.3.0-dev", "dev-main"].iter().map(|s| v(s)).collect();
+        let best = |constraint: &str, minimum: Stability| {
+            constraint.parse::<Constraint>().unwrap().best_match(&candidates, minimum).map(ToString::to_string)
+        };
+        assert_eq!(best("^1.0", Stability::Stable).as_deref(), Some("1.1.0.0"));
+        assert_eq!(best("^1.0", Stability::Beta).as_deref(), Some("1.2.0.0-RC1"));
+        assert_eq!(best("^1.0", Stability::Dev).as_deref(), Some("1.3.0.0-dev"));
+        assert_eq!(best("^1.0@RC", Stability::Stable).as_deref(), Some("1.2.0.0-RC1"));
+        assert_eq!(best("^1.0@stable", Stability::Dev).as_deref(), Some("1.1.0.0"));
+        assert_eq!(best("dev-main", Stability::Stable).as_deref(), Some("dev-main"));
+        assert_eq!(best(">=1.1.0-beta1 <1.2", Stability::Stable).as_deref(), Some("1.1.0.0"));
+        assert_eq!(best("1.1.0-beta1", Stability::Stable).as_deref(), Some("1.1.0.0-beta1"));
+
+        let constraint: Constraint = "^1.0 || dev-main".parse().unwrap();
+        let all: Vec<String> = constraint.filter(&candidates, Stability::Stable).map(ToString::to_string).collect();
+        assert_eq!(all, ["1.0.0.0", "1.1.0.0-beta1", "1.1.0.0", "1.2.0.0-RC1", "1.3.0.0-dev", "dev-main"]);
+    }
+}
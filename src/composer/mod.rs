@@ -0,0 +1,539 @@
+//! Composer (PHP) 使用的版本号, 规则与 `Composer\Semver\VersionParser` 一致
+//!
+//! 版本号在解析时规范化为 Composer 的内部形式: 数字部分补齐为 4 个, 稳定性后缀展开为全称,
+//! 例如 `1.2` → `1.2.0.0`、`1.0-p1` → `1.0.0.0-patch1`、`1.x-dev` → `1.9999999.9999999.9999999-dev`,
+//! `dev-` 开头的分支名原样保留。比较规则与 PHP 的 `version_compare` 一致。
+//! [`Constraint`] 用于解析 `^1.2 || ~2.0.3` 这样的约束, 并结合 [`Stability`] 从候选版本中挑选版本
+//!
+//! ```
+//! use version::composer::{Stability, Version};
+//!
+//! let v: Version = "v1.2-RC1".parse().unwrap();
+//! assert_eq!(v.to_string(), "1.2.0.0-RC1");
+//! assert_eq!(v.stability(), Stability::RC);
+//! assert!(v < "1.2".parse().unwrap());
+//! ```
+
+mod constraint;
+
+pub use constraint::{Comparator, Constraint, Op};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个规范化后的 Composer 版本号
+#[derive(Debug, Clone)]
+pub struct Version {
+    normalized: String,
+}
+
+///
+/// 版本的稳定性, 从低到高依次为 dev < alpha < beta < RC < stable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stability {
+    /// `dev`, 开发分支或 `-dev` 结尾的版本
+    Dev,
+    /// `alpha`, 也写作 `a`
+    Alpha,
+    /// `beta`, 也写作 `b`
+    Beta,
+    /// `RC`
+    RC,
+    /// `stable`, 正式版本以及 `-patch` 补丁版本
+    Stable,
+}
+
+///
+/// 解析 Composer 版本号或约束时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("非法的 Composer 版本号 \"{0}\"")]
+    InvalidVersion(String),
+
+    #[error("无法解析版本约束 \"{0}\"")]
+    InvalidConstraint(String),
+
+    #[error("非法的稳定性 \"{0}\"")]
+    InvalidStability(String),
+}
+
+impl Version {
+
+    /// 直接使用已经规范化的字符串
+    fn from_normalized(normalized: String) -> Version {
+        Version { normalized }
+    }
+
+    /// 是否为 `dev-main` 这样的分支版本, 分支版本只与同名分支相等
+    pub fn is_dev_branch(&self) -> bool {
+        self.normalized.starts_with("dev-")
+    }
+
+    /// 版本的稳定性
+    pub fn stability(&self) -> Stability {
+        parse_stability(&self.normalized)
+    }
+}
+
+impl Stability {
+
+    /// 稳定性后缀的全称, 与 `VersionParser::expandStability` 一致
+    fn expand(modifier: &str) -> &str {
+        match modifier {
+            "a" => "alpha",
+            "b" => "beta",
+            "p" | "pl" => "patch",
+            "rc" => "RC",
+            other => other,
+        }
+    }
+}
+
+impl fmt::Display for Stability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Stability::Dev => "dev",
+            Stability::Alpha => "alpha",
+            Stability::Beta => "beta",
+            Stability::RC => "RC",
+            Stability::Stable => "stable",
+        })
+    }
+}
+
+/// 解析 `minimum-stability` 或 `@beta` 中的稳定性名称, 不区分大小写
+impl FromStr for Stability {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Stability, ParseError> {
+        match s.to_ascii_lowercase().as_str() {
+            "dev" => Ok(Stability::Dev),
+            "alpha" => Ok(Stability::Alpha),
+            "beta" => Ok(Stability::Beta),
+            "rc" => Ok(Stability::RC),
+            "stable" => Ok(Stability::Stable),
+            _ => Err(ParseError::InvalidStability(s.to_string())),
+        }
+    }
+}
+
+/// 判断任意版本字符串的稳定性, 与 `VersionParser::parseStability` 一致
+fn parse_stability(version: &str) -> Stability {
+    let version = version.split_once('#').map_or(version, |(v, _)| v);
+    if version.starts_with("dev-") || version.ends_with("-dev") {
+        return Stability::Dev
+    }
+    let lower = version.to_ascii_lowercase();
+    let lower = lower.split_once('+').map_or(lower.as_str(), |(v, _)| v);
+    if lower.ends_with("dev") {
+        return Stability::Dev
+    }
+    let trimmed = lower.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.' || c == '-');
+    // 取最长的后缀, 与正则从最左侧开始匹配的结果一致
+    match ["stable", "alpha", "patch", "beta", "rc", "pl", "a", "b", "p"].iter().find(|m| trimmed.ends_with(*m)) {
+        Some(&"beta" | &"b") => Stability::Beta,
+        Some(&"alpha" | &"a") => Stability::Alpha,
+        Some(&"rc") => Stability::RC,
+        _ => Stability::Stable,
+    }
+}
+
+///
+/// 版本号之后的修饰部分, 对应 `[._-]?(?:(stable|beta|b|RC|alpha|a|patch|pl|p)((?:[.-]?\d+)*+)?)?([.-]?dev)?`
+struct Modifier {
+    stability: Option<(String, String)>,
+    dev: bool,
+}
+
+impl Modifier {
+
+    /// 整个字符串都是修饰部分时返回解析结果
+    fn parse(s: &str) -> Option<Modifier> {
+        let lower = s.to_ascii_lowercase();
+        let without_separator = lower.strip_prefix(['.', '_', '-']);
+        without_separator.and_then(Modifier::parse_body).or_else(|| Modifier::parse_body(&lower))
+    }
+
+    fn parse_body(s: &str) -> Option<Modifier> {
+        let mut rest = s;
+        let mut stability = None;
+        if let Some(name) = ["stable", "beta", "b", "rc", "alpha", "a", "patch", "pl", "p"].iter().find(|m| rest.starts_with(*m)) {
+            rest = &rest[name.len()..];
+            let mut number = String::new();
+            loop {
+                let digits_start = rest.strip_prefix(['.', '-']).unwrap_or(rest);
+                let len = digits_start.bytes().take_while(u8::is_ascii_digit).count();
+                if len == 0 {
+                    break;
+                }
+                let consumed = rest.len() - digits_start.len() + len;
+                number.push_str(&rest[..consumed]);
+                rest = &rest[consumed..];
+            }
+            stability = Some((name.to_string(), number));
+        }
+        let dev = match rest.strip_prefix(['.', '-']).unwrap_or(rest) {
+            "dev" => true,
+            _ if rest.is_empty() => false,
+            _ => return None,
+        };
+        Some(Modifier { stability, dev })
+    }
+
+    /// 将修饰部分追加到规范化的数字部分之后
+    fn apply(self, mut version: String) -> String {
+        if let Some((name, number)) = self.stability {
+            if name == "stable" {
+                return version
+            }
+            version.push('-');
+            version.push_str(Stability::expand(&name));
+            version.push_str(number.trim_start_matches(['.', '-']));
+        }
+        if self.dev {
+            version.push_str("-dev");
+        }
+        version
+    }
+}
+
+/// 规范化版本字符串, 与 `VersionParser::normalizeVersion` 一致
+fn normalize(input: &str) -> Result<String, ParseError> {
+    let mut version = input.trim();
+
+    // 去掉 `1.0.x-dev as 1.0.0` 这样的别名
+    let words: Vec<&str> = version.split_whitespace().collect();
+    if let [target, "as", _] = *words.as_slice() {
+        version = target;
+    }
+    if let Some((head, flag)) = version.rsplit_once('@')
+        && flag.parse::<Stability>().is_ok()
+    {
+        version = head;
+    }
+    if matches!(version, "master" | "trunk" | "default") {
+        return Ok(format!("dev-{version}"))
+    }
+    if version.get(..4).is_some_and(|p| p.eq_ignore_ascii_case("dev-")) {
+        return Ok(format!("dev-{}", &version[4..]))
+    }
+    if let Some((head, metadata)) = version.split_once('+')
+        && !head.is_empty()
+        && !head.contains(|c: char| c == ',' || c.is_whitespace())
+        && !metadata.is_empty()
+        && !metadata.contains(char::is_whitespace)
+    {
+        version = head;
+    }
+
+    if let Some(normalized) = normalize_classical(version).or_else(|| normalize_date(version)) {
+        return Ok(normalized)
+    }
+
+    // `1.x-dev` 这样的数字分支
+    let lower = version.to_ascii_lowercase();
+    if let Some(head) = lower.strip_suffix("dev") {
+        let head = head.strip_suffix(['.', '-']).unwrap_or(head);
+        if let Some(normalized) = normalize_branch(&version[..head.len()]) {
+            return Ok(normalized)
+        }
+    }
+    Err(ParseError::InvalidVersion(input.to_string()))
+}
+
+/// `v?(\d{1,5})(\.\d+)?(\.\d+)?(\.\d+)?` 加修饰部分, 数字部分补齐为 4 个
+fn normalize_classical(version: &str) -> Option<String> {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let major = version.bytes().take_while(u8::is_ascii_digit).count();
+    if major == 0 || major > 5 {
+        return None
+    }
+    let mut normalized = version[..major].to_string();
+    let mut rest = &version[major..];
+    for _ in 0..3 {
+        let digits = rest.strip_prefix('.').map_or(0, |r| r.bytes().take_while(u8::is_ascii_digit).count());
+        if digits == 0 {
+            normalized.push_str(".0");
+            continue;
+        }
+        normalized.push_str(&rest[..digits + 1]);
+        rest = &rest[digits + 1..];
+    }
+    Modifier::parse(rest).map(|m| m.apply(normalized))
+}
+
+/// `v?(\d{4}(?:[.:-]?\d{2}){1,6}(?:[.:-]?\d{1,3}){0,2})` 加修饰部分, 分隔符统一替换为 `.`
+fn normalize_date(version: &str) -> Option<String> {
+    let version = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    (4..=version.len()).rev().filter(|&k| version.is_char_boundary(k)).find_map(|k| {
+        let (date, rest) = version.split_at(k);
+        if !is_date(date.as_bytes()) {
+            return None
+        }
+        let modifier = Modifier::parse(rest)?;
+        let date = date.chars().map(|c| if c.is_ascii_digit() { c } else { '.' }).collect();
+        Some(modifier.apply(date))
+    })
+}
+
+fn is_date(s: &[u8]) -> bool {
+    fn tail(s: &[u8], pairs: usize, extra: usize) -> bool {
+        if s.is_empty() {
+            return pairs >= 1
+        }
+        let body = match s[0] {
+            b'.' | b':' | b'-' => &s[1..],
+            _ => s,
+        };
+        let digits = body.iter().take_while(|b| b.is_ascii_digit()).count();
+        let pair = extra == 0 && pairs < 6 && digits >= 2 && tail(&body[2..], pairs + 1, 0);
+        pair || (pairs >= 1 && extra < 2 && (1..=digits.min(3)).any(|n| tail(&body[n..], pairs, extra + 1)))
+    }
+    s.len() >= 4 && s[..4].iter().all(u8::is_ascii_digit) && tail(&s[4..], 0, 0)
+}
+
+/// 将 `1.x`、`2.3.*` 这样的分支名规范化为 `1.9999999.9999999.9999999-dev`, 不是数字分支时返回 `None`
+fn normalize_branch(name: &str) -> Option<String> {
+    let name = name.trim();
+    let name = name.strip_prefix(['v', 'V']).unwrap_or(name);
+    let parts: Vec<&str> = name.split('.').collect();
+    let is_number = |p: &str| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit());
+    let valid = parts.len() <= 4
+        && is_number(parts[0])
+        && parts[1..].iter().all(|p| is_number(p) || matches!(*p, "x" | "X" | "*"));
+    if !valid {
+        return None
+    }
+    let mut components: Vec<&str> = parts.iter().map(|p| if is_number(p) { *p } else { "9999999" }).collect();
+    components.resize(4, "9999999");
+    Some(format!("{}-dev", components.join(".")))
+}
+
+///
+/// `version_compare` 拆分出的一部分
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Part {
+    /// 特殊字符串的次序, 数字在比较中视为 `#`
+    Special(i8),
+    Number(u64),
+}
+
+/// 次序与 `php_version_compare` 的 `special_forms` 一致, 按前缀匹配, 未知的字符串最低
+fn special_order(form: &str) -> i8 {
+    const FORMS: [(&str, i8); 10] = [
+        ("dev", 0), ("alpha", 1), ("a", 1), ("beta", 2), ("b", 2), ("RC", 3), ("rc", 3), ("#", 4), ("pl", 5), ("p", 5),
+    ];
+    FORMS.iter().find(|(name, _)| form.starts_with(name)).map_or(-6, |(_, order)| *order)
+}
+
+/// 与 `php_canonicalize_version` 一致: 在数字与非数字之间断开, 其余非字母数字字符视为分隔符
+fn canonical_parts(version: &str) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut flush = |current: &mut String| {
+        if current.is_empty() {
+            return
+        }
+        parts.push(match current.parse::<u64>() {
+            Ok(n) => Part::Number(n),
+            Err(_) if current.bytes().all(|b| b.is_ascii_digit()) => Part::Number(u64::MAX),
+            Err(_) => Part::Special(special_order(current)),
+        });
+        current.clear();
+    };
+    for c in version.chars() {
+        if !c.is_ascii_alphanumeric() {
+            flush(&mut current);
+            continue;
+        }
+        if current.ends_with(|last: char| last.is_ascii_digit() != c.is_ascii_digit()) {
+            flush(&mut current);
+        }
+        current.push(c);
+    }
+    flush(&mut current);
+    parts
+}
+
+fn cmp_part(a: Part, b: Part) -> Ordering {
+    const NUMBER: i8 = 4;
+    match (a, b) {
+        (Part::Number(a), Part::Number(b)) => a.cmp(&b),
+        (Part::Number(_), Part::Special(b)) => NUMBER.cmp(&b),
+        (Part::Special(a), Part::Number(_)) => a.cmp(&NUMBER),
+        (Part::Special(a), Part::Special(b)) => a.cmp(&b),
+    }
+}
+
+/// 与 PHP 的 `version_compare` 一致
+///
+/// 一方用尽时, 另一方剩余的是数字则它更大, 否则将剩余部分与 `#` 比较
+fn version_compare(a: &[Part], b: &[Part]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(&x), Some(&y)) => cmp_part(x, y),
+            (Some(Part::Number(_)), None) => Ordering::Greater,
+            (None, Some(Part::Number(_))) => Ordering::Less,
+            (Some(&x), None) => return cmp_part(x, Part::Special(4)),
+            (None, Some(&y)) => return cmp_part(Part::Special(4), y),
+            (None, None) => unreachable!(),
+        };
+        if ordering.is_ne() {
+            return ordering
+        }
+    }
+    Ordering::Equal
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 数字版本按 `version_compare` 比较; 分支版本排在所有数字版本之前, 分支之间按名称比较
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_dev_branch(), other.is_dev_branch()) {
+            (true, true) => self.normalized.cmp(&other.normalized),
+            (true, false) => Ordering::Less,
+            (false, true) => Ordering::Greater,
+            (false, false) => version_compare(&canonical_parts(&self.normalized), &canonical_parts(&other.normalized)),
+        }
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        if self.is_dev_branch() {
+            self.normalized.hash(state);
+        } else {
+            // 与 Eq 保持一致, `beta01` 与 `beta1` 相等
+            canonical_parts(&self.normalized).hash(state);
+        }
+    }
+}
+
+/// 输出规范化后的字符串
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.normalized)
+    }
+}
+
+/// 解析并规范化版本号
+///
+/// 支持 `v` 前缀、1 到 4 个数字部分、`-beta2`/`-RC1`/`-p1` 这样的稳定性后缀、`-dev` 后缀、
+/// 日期版本 `20240101`、`dev-main` 分支以及 `1.x-dev` 数字分支; `+` 之后的元数据与 `@beta` 会被丢弃
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        normalize(s).map(Version::from_normalized)
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Stability, Version};
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 用例取自 composer/semver 的 VersionParserTest
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("1.0.0", "1.0.0.0"),
+            ("1.2", "1.2.0.0"),
+            ("1.2.3.4", "1.2.3.4"),
+            ("v1.0.0", "1.0.0.0"),
+            ("0.1.2-beta1", "0.1.2.0-beta1"),
+            ("1.0.0RC1dev", "1.0.0.0-RC1-dev"),
+            ("1.0.0-rC15-dev", "1.0.0.0-RC15-dev"),
+            ("1.0.0.RC.15-dev", "1.0.0.0-RC15-dev"),
+            ("1.0.0-rc1", "1.0.0.0-RC1"),
+            ("1.0.0.pl3-dev", "1.0.0.0-patch3-dev"),
+            ("1.0-dev", "1.0.0.0-dev"),
+            ("1.0-p1", "1.0.0.0-patch1"),
+            ("1.0.0-pl3", "1.0.0.0-patch3"),
+            ("10.4.13-beta.2", "10.4.13.0-beta2"),
+            ("1.0.0-stable", "1.0.0.0"),
+            ("1.0.0+foo", "1.0.0.0"),
+            ("1.0.0-beta.5+foo", "1.0.0.0-beta5"),
+            ("1.0.0@beta", "1.0.0.0"),
+            ("20100102", "20100102"),
+            ("20100102-203040-p1", "20100102.203040-patch1"),
+            ("20100102203040-10", "20100102203040.10"),
+            ("2010-01-02.5", "2010.01.02.5"),
+            ("dev-master", "dev-master"),
+            ("master", "dev-master"),
+            ("dev-feature/foo", "dev-feature/foo"),
+            ("1.x-dev", "1.9999999.9999999.9999999-dev"),
+            ("2.3.x-dev", "2.3.9999999.9999999-dev"),
+            ("1.0.0-dev as 1.0", "1.0.0.0-dev"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(v(input).to_string(), expected, "{input}");
+        }
+        for input in ["", "a", "1.0.0-meh", "1.0.0.0.0", "feature-foo", "1.0 .2", "123456.0-x"] {
+            assert_eq!(input.parse::<Version>().unwrap_err(), ParseError::InvalidVersion(input.to_string()), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_stability() {
+        let cases = [
+            ("1.0.0", Stability::Stable),
+            ("1.0-p1", Stability::Stable),
+            ("1.0.0-RC1", Stability::RC),
+            ("1.0.0-b2", Stability::Beta),
+            ("1.0.0-alpha3", Stability::Alpha),
+            ("1.0.0-dev", Stability::Dev),
+            ("1.x-dev", Stability::Dev),
+            ("dev-main", Stability::Dev),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(v(input).stability(), expected, "{input}");
+        }
+        assert!(Stability::Dev < Stability::Alpha && Stability::Beta < Stability::RC && Stability::RC < Stability::Stable);
+        assert_eq!("rc".parse::<Stability>(), Ok(Stability::RC));
+        assert_eq!(Stability::RC.to_string(), "RC");
+    }
+
+    #[test]
+    fn test_compare() {
+        let ordered = [
+            "dev-feature", "dev-main", "1.0.0-dev", "1.0.0-alpha1", "1.0.0-beta", "1.0.0-beta2", "1.0.0-RC1",
+            "1.0.0", "1.0.0-p1", "1.0.0.1", "1.0.1", "1.9999999.9999999.9999999-dev", "2.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        let set: HashSet<Version> = ["1.0.0-beta01", "1.0-beta1", "v1.0.0.0-b1"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+}
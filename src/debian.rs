@@ -0,0 +1,445 @@
+//! Debian 软件包版本号, 格式为 `[epoch:]upstream_version[-debian_revision]`
+//!
+//! 比较规则与 `dpkg --compare-versions` 完全一致:
+//! `~` 排在一切字符(包括字符串结尾)之前, 字母排在非字母符号之前, 连续的数字按数值比较
+//!
+//! `str::parse` 按照 Debian Policy 检查字符集; dpkg 对上游版本号不以数字开头、含有非法字符只给出警告,
+//! 需要与 dpkg 一样接受这些版本号时使用 [`Version::parse_lenient`]
+//!
+//! ```
+//! use version::debian::Version;
+//!
+//! let v: Version = "1:2.3.4~rc1-0ubuntu2".parse().unwrap();
+//! assert_eq!((v.epoch(), v.upstream(), v.revision()), (1, "2.3.4~rc1", "0ubuntu2"));
+//! assert!(v < "1:2.3.4-0ubuntu1".parse().unwrap());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 Debian 软件包版本号
+///
+/// 版本号之间按照 dpkg 的算法比较, 因此 `1.0 == 1.0-0`、`009 == 9`
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u32,
+    upstream: String,
+    revision: String,
+}
+
+///
+/// 解析 Debian 版本号时可能发生的错误, 与 dpkg 的 `parseversion` 对应
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("版本号中存在空白")]
+    EmbeddedSpace,
+
+    #[error("纪元为空")]
+    EpochEmpty,
+
+    #[error("纪元不是数字")]
+    EpochNotNumber,
+
+    #[error("纪元不能为负数")]
+    EpochNegative,
+
+    #[error("纪元过大, 不能超过 {}", i32::MAX)]
+    EpochTooBig,
+
+    #[error("冒号之后没有内容")]
+    NothingAfterColon,
+
+    #[error("修订号为空")]
+    RevisionEmpty,
+
+    #[error("上游版本号为空")]
+    UpstreamEmpty,
+
+    #[error("上游版本号必须以数字开头")]
+    UpstreamNotStartWithDigit,
+
+    #[error("上游版本号中存在非法字符 '{0}'")]
+    InvalidUpstreamCharacter(char),
+
+    #[error("修订号中存在非法字符 '{0}'")]
+    InvalidRevisionCharacter(char),
+}
+
+impl Version {
+
+    /// 按照 `dpkg --compare-versions` 的方式解析
+    ///
+    /// dpkg 只给出警告的问题(上游版本号不以数字开头、上游版本号或修订号中存在非法字符)不视为错误,
+    /// 其余错误与 `str::parse` 相同
+    ///
+    /// ```
+    /// use version::debian::Version;
+    ///
+    /// assert!("1.0_beta-1".parse::<Version>().is_err());
+    /// let v = Version::parse_lenient("1.0_beta-1").unwrap();
+    /// assert!(v > Version::parse_lenient("1.0-1").unwrap());
+    /// ```
+    pub fn parse_lenient(s: &str) -> Result<Version, ParseError> {
+        parse(s, false)
+    }
+
+    /// 纪元, 未指定时为 0
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// 上游版本号
+    pub fn upstream(&self) -> &str {
+        &self.upstream
+    }
+
+    /// Debian 修订号, 未指定时为空字符串
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch.cmp(&other.epoch)
+            .then_with(|| verrevcmp(&self.upstream, &other.upstream))
+            .then_with(|| verrevcmp(&self.revision, &other.revision))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致, 使用去掉前导零的规范形式
+        self.epoch.hash(state);
+        canonical(&self.upstream).hash(state);
+        canonical(&self.revision).hash(state);
+    }
+}
+
+/// 以 `[epoch:]upstream[-revision]` 形式输出, 纪元为 0 或修订号为空时省略
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+        f.write_str(&self.upstream)?;
+        if !self.revision.is_empty() {
+            write!(f, "-{}", self.revision)?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 Debian 版本号
+///
+/// 与 dpkg 的规则一致: 第一个 `:` 之前为纪元, 最后一个 `-` 之后为修订号,
+/// 上游版本号只能包含字母数字与 `.+-~:`, 修订号只能包含字母数字与 `.+~`
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        parse(s, true)
+    }
+}
+
+/// 解析版本号, `strict` 为 `false` 时跳过 dpkg 只给出警告的检查
+fn parse(s: &str, strict: bool) -> Result<Version, ParseError> {
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(ParseError::Empty)
+    }
+    if s.contains(char::is_whitespace) {
+        return Err(ParseError::EmbeddedSpace)
+    }
+
+    // 纪元
+    let (epoch, rest) = match s.split_once(':') {
+        Some((epoch, rest)) => {
+            if epoch.is_empty() {
+                return Err(ParseError::EpochEmpty)
+            }
+            if epoch.strip_prefix('-').is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit())) {
+                return Err(ParseError::EpochNegative)
+            }
+            if !epoch.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseError::EpochNotNumber)
+            }
+            let epoch = epoch.parse::<u32>().ok().filter(|n| *n <= i32::MAX as u32).ok_or(ParseError::EpochTooBig)?;
+            if rest.is_empty() {
+                return Err(ParseError::NothingAfterColon)
+            }
+            (epoch, rest)
+        }
+        None => (0, s),
+    };
+
+    // 修订号
+    let (upstream, revision) = match rest.rsplit_once('-') {
+        Some((_, "")) => return Err(ParseError::RevisionEmpty),
+        Some((upstream, revision)) => (upstream, revision),
+        None => (rest, ""),
+    };
+
+    if upstream.is_empty() {
+        return Err(ParseError::UpstreamEmpty)
+    }
+    if strict {
+        if !upstream.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(ParseError::UpstreamNotStartWithDigit)
+        }
+        if let Some(c) = upstream.chars().find(|c| !c.is_ascii_alphanumeric() && !".-+~:".contains(*c)) {
+            return Err(ParseError::InvalidUpstreamCharacter(c))
+        }
+        if let Some(c) = revision.chars().find(|c| !c.is_ascii_alphanumeric() && !".+~".contains(*c)) {
+            return Err(ParseError::InvalidRevisionCharacter(c))
+        }
+    }
+
+    Ok(Version {
+        epoch,
+        upstream: upstream.to_string(),
+        revision: revision.to_string(),
+    })
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+/// 单个字符的排序权重, 字符串结尾视为 0
+///
+/// 数字为 0, 字母为其本身, `~` 为 -1, 其他符号排在所有字母之后
+fn order(c: u8) -> i32 {
+    match c {
+        b'0'..=b'9' | 0 => 0,
+        b'~' => -1,
+        c if c.is_ascii_alphabetic() => c as i32,
+        c => c as i32 + 256,
+    }
+}
+
+/// dpkg 的 `verrevcmp`: 交替比较非数字部分与数字部分
+fn verrevcmp(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let at = |i: usize| a.get(i).copied().unwrap_or(0);
+    let bt = |j: usize| b.get(j).copied().unwrap_or(0);
+    let (mut i, mut j) = (0, 0);
+
+    while i < a.len() || j < b.len() {
+        // 非数字部分逐字符比较
+        while (i < a.len() && !at(i).is_ascii_digit()) || (j < b.len() && !bt(j).is_ascii_digit()) {
+            let (ac, bc) = (order(at(i)), order(bt(j)));
+            if ac != bc {
+                return ac.cmp(&bc)
+            }
+            i += 1;
+            j += 1;
+        }
+
+        // 数字部分去掉前导零后按数值比较
+        while at(i) == b'0' {
+            i += 1;
+        }
+        while bt(j) == b'0' {
+            j += 1;
+        }
+        let mut first_diff = Ordering::Equal;
+        while at(i).is_ascii_digit() && bt(j).is_ascii_digit() {
+            if first_diff == Ordering::Equal {
+                first_diff = at(i).cmp(&bt(j));
+            }
+            i += 1;
+            j += 1;
+        }
+        if at(i).is_ascii_digit() {
+            return Ordering::Greater
+        }
+        if bt(j).is_ascii_digit() {
+            return Ordering::Less
+        }
+        if first_diff != Ordering::Equal {
+            return first_diff
+        }
+    }
+    Ordering::Equal
+}
+
+/// 生成与 `verrevcmp` 相等关系一致的规范形式:
+/// 去掉每段数字的前导零, 并去掉末尾值为零的数字段
+fn canonical(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while !rest.is_empty() {
+        let non_digit = rest.find(|c: char| c.is_ascii_digit()).unwrap_or(rest.len());
+        out.push_str(&rest[..non_digit]);
+        rest = &rest[non_digit..];
+        let digit = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let trimmed = rest[..digit].trim_start_matches('0');
+        rest = &rest[digit..];
+        if !trimmed.is_empty() {
+            out.push_str(trimmed);
+        } else if digit > 0 && !rest.is_empty() {
+            out.push('0');
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Version};
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 比较结果取自 dpkg 的测试用例(经由 apt 的 versions.lst 整理)
+    #[test]
+    fn test_compare_table() {
+        let cases = [
+            ("7.6p2-4", "7.6-0", Ordering::Greater),
+            ("1.0.3-3", "1.0-1", Ordering::Greater),
+            ("1.3", "1.2.2-2", Ordering::Greater),
+            ("1.3", "1.2.2", Ordering::Greater),
+            ("0-pre", "0-pre", Ordering::Equal),
+            ("0-pre", "0-pree", Ordering::Less),
+            ("1.1.6r2-2", "1.1.6r-1", Ordering::Greater),
+            ("2.6b2-1", "2.6b-2", Ordering::Greater),
+            ("98.1p5-1", "98.1-pre2-b6-2", Ordering::Less),
+            ("0.4a6-2", "0.4-1", Ordering::Greater),
+            ("1:3.0.5-2", "1:3.0.5.1", Ordering::Less),
+            ("3.0~rc1-1", "3.0-1", Ordering::Less),
+            ("1.0", "1.0-0", Ordering::Equal),
+            ("0.2", "1.0-0", Ordering::Less),
+            ("1.0", "1.0-0+b1", Ordering::Less),
+            ("1.0", "1.0-0~", Ordering::Greater),
+            ("0:0-0-0", "0-0", Ordering::Greater),
+            ("0", "0", Ordering::Equal),
+            ("0", "00", Ordering::Equal),
+            ("1.2.3", "1.2.3", Ordering::Equal),
+            ("4.4.3-2", "4.4.3-2", Ordering::Equal),
+            ("1:2ab:5", "1:2ab:5", Ordering::Equal),
+            ("7:1-a:b-5", "7:1-a:b-5", Ordering::Equal),
+            ("57:1.2.3abYZ+~-4-5", "57:1.2.3abYZ+~-4-5", Ordering::Equal),
+            ("1.2.3", "0:1.2.3", Ordering::Equal),
+            ("1.2.3", "1.2.3-0", Ordering::Equal),
+            ("009", "9", Ordering::Equal),
+            ("009ab5", "9ab5", Ordering::Equal),
+            ("1.2.3", "1.2.3-1", Ordering::Less),
+            ("1.2.3", "1.2.4", Ordering::Less),
+            ("1.2.4", "1.2.3", Ordering::Greater),
+            ("1.2.24", "1.2.3", Ordering::Greater),
+            ("0.10.0", "0.8.7", Ordering::Greater),
+            ("3.2", "2.3", Ordering::Greater),
+            ("1.3.2a", "1.3.2", Ordering::Greater),
+            ("0.5.0~git", "0.5.0~git2", Ordering::Less),
+            ("2a", "21", Ordering::Less),
+            ("1.3.2a", "1.3.2b", Ordering::Less),
+            ("1:1.2.3", "1.2.4", Ordering::Greater),
+            ("1:1.2.3", "1:1.2.4", Ordering::Less),
+            ("1.2a+~bCd3", "1.2a++", Ordering::Less),
+            ("1.2a+~bCd3", "1.2a+~", Ordering::Greater),
+            ("5:2", "304-2", Ordering::Greater),
+            ("5:2", "304:2", Ordering::Less),
+            ("25:2", "3:2", Ordering::Greater),
+            ("1:2:123", "1:12:3", Ordering::Less),
+            ("1.2-5", "1.2-3-5", Ordering::Less),
+            ("5.10.0", "5.005", Ordering::Greater),
+            ("3a9.8", "3.10.2", Ordering::Less),
+            ("3a9.8", "3~10", Ordering::Greater),
+            ("1.4+OOo3.0.0~", "1.4+OOo3.0.0-4", Ordering::Less),
+            ("2.4.7-1", "2.4.7-z", Ordering::Less),
+            ("1.002-1+b2", "1.00", Ordering::Greater),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(v(a).cmp(&v(b)), expected, "{a} <=> {b}");
+            assert_eq!(v(b).cmp(&v(a)), expected.reverse(), "{b} <=> {a}");
+        }
+    }
+
+    /// 测试波浪号排在一切之前
+    #[test]
+    fn test_tilde() {
+        let ordered = ["1.0~~", "1.0~~a", "1.0~", "1.0", "1.0a", "1.0+", "1.0.1"];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+    }
+
+    /// 测试相等的版本号具有相同的哈希值
+    #[test]
+    fn test_hash_consistent_with_eq() {
+        let set: HashSet<Version> = ["1.0", "1.0-0", "0:1.0", "01.00", "1.00-00"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(v("1.0."), v("1.0.0"));
+        let set: HashSet<Version> = ["1.0.", "1.0.0", "1.0.00"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    /// 测试解析错误, 与 dpkg 的错误信息对应
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            ("0:0 0-1", ParseError::EmbeddedSpace),
+            (":1.0", ParseError::EpochEmpty),
+            ("a:0-0", ParseError::EpochNotNumber),
+            ("-1:0-0", ParseError::EpochNegative),
+            ("1-:0-0", ParseError::EpochNotNumber),
+            ("999999999999999999999:1.0", ParseError::EpochTooBig),
+            ("2147483648:1.0", ParseError::EpochTooBig),
+            ("1:", ParseError::NothingAfterColon),
+            ("1.0-", ParseError::RevisionEmpty),
+            ("-1", ParseError::UpstreamEmpty),
+            ("a1.0", ParseError::UpstreamNotStartWithDigit),
+            ("1.0_1", ParseError::InvalidUpstreamCharacter('_')),
+            ("1:1.0-1:2", ParseError::InvalidRevisionCharacter(':')),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+        assert_eq!(v(" 2147483647:1.0-1 ").to_string(), "2147483647:1.0-1");
+    }
+
+    /// 测试宽松解析只放过 dpkg 给出警告的问题
+    #[test]
+    fn test_parse_lenient() {
+        let lenient = |s: &str| Version::parse_lenient(s).unwrap_or_else(|e| panic!("{s}: {e}"));
+        assert_eq!(lenient("a1.0").upstream(), "a1.0");
+        assert_eq!(lenient("1:1.0_1-1:2").to_string(), "1:1.0_1-1:2");
+        assert!(lenient("a1.0") > lenient("1.0"));
+        assert!(lenient("1.0_1") > lenient("1.0+1"));
+        assert_eq!(lenient("1.0-1"), v("1.0-1"));
+        for (input, expected) in [("1.0 1", ParseError::EmbeddedSpace), ("-1:1.0", ParseError::EpochNegative), ("1.0-", ParseError::RevisionEmpty)] {
+            assert_eq!(Version::parse_lenient(input).unwrap_err(), expected, "{input}");
+        }
+    }
+}
@@ -0,0 +1,509 @@
+//! Go 模块的版本号, 规则与 `golang.org/x/mod` 中的 `semver` 与 `module` 包一致
+//!
+//! 版本号以 `v` 开头, 主版本号为 2 及以上但没有迁移到 `/vN` 路径的模块使用 `+incompatible` 后缀。
+//! 没有打标签的提交使用伪版本号, 共有三种形式:
+//!
+//! - `vX.0.0-yyyymmddhhmmss-abcdefabcdef`: 之前没有任何标签
+//! - `vX.Y.Z-pre.0.yyyymmddhhmmss-abcdefabcdef`: 基于先行版本 `vX.Y.Z-pre`
+//! - `vX.Y.(Z+1)-0.yyyymmddhhmmss-abcdefabcdef`: 基于正式版本 `vX.Y.Z`
+//!
+//! ```
+//! use version::go::Version;
+//!
+//! let v: Version = "v1.2.4-0.20191109021931-daa7c04131f5".parse().unwrap();
+//! let pseudo = v.pseudo().unwrap();
+//! assert_eq!(pseudo.timestamp().to_string(), "2019-11-09T02:19:31Z");
+//! assert_eq!(pseudo.revision(), "daa7c04131f5");
+//! assert_eq!(pseudo.base().unwrap().to_string(), "v1.2.3");
+//! assert!(v > "v1.2.3".parse().unwrap());
+//! ```
+
+use crate::calver::Date;
+use crate::Identifier;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 Go 模块版本号
+///
+/// 版本号之间按照 `go list -m -versions` 的顺序比较: 先按 SemVer 规则比较,
+/// 相同时不带 `+incompatible` 的排在前面
+#[derive(Debug, Clone)]
+pub struct Version {
+    version: crate::Version,
+    incompatible: bool,
+    pseudo: Option<Pseudo>,
+}
+
+///
+/// 伪版本号中包含的信息
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pseudo {
+    timestamp: Timestamp,
+    revision: String,
+    base: Option<Box<Version>>,
+}
+
+///
+/// 伪版本号中的提交时间, 使用 UTC 时间
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp {
+    date: Date,
+    hour: u8,
+    minute: u8,
+    second: u8,
+}
+
+///
+/// 解析 Go 模块版本号时可能发生的错误
+#[derive(Error, Debug)]
+pub enum ParseError {
+
+    #[error("Go 模块版本号必须以 'v' 开头")]
+    MissingPrefix,
+
+    #[error(transparent)]
+    Version(#[from] crate::ParseError),
+
+    #[error("简写的版本号 \"{0}\" 不能带有先行版本号或编译元数据")]
+    IncompleteVersion(String),
+
+    #[error("Go 模块版本号只允许 +incompatible 编译元数据, 而不是 \"+{0}\"")]
+    InvalidBuild(String),
+
+    #[error("主版本号 v{0} 不需要 +incompatible 后缀")]
+    IncompatibleMajor(u64),
+
+    #[error("非法的伪版本号 \"{0}\"")]
+    InvalidPseudoVersion(String),
+}
+
+///
+/// 检查模块路径与版本号是否匹配时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum PathError {
+
+    #[error("非法的模块路径 \"{0}\"")]
+    InvalidPath(String),
+
+    #[error("模块路径要求主版本号为 {expected}, 实际为 v{found}")]
+    MajorMismatch { expected: String, found: u64 },
+
+    #[error("模块路径带有主版本号后缀, 不能使用 +incompatible 版本")]
+    IncompatibleWithSuffix,
+}
+
+impl Version {
+
+    /// 对应的 SemVer 版本号, 不包含 `+incompatible`
+    pub fn semver(&self) -> &crate::Version {
+        &self.version
+    }
+
+    /// 主版本号
+    pub fn major(&self) -> u64 {
+        self.version.major
+    }
+
+    /// 是否带有 `+incompatible` 后缀
+    pub fn is_incompatible(&self) -> bool {
+        self.incompatible
+    }
+
+    /// 是否为先行版本, 伪版本号总是先行版本
+    pub fn is_prerelease(&self) -> bool {
+        !self.version.pre.is_empty()
+    }
+
+    /// 伪版本号的信息, 不是伪版本号时为 `None`
+    pub fn pseudo(&self) -> Option<&Pseudo> {
+        self.pseudo.as_ref()
+    }
+
+    /// 检查模块路径的主版本号后缀是否与版本号匹配, 与 `module.CheckPathMajor` 一致
+    ///
+    /// 没有后缀的路径只能使用 v0、v1 或带有 `+incompatible` 的版本号,
+    /// 带有 `/vN`(或 `gopkg.in` 的 `.vN`)后缀的路径只能使用主版本号为 N 的版本号
+    ///
+    /// ```
+    /// use version::go::Version;
+    ///
+    /// let v: Version = "v2.1.0".parse().unwrap();
+    /// assert!(v.check_path("github.com/foo/bar/v2").is_ok());
+    /// assert!(v.check_path("github.com/foo/bar").is_err());
+    /// ```
+    pub fn check_path(&self, path: &str) -> Result<(), PathError> {
+        let (_, suffix) = split_path_version(path).ok_or_else(|| PathError::InvalidPath(path.to_string()))?;
+        if suffix.is_empty() {
+            if self.major() <= 1 || self.incompatible {
+                return Ok(())
+            }
+            return Err(PathError::MajorMismatch { expected: "v0 或 v1".to_string(), found: self.major() })
+        }
+
+        if self.incompatible {
+            return Err(PathError::IncompatibleWithSuffix)
+        }
+        let suffix = suffix.trim_end_matches("-unstable");
+        // 兼容早期为 gopkg.in 的 .v1 路径生成的 v0.0.0 伪版本号
+        let is_v0_pseudo = self.pseudo.as_ref().is_some_and(|p| p.base.is_none()) && self.major() == 0;
+        if suffix == ".v1" && is_v0_pseudo {
+            return Ok(())
+        }
+        let expected = &suffix[1..];
+        if expected[1..].parse::<u64>().is_ok_and(|n| n == self.major()) {
+            return Ok(())
+        }
+        Err(PathError::MajorMismatch { expected: expected.to_string(), found: self.major() })
+    }
+}
+
+impl Pseudo {
+
+    /// 提交时间
+    pub fn timestamp(&self) -> Timestamp {
+        self.timestamp
+    }
+
+    /// 提交哈希的前缀
+    pub fn revision(&self) -> &str {
+        &self.revision
+    }
+
+    /// 伪版本号所基于的标签, 第一种形式没有基础版本
+    pub fn base(&self) -> Option<&Version> {
+        self.base.as_deref()
+    }
+}
+
+impl Timestamp {
+
+    /// 日期
+    pub fn date(&self) -> Date {
+        self.date
+    }
+
+    /// 时
+    pub fn hour(&self) -> u8 {
+        self.hour
+    }
+
+    /// 分
+    pub fn minute(&self) -> u8 {
+        self.minute
+    }
+
+    /// 秒
+    pub fn second(&self) -> u8 {
+        self.second
+    }
+
+    /// 解析 `yyyymmddhhmmss` 形式的时间
+    fn parse(s: &str) -> Option<Timestamp> {
+        if s.len() != 14 || !s.bytes().all(|b| b.is_ascii_digit()) {
+            return None
+        }
+        let field = |range: std::ops::Range<usize>| s[range].parse::<u16>().ok();
+        let date = Date::new(field(0..4)?, field(4..6)? as u8, field(6..8)? as u8)?;
+        let (hour, minute, second) = (field(8..10)? as u8, field(10..12)? as u8, field(12..14)? as u8);
+        if hour > 23 || minute > 59 || second > 59 {
+            return None
+        }
+        Some(Timestamp { date, hour, minute, second })
+    }
+}
+
+/// 以 RFC 3339 形式输出, 例如 `2019-11-09T02:19:31Z`
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}T{:02}:{:02}:{:02}Z", self.date, self.hour, self.minute, self.second)
+    }
+}
+
+///
+/// 拆分模块路径与主版本号后缀, 与 `module.SplitPathVersion` 一致
+///
+/// 返回 `(前缀, 后缀)`, 没有后缀时后缀为空字符串; 后缀不合法(例如 `/v1`、`/v0`、`/v2.1`)时返回 `None`
+///
+/// ```
+/// use version::go::split_path_version;
+///
+/// assert_eq!(split_path_version("github.com/foo/bar/v3"), Some(("github.com/foo/bar", "/v3")));
+/// assert_eq!(split_path_version("gopkg.in/yaml.v2"), Some(("gopkg.in/yaml", ".v2")));
+/// assert_eq!(split_path_version("github.com/foo/bar"), Some(("github.com/foo/bar", "")));
+/// assert_eq!(split_path_version("github.com/foo/bar/v1"), None);
+/// ```
+pub fn split_path_version(path: &str) -> Option<(&str, &str)> {
+    let bytes = path.as_bytes();
+    if path.starts_with("gopkg.in/") {
+        let mut i = path.strip_suffix("-unstable").unwrap_or(path).len();
+        while i > 0 && bytes[i - 1].is_ascii_digit() {
+            i -= 1;
+        }
+        // gopkg.in 的路径必须以 .vN 结尾
+        if i <= 1 || bytes[i - 1] != b'v' || bytes[i - 2] != b'.' {
+            return None
+        }
+        let (prefix, major) = path.split_at(i - 2);
+        if major.len() <= 2 || (major.as_bytes()[2] == b'0' && major != ".v0") {
+            return None
+        }
+        return Some((prefix, major))
+    }
+
+    let mut i = path.len();
+    let mut dot = false;
+    while i > 0 && (bytes[i - 1].is_ascii_digit() || bytes[i - 1] == b'.') {
+        dot |= bytes[i - 1] == b'.';
+        i -= 1;
+    }
+    if i <= 1 || i == path.len() || bytes[i - 1] != b'v' || bytes[i - 2] != b'/' {
+        return Some((path, ""))
+    }
+    let (prefix, major) = path.split_at(i - 2);
+    if dot || major.len() <= 2 || major.as_bytes()[2] == b'0' || major == "/v1" {
+        return None
+    }
+    Some((prefix, major))
+}
+
+/// 识别伪版本号并拆分出时间、提交哈希与基础版本
+///
+/// 与 `module.IsPseudoVersion` 的正则表达式等价: 最后一个先行版本标识符为 `yyyymmddhhmmss-rev`,
+/// 并且要么版本号为 `vX.0.0` 且只有这一个标识符, 要么它前面的标识符为 `0`
+fn parse_pseudo(s: &str, version: &crate::Version, incompatible: bool) -> Result<Option<Pseudo>, ParseError> {
+    let invalid = || ParseError::InvalidPseudoVersion(s.to_string());
+    let Some((Identifier::AlphaNumeric(last), rest)) = version.pre.split_last() else {
+        return Ok(None)
+    };
+    let Some((timestamp, revision)) = last.split_once('-') else {
+        return Ok(None)
+    };
+    let is_pseudo_tail = timestamp.len() == 14 && timestamp.bytes().all(|b| b.is_ascii_digit())
+        && !revision.is_empty() && revision.bytes().all(|b| b.is_ascii_alphanumeric());
+    if !is_pseudo_tail {
+        return Ok(None)
+    }
+
+    let base = match rest.split_last() {
+        // vX.0.0-yyyymmddhhmmss-abcdef
+        None if version.minor == 0 && version.patch == 0 => {
+            if incompatible {
+                return Err(invalid())
+            }
+            None
+        }
+        // vX.Y.(Z+1)-0.yyyymmddhhmmss-abcdef
+        Some((Identifier::Numeric(0), [])) => {
+            let patch = version.patch.checked_sub(1).ok_or_else(invalid)?;
+            let base = crate::Version::new(version.major, version.minor, patch);
+            Some(Box::new(Version { version: base, incompatible, pseudo: None }))
+        }
+        // vX.Y.Z-pre.0.yyyymmddhhmmss-abcdef
+        Some((Identifier::Numeric(0), pre)) => {
+            let base = crate::Version { pre: pre.to_vec(), ..crate::Version::new(version.major, version.minor, version.patch) };
+            Some(Box::new(Version { version: base, incompatible, pseudo: None }))
+        }
+        _ => return Ok(None),
+    };
+
+    let timestamp = Timestamp::parse(timestamp).ok_or_else(invalid)?;
+    Ok(Some(Pseudo { timestamp, revision: revision.to_string(), base }))
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.version.cmp(&other.version).then(self.incompatible.cmp(&other.incompatible))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.version.hash(state);
+        self.incompatible.hash(state);
+    }
+}
+
+/// 以规范形式输出, 简写的 `v1.2` 输出为 `v1.2.0`
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{:#}", self.version)?;
+        if self.incompatible {
+            f.write_str("+incompatible")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 Go 模块版本号
+///
+/// 接受 `v1`、`v1.2` 这样的简写, 编译元数据只允许 `+incompatible`
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let rest = s.strip_prefix('v').ok_or(ParseError::MissingPrefix)?;
+        let (rest, build) = match rest.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (rest, None),
+        };
+        let incompatible = match build {
+            Some("incompatible") => true,
+            Some(build) => return Err(ParseError::InvalidBuild(build.to_string())),
+            None => false,
+        };
+
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (rest, None),
+        };
+        let version = match core.matches('.').count() {
+            0 | 1 if pre.is_some() || build.is_some() => return Err(ParseError::IncompleteVersion(s.to_string())),
+            0 => format!("{core}.0").parse::<crate::Version>()?,
+            _ => rest.parse::<crate::Version>()?,
+        };
+
+        if incompatible && version.major < 2 {
+            return Err(ParseError::IncompatibleMajor(version.major))
+        }
+        let pseudo = parse_pseudo(s, &version, incompatible)?;
+        Ok(Version { version, incompatible, pseudo })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, PathError, Version};
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(v("v1").to_string(), "v1.0.0");
+        assert_eq!(v("v1.2").to_string(), "v1.2.0");
+        assert_eq!(v("v1.2.3-rc.1").to_string(), "v1.2.3-rc.1");
+        assert!(v("v2.3.4+incompatible").is_incompatible());
+        assert_eq!(v("v2.3.4+incompatible").to_string(), "v2.3.4+incompatible");
+
+        let cases = [
+            ("1.2.3", ParseError::MissingPrefix),
+            ("v1.2.3+build", ParseError::InvalidBuild("build".to_string())),
+            ("v1.0.0+incompatible", ParseError::IncompatibleMajor(1)),
+            ("v1-pre", ParseError::IncompleteVersion("v1-pre".to_string())),
+            ("v1.2+incompatible", ParseError::IncompleteVersion("v1.2+incompatible".to_string())),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(format!("{:?}", input.parse::<Version>().unwrap_err()), format!("{expected:?}"), "{input}");
+        }
+        assert!(matches!("v01.2.3".parse::<Version>(), Err(ParseError::Version(_))));
+        assert!(matches!("v1.2.3.4".parse::<Version>(), Err(ParseError::Version(_))));
+    }
+
+    /// 测试三种伪版本号
+    #[test]
+    fn test_pseudo() {
+        let cases = [
+            ("v0.0.0-20191109021931-daa7c04131f5", None),
+            ("v1.2.4-0.20191109021931-daa7c04131f5", Some("v1.2.3")),
+            ("v1.2.3-pre.0.20191109021931-daa7c04131f5", Some("v1.2.3-pre")),
+            ("v2.0.1-0.20191109021931-daa7c04131f5+incompatible", Some("v2.0.0+incompatible")),
+        ];
+        for (input, base) in cases {
+            let version = v(input);
+            let pseudo = version.pseudo().unwrap_or_else(|| panic!("{input}"));
+            assert_eq!(pseudo.timestamp().to_string(), "2019-11-09T02:19:31Z");
+            assert_eq!(pseudo.revision(), "daa7c04131f5");
+            assert_eq!(pseudo.base().map(ToString::to_string).as_deref(), base, "{input}");
+            assert!(version.is_prerelease());
+        }
+
+        for input in ["v1.2.3", "v1.2.3-20191109021931-daa7c04131f5", "v1.2.3-0.2019110902193-daa7c04131f5", "v1.2.3-1.20191109021931-abc"] {
+            assert!(v(input).pseudo().is_none(), "{input}");
+        }
+
+        for input in ["v1.2.0-0.20191109021931-daa7c04131f5", "v0.0.0-20191332021931-daa7c04131f5", "v2.0.0-20191109021931-daa7c04131f5+incompatible"] {
+            assert!(matches!(input.parse::<Version>(), Err(ParseError::InvalidPseudoVersion(s)) if s == input), "{input}");
+        }
+    }
+
+    /// 测试与 go list -m -versions 一致的排序
+    #[test]
+    fn test_ordering() {
+        let ordered = [
+            "v0.0.0-20170915032832-14c0d48ead0c",
+            "v0.0.0-20191109021931-daa7c04131f5",
+            "v0.1.0",
+            "v1.2.3-pre",
+            "v1.2.3-pre.0.20191109021931-daa7c04131f5",
+            "v1.2.3",
+            "v1.2.4-0.20191109021931-daa7c04131f5",
+            "v1.2.4",
+            "v2.0.0",
+            "v2.0.0+incompatible",
+            "v2.0.1-0.20191109021931-daa7c04131f5+incompatible",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(v("v1.2"), v("v1.2.0"));
+    }
+
+    /// 测试模块路径的主版本号后缀
+    #[test]
+    fn test_check_path() {
+        let ok = [
+            ("github.com/foo/bar", "v1.2.3"),
+            ("github.com/foo/bar", "v0.0.0-20191109021931-daa7c04131f5"),
+            ("github.com/foo/bar", "v2.0.0+incompatible"),
+            ("github.com/foo/bar/v2", "v2.1.0"),
+            ("github.com/foo/bar/v10", "v10.0.0-rc.1"),
+            ("gopkg.in/yaml.v2", "v2.4.0"),
+            ("gopkg.in/check.v1", "v0.0.0-20161208181325-20d25e280405"),
+        ];
+        for (path, version) in ok {
+            assert_eq!(v(version).check_path(path), Ok(()), "{path}@{version}");
+        }
+
+        let mismatch = |expected: &str, found| PathError::MajorMismatch { expected: expected.to_string(), found };
+        let err = [
+            ("github.com/foo/bar", "v2.0.0", mismatch("v0 或 v1", 2)),
+            ("github.com/foo/bar/v2", "v3.0.0", mismatch("v2", 3)),
+            ("github.com/foo/bar/v2", "v1.0.0", mismatch("v2", 1)),
+            ("gopkg.in/yaml.v2", "v3.0.0", mismatch("v2", 3)),
+            ("github.com/foo/bar/v2", "v2.0.0+incompatible", PathError::IncompatibleWithSuffix),
+            ("github.com/foo/bar/v1", "v1.0.0", PathError::InvalidPath("github.com/foo/bar/v1".to_string())),
+            ("github.com/foo/bar/v02", "v2.0.0", PathError::InvalidPath("github.com/foo/bar/v02".to_string())),
+            ("gopkg.in/yaml", "v2.0.0", PathError::InvalidPath("gopkg.in/yaml".to_string())),
+        ];
+        for (path, version, expected) in err {
+            assert_eq!(v(version).check_path(path), Err(expected), "{path}@{version}");
+        }
+    }
+}
@@ -0,0 +1,350 @@
+//! HashiCorp `go-version` 风格的版本约束, Terraform 的 `required_version` 与模块版本约束使用这种写法
+//!
+//! 约束由以 `,` 分隔的条件组成, 例如 `~> 1.5.0, != 1.5.3`, 所有条件均满足时版本才满足约束。
+//! 约束中的版本号基于 [`crate::Version`], 但按照 `go-version` 的规则解析: 允许 `v` 前缀、
+//! 1 到 3 个部分以及不带 `-` 的先行版本号, 例如 `v1.2`、`1.2.0beta1`
+//!
+//! 与 `go-version` 一致, 带有先行版本号的版本只能匹配引用了同一 主.副.补丁 先行版本的条件
+//!
+//! 由于版本号使用 [`crate::Version`] 表示, 与 `go-version` 有两处不同:
+//! - 数字部分最多 3 个, `1.2.3.4` 返回 [`ParseError::TooManySegments`], 而 `go-version` 接受任意个部分
+//! - 先行版本号按照 SemVer 校验, `1.0.0-rc.01` 这样含有前导零的数字标识符会被拒绝, 而 `go-version` 接受
+//!
+//! ```
+//! use version::hashicorp::{parse_version, Constraints};
+//!
+//! let constraints: Constraints = "~> 1.5.0, != 1.5.3".parse().unwrap();
+//! assert!(constraints.check(&parse_version("1.5.7").unwrap()));
+//! assert!(!constraints.check(&parse_version("1.5.3").unwrap()));
+//! assert!(!constraints.check(&parse_version("1.6.0").unwrap()));
+//! ```
+
+use crate::{parse_build, parse_pre, Version};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 以 `,` 分隔的一组条件, 对应 `go-version` 的 `Constraints`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraints {
+    constraints: Vec<Constraint>,
+}
+
+///
+/// 单个条件, 由运算符与版本号组成, 例如 `>= 1.2.0`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Constraint {
+    op: Op,
+    version: Version,
+    /// 书写时的部分数, `~>` 据此决定可以升级的部分
+    segments: usize,
+    original: String,
+}
+
+///
+/// 条件的运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `=`, 未写运算符时的默认值
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `>`
+    Greater,
+    /// `<`
+    Less,
+    /// `>=`
+    GreaterEqual,
+    /// `<=`
+    LessEqual,
+    /// `~>`, 只允许最后写出的部分增大, `~> 1.2` 允许 `1.x`, `~> 1.2.0` 只允许 `1.2.x`
+    Pessimistic,
+}
+
+///
+/// 解析约束或 `go-version` 风格的版本号时可能发生的错误
+#[derive(Error, Debug)]
+pub enum ParseError {
+
+    #[error("存在空的条件")]
+    EmptyConstraint,
+
+    #[error("非法的条件 \"{0}\"")]
+    MalformedConstraint(String),
+
+    #[error("非法的版本号 \"{0}\"")]
+    MalformedVersion(String),
+
+    #[error("版本号有 {0} 个部分, 最多只能有 3 个")]
+    TooManySegments(usize),
+
+    #[error("版本号解析失败: {0}")]
+    Version(#[from] crate::ParseError),
+}
+
+impl Constraints {
+
+    /// 组成约束的所有条件
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// 判断版本号是否满足所有条件
+    pub fn check(&self, version: &Version) -> bool {
+        self.constraints.iter().all(|c| c.check(version))
+    }
+}
+
+impl Constraint {
+
+    /// 运算符
+    pub fn op(&self) -> Op {
+        self.op
+    }
+
+    /// 条件中的版本号, 缺省的部分补零
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// 判断版本号是否满足条件
+    pub fn check(&self, version: &Version) -> bool {
+        let c = &self.version;
+        match self.op {
+            Op::Equal => version == c,
+            Op::NotEqual => version != c,
+            Op::Greater => self.prerelease_check(version) && version > c,
+            Op::Less => self.prerelease_check(version) && version < c,
+            Op::GreaterEqual => self.prerelease_check(version) && version >= c,
+            Op::LessEqual => self.prerelease_check(version) && version <= c,
+            Op::Pessimistic => self.check_pessimistic(version),
+        }
+    }
+
+    /// 先行版本只能匹配带有先行版本号且 主.副.补丁 相同的条件
+    fn prerelease_check(&self, version: &Version) -> bool {
+        match (version.pre.is_empty(), self.version.pre.is_empty()) {
+            (false, false) => numbers(version) == numbers(&self.version),
+            (false, true) => false,
+            _ => true,
+        }
+    }
+
+    /// 与 `constraintPessimistic` 一致: 写出的部分中, 除最后一个外都必须相同, 最后一个不能更小
+    fn check_pessimistic(&self, version: &Version) -> bool {
+        // 带有先行版本号的 `~>` 只匹配先行版本
+        if !self.prerelease_check(version) || (!self.version.pre.is_empty() && version.pre.is_empty()) {
+            return false
+        }
+        if *version < self.version {
+            return false
+        }
+        let (v, c) = (numbers(version), numbers(&self.version));
+        let last = self.segments - 1;
+        v[..last] == c[..last] && v[last] >= c[last]
+    }
+}
+
+fn numbers(version: &Version) -> [u64; 3] {
+    [version.major, version.minor, version.patch]
+}
+
+/// 按照 `go-version` 的规则解析版本号
+///
+/// 允许 `v` 前缀与首尾空白; 数字部分有 1 到 3 个, 缺省的部分补零, 允许前导零;
+/// 以字母开头的先行版本号可以省略 `-`, 例如 `1.2.0beta1` 等同于 `1.2.0-beta1`
+///
+/// ```
+/// use version::hashicorp::parse_version;
+///
+/// assert_eq!(parse_version("v1.2").unwrap().to_string(), "1.2.0");
+/// assert_eq!(parse_version("1.02.0beta1").unwrap().to_string(), "1.2.0-beta1");
+/// ```
+pub fn parse_version(s: &str) -> Result<Version, ParseError> {
+    parse_with_segments(s).map(|(version, _)| version)
+}
+
+/// 解析版本号, 同时返回书写时的部分数
+fn parse_with_segments(s: &str) -> Result<(Version, usize), ParseError> {
+    let malformed = || ParseError::MalformedVersion(s.to_string());
+    let trimmed = s.trim();
+    let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+    let (rest, build) = match trimmed.split_once('+') {
+        Some((rest, build)) => (rest, parse_build(build)?),
+        None => (trimmed, Vec::new()),
+    };
+    let core_len = rest.bytes().take_while(|b| b.is_ascii_digit() || *b == b'.').count();
+    let (core, pre) = rest.split_at(core_len);
+    let pre = match pre.strip_prefix('-') {
+        Some(pre) => parse_pre(pre)?,
+        None if pre.is_empty() => Vec::new(),
+        None if pre.starts_with(|c: char| c.is_ascii_alphabetic()) => parse_pre(pre)?,
+        None => return Err(malformed()),
+    };
+
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.iter().any(|p| p.is_empty()) {
+        return Err(malformed())
+    }
+    if parts.len() > 3 {
+        return Err(ParseError::TooManySegments(parts.len()))
+    }
+    let mut numbers = [0; 3];
+    for (n, part) in numbers.iter_mut().zip(&parts) {
+        *n = part.parse().map_err(|_| malformed())?;
+    }
+    let [major, minor, patch] = numbers;
+    Ok((Version { major, minor, patch, pre, build }, parts.len()))
+}
+
+impl fmt::Display for Constraints {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{constraint}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 输出书写时的条件, 去掉首尾空白
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.original)
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::Equal => "=",
+            Op::NotEqual => "!=",
+            Op::Greater => ">",
+            Op::Less => "<",
+            Op::GreaterEqual => ">=",
+            Op::LessEqual => "<=",
+            Op::Pessimistic => "~>",
+        })
+    }
+}
+
+/// 解析以 `,` 分隔的条件
+impl FromStr for Constraints {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Constraints, ParseError> {
+        let constraints = s.split(',').map(str::parse).collect::<Result<Vec<Constraint>, ParseError>>()?;
+        Ok(Constraints { constraints })
+    }
+}
+
+impl TryFrom<&str> for Constraints {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Constraints, ParseError> {
+        s.parse()
+    }
+}
+
+/// 解析单个条件, 运算符与版本号之间允许存在空白
+impl FromStr for Constraint {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Constraint, ParseError> {
+        let original = s.trim();
+        if original.is_empty() {
+            return Err(ParseError::EmptyConstraint)
+        }
+        let (op, rest) = [
+            ("~>", Op::Pessimistic),
+            (">=", Op::GreaterEqual),
+            ("<=", Op::LessEqual),
+            ("!=", Op::NotEqual),
+            (">", Op::Greater),
+            ("<", Op::Less),
+            ("=", Op::Equal),
+        ]
+        .iter()
+        .find_map(|(prefix, op)| original.strip_prefix(prefix).map(|rest| (*op, rest)))
+        .unwrap_or((Op::Equal, original));
+        let rest = rest.trim_start();
+        if rest.is_empty() || rest.contains(char::is_whitespace) {
+            return Err(ParseError::MalformedConstraint(original.to_string()))
+        }
+        let (version, segments) = parse_with_segments(rest)?;
+        Ok(Constraint { op, version, segments, original: original.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_version, Constraint, Constraints, Op, ParseError};
+
+    /// 用例取自 go-version 的 constraint_test.go
+    #[test]
+    fn test_check() {
+        let cases = [
+            (">= 1.0, < 1.2", "1.1.5", true),
+            ("< 1.0, < 1.2", "1.1.5", false),
+            ("= 1.0", "1.1.5", false),
+            ("= 1.0", "1.0.0", true),
+            ("1.0", "1.0.0", true),
+            ("~> 1.0", "2.0", false),
+            ("~> 1.0", "1.1", true),
+            ("~> 1.0", "1.2.3", true),
+            ("~> 1.0.0", "1.2.3", false),
+            ("~> 1.0.0", "1.0.7", true),
+            ("~> 1.0.0", "1.1.0", false),
+            ("~> 1.0.7", "1.0.4", false),
+            ("~> 1.0.7", "1.0.7", true),
+            ("~> 1.0.7", "1.0.8", true),
+            ("~> 1", "3.4.5", true),
+            ("~> 2.0", "2.1.0-beta", false),
+            ("~> 2.1.0-a", "2.2.0", false),
+            ("~> 2.1.0-a", "2.1.0", false),
+            ("~> 2.1.0-a", "2.1.0-beta", true),
+            ("~> 2.1.0-a", "2.2.0-alpha", false),
+            ("> 2.0", "2.1.0-beta", false),
+            (">= 2.1.0-a", "2.1.0-beta", true),
+            (">= 2.1.0-a", "2.1.1-beta", false),
+            (">= 2.0.0", "2.1.0-beta", false),
+            (">= 2.1.0-a", "2.1.1", true),
+            (">= 2.1.0-a", "2.1.0", true),
+            ("<= 2.1.0-a", "2.0.0", true),
+            ("= 2.1.0-a", "2.1.0-a", true),
+            ("!= 1.5.3", "1.5.3", false),
+            ("~> v1.2", "v1.9.0", true),
+            ("~>1.5.0, != 1.5.3", "1.5.4", true),
+            (">= 1.0.0beta1", "1.0.0-beta2", true),
+        ];
+        for (constraints, version, expected) in cases {
+            let parsed: Constraints = constraints.parse().unwrap_or_else(|e| panic!("{constraints}: {e}"));
+            assert_eq!(parsed.check(&parse_version(version).unwrap()), expected, "{constraints} {version}");
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        let c: Constraint = "  ~>  1.2  ".parse().unwrap();
+        assert_eq!(c.op(), Op::Pessimistic);
+        assert_eq!(c.version().to_string(), "1.2.0");
+        assert_eq!(c.to_string(), "~>  1.2");
+        let constraints: Constraints = ">=1.0,<2.0".parse().unwrap();
+        assert_eq!(constraints.constraints().len(), 2);
+        assert_eq!(constraints.to_string(), ">=1.0, <2.0");
+
+        assert!(matches!("".parse::<Constraints>(), Err(ParseError::EmptyConstraint)));
+        assert!(matches!(">= 1.0,".parse::<Constraints>(), Err(ParseError::EmptyConstraint)));
+        assert!(matches!("~> 1.0 2".parse::<Constraint>(), Err(ParseError::MalformedConstraint(_))));
+        assert!(matches!("=>1.0".parse::<Constraint>(), Err(ParseError::MalformedVersion(_))));
+        // 与 go-version 不同: 不接受 3 个以上的部分, 也不接受先行版本号中的前导零
+        assert!(matches!("1.2.3.4".parse::<Constraint>(), Err(ParseError::TooManySegments(4))));
+        assert!(matches!(parse_version("1.2.3.4.5"), Err(ParseError::TooManySegments(5))));
+        assert!(matches!(parse_version("1.0.0-rc.01"), Err(ParseError::Version(_))));
+        assert!(matches!(parse_version("1..2"), Err(ParseError::MalformedVersion(_))));
+        assert!(matches!(parse_version("1.2.3-"), Err(ParseError::Version(_))));
+    }
+}
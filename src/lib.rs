@@ -4,120 +4,442 @@
 //! ## 快速开始
 //! ```
 //! use version::Version;
-//! let version_old = Version::build_string("1.0.0").unwrap();
-//! let version_new = Version::build_string("2.1.0").unwrap();
+//! let version_old = "1.0.0".parse::<Version>().unwrap();
+//! let version_new = "2.1.0".parse::<Version>().unwrap();
 //!
 //! if version_old.is_newer(&version_new) {
-//!     println!("{} 是新版本", version_new.to_string());
+//!     println!("{} 是新版本", version_new);
 //! } else {
 //!     panic!("版本号判断错误")
 //! }
 //! ```
+//!
+//! ## 迁移说明
+//! 主版本号、副版本号与补丁版本号的类型已由 `u8` 扩大为 `u64`,
+//! 超出 `u64` 范围的数字会返回 [`ParseErrorKind::Overflow`] 而不再是 [`ParseErrorKind::IntError`]。
+//! 仍需要 `u8` 的调用方可以通过访问方法配合 `u8::try_from` 进行转换:
+//! ```
+//! use version::Version;
+//! let v = "1.255.300".parse::<Version>().unwrap();
+//! assert_eq!(u8::try_from(v.minor()).ok(), Some(255));
+//! assert_eq!(u8::try_from(v.patch()).ok(), None);
+//! ```
+//!
+//! ## 排序
+//! [`Version`] 实现了 `Ord` 与 `Hash`, 遵循 SemVer 第 11 条的优先级规则,
+//! 可以直接排序或作为 `BTreeMap`/`HashSet` 的键使用:
+//! ```
+//! use version::Version;
+//! let mut versions: Vec<Version> = ["1.0.0", "1.0.0-rc.1", "1.0.0-alpha", "0.9.0"]
+//!     .iter()
+//!     .map(|s| s.parse::<Version>().unwrap())
+//!     .collect();
+//! versions.sort();
+//! assert_eq!(versions.last().unwrap().to_string(), "1.0.0");
+//! assert_eq!(versions[1].to_string(), "1.0.0-alpha");
+//! ```
+//!
+//! ## 版本需求
+//! [`VersionReq`] 支持 Cargo 风格的版本需求, 例如 `^1.2`、`~1.2.3`、`>=1.0, <2.0` 与 `1.*`:
+//! ```
+//! use version::{Version, VersionReq};
+//! let req: VersionReq = ">=1.0, <2.0".parse().unwrap();
+//! assert!(req.matches(&"1.4.2".parse::<Version>().unwrap()));
+//! ```
+//!
+//! ## 宽松解析
+//! `str::parse` 严格遵循 SemVer, 人工书写或 git 标签中常见的 `v1.2.3`、`1`、`1.2.3_rc1` 等写法可以交给
+//! [`Parser::lenient`] 解析, 它会记录做出的每一项修正 [`Coercion`], 方便调用方给出警告:
+//! ```
+//! use version::{Coercion, Parser};
+//! let parsed = Parser::lenient().parse("v1").unwrap();
+//! assert_eq!(parsed.version().to_string(), "1.0.0");
+//! assert_eq!(parsed.coercions(), [Coercion::Prefix('v'), Coercion::MissingMinor]);
+//! ```
+//!
+//! 宽松模式还会把全角字符转换为半角, 并按照 [`StageWords`] 把 `测试版`、`正式版` 等发布阶段词语替换为标准的先行版本号:
+//! ```
+//! use version::Parser;
+//! let v = |s: &str| Parser::lenient().parse(s).unwrap().into_version();
+//! assert_eq!(v("１．２．３－测试版").to_string(), "1.2.3-beta");
+//! assert!(v("1.2.0-候选版") < v("1.2.0-正式版"));
+//! ```
+//!
+//! ## 其他生态的版本号
+//! - [`npm`]: node-semver 风格的版本范围, 例如 `1.2.3 - 2.3.4`、`^0.0.1 || 1.x`
+//! - [`pep440`]: Python 的 PEP 440 版本号, 例如 `1!2.0.post1.dev3`、`2.0a1+local.7`
+//! - [`debian`]: Debian 软件包版本号, 例如 `1:2.3~rc1-0ubuntu2`, 比较规则与 dpkg 一致
+//! - [`rpm`]: RPM 软件包版本号, 例如 `1:2.0~rc1^git3-1.fc40`, 比较规则与 `rpmvercmp` 一致
+//! - [`maven`]: Maven 的版本号, 例如 `1.0-alpha-1`、`1.0-SNAPSHOT`, 比较规则与 `ComparableVersion` 一致, 以及 `[1.0,2.0)` 这样的版本范围
+//! - [`calver`]: 日历版本号, 例如 `2026.10.3`、`26.04`, 格式由 `YYYY.0M.MICRO` 这样的格式串描述
+//! - [`numeric`]: 任意长度的纯数字版本号, 例如 `130.0.6723.91`, 可以选择末尾的零是否影响比较
+//! - [`go`]: Go 模块版本号, 支持 `+incompatible`、伪版本号与 `/vN` 路径后缀检查
+//! - [`nuget`]: NuGet 版本号, 例如 `1.0.0.1`、`1.0.0-beta.1`, 以及 `[1.0, 2.0)`、`1.*` 这样的版本范围
+//! - [`rubygems`]: RubyGems 版本号, 例如 `2.7.0.rc1`、`1.0.a`, 以及 `~> 1.4, != 1.4.2` 这样的版本需求
+//! - [`composer`]: Composer (PHP) 版本号, 例如 `1.0-p1`、`dev-main`, 以及 `^1.2 || ~2.0.3` 这样的约束与稳定性
+//! - [`hashicorp`]: HashiCorp go-version 风格的约束, 例如 Terraform 的 `~> 1.5.0, != 1.5.3`
+//! - [`apk`]: Alpine 软件包版本号, 例如 `1.2.3_rc1-r4`, 比较规则与 apk-tools 一致
+//! - [`pacman`]: Arch Linux 软件包版本号, 例如 `1:2.0rc1-3`, 比较规则与 `vercmp` 一致
+//! - [`portage`]: Gentoo 软件包版本号, 例如 `1.2.3b_alpha4_p2-r1`, 比较规则与 PMS 一致
+//!
+//! [`scheme`] 模块以统一的 [`scheme::VersionScheme`] 接口封装 Debian、RPM 与上述发行版的版本号以及 [`Version`],
+//! 可以根据软件包生态的名称选择比较方式
+//!
+//! 来源未知的版本号可以使用 [`any`] 模块自动识别方案, 得到带有置信度的 [`any::AnyVersion`]
+//!
+//! ## 可选特性
+//! - `serde`: 为 [`Version`] 与 [`ParseError`] 实现序列化, [`Version`] 默认序列化为版本号字符串,
+//!   结构化形式见 `structured` 模块
 
-use std::num::ParseIntError;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::num::{IntErrorKind, ParseIntError};
+use std::ops::Range;
+use std::str::FromStr;
 use thiserror::Error;
 
+pub mod any;
+pub mod apk;
+pub mod calver;
+pub mod composer;
+pub mod debian;
+pub mod go;
+pub mod hashicorp;
+pub mod maven;
+pub mod npm;
+pub mod nuget;
+pub mod numeric;
+pub mod pacman;
+pub mod pep440;
+pub mod portage;
+pub mod rpm;
+pub mod rubygems;
+pub mod scheme;
+mod parser;
+mod req;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+pub use parser::{Coercion, ParseOptions, Parsed, Parser, Stage, StageWords};
+pub use req::{Comparator, Op, ReqParseError, VersionReq};
+#[cfg(feature = "serde")]
+pub use serde_impl::structured;
+
 ///
 /// 表示一个版本号的结构体
 ///
-/// 包含了 major(主版本号) minor(次版本号) patch(补丁版本号) 和 可选的suffix(版本后缀)
+/// 遵循 [SemVer 2.0.0](https://semver.org/lang/zh-CN/) 规范,
+/// 包含了 major(主版本号) minor(次版本号) patch(补丁版本号)、
+/// 可选的 pre(先行版本号) 和 可选的 build(版本编译信息)
 ///
 /// ```
 /// use version::Version;
 ///
 /// // 基于现有字符串
-/// let version_s = Version::build_string("1.0.0").unwrap();
-/// println!("{}", version_s.to_string())
+/// let version_s = "1.0.0-rc.1+build.5".parse::<Version>().unwrap();
+/// println!("{}", version_s)
 /// ```
+///
+/// # 比较
+/// 版本号之间按照 SemVer 的优先级进行比较, 版本编译信息不参与比较,
+/// 因此 `1.0.0+a` 与 `1.0.0+b` 相等, 且具有相同的哈希值
+#[derive(Debug, Clone)]
 pub struct Version {
-    major: u8,
-    minor: u8,
-    patch: u8,
-    suffix: String,
+    major: u64,
+    minor: u64,
+    patch: u64,
+    pre: Vec<Identifier>,
+    build: Vec<String>,
 }
 
 ///
-/// 表示在解析操作期间可能发生的错误。
+/// 先行版本号中的单个标识符
+///
+/// 按照 SemVer 规范, 纯数字的标识符与包含字母或连接号的标识符区分存储,
+/// 纯数字标识符之间按数值比较, 其余标识符按 ASCII 字典序比较,
+/// 纯数字标识符的优先级总是低于非数字标识符
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Identifier {
+    /// 纯数字标识符, 例如 `rc.1` 中的 `1`
+    Numeric(u64),
+    /// 包含字母或连接号的标识符, 例如 `rc.1` 中的 `rc`
+    AlphaNumeric(String),
+}
+
 ///
-/// 这个枚举包含两种变体:
+/// 版本号中的组成部分, 用于在错误信息中指明出错的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Component {
+    /// 主版本号
+    Major,
+    /// 副版本号
+    Minor,
+    /// 补丁版本号
+    Patch,
+    /// 先行版本号
+    Pre,
+    /// 版本编译信息
+    Build,
+}
+
+impl fmt::Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Component::Major => "主版本号",
+            Component::Minor => "副版本号",
+            Component::Patch => "补丁版本号",
+            Component::Pre => "先行版本号",
+            Component::Build => "编译信息",
+        })
+    }
+}
+
+///
+/// 解析版本号失败时的错误
+///
+/// 除了错误的种类 [`ParseErrorKind`] 之外, 还记录了解析的输入、出错片段的字节范围、
+/// 出错的组成部分以及修正建议。以 `{:#}` 格式化或调用 [`ParseError::render`]
+/// 可以得到类似 rustc 诊断信息的输出, 在输入下方用 `^` 标出出错的位置:
+///
+/// ```
+/// use version::{Component, ParseErrorKind, Version};
+///
+/// let err = "1.x.3".parse::<Version>().unwrap_err();
+/// assert!(matches!(err.kind(), ParseErrorKind::IntError(_)));
+/// assert_eq!((err.input(), err.span(), err.component()), ("1.x.3", 2..3, Some(Component::Minor)));
+/// assert_eq!(err.to_string(), "副版本号 \"x\" 不是合法的数字");
+/// assert_eq!(err.render(), "\
+/// 错误: 副版本号 \"x\" 不是合法的数字
+///   |
+///   | 1.x.3
+///   |   ^ 副版本号
+///   |
+///   = 建议: 通配符 \"x\" 只能用于版本需求, 请改为具体的数字");
+/// ```
+///
+/// 通过 [`Parser`] 宽松解析时, 输入与位置对应的是调用方传入的原始字符串
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    kind: ParseErrorKind,
+    input: String,
+    span: Range<usize>,
+    component: Option<Component>,
+    suggestion: Option<String>,
+}
+
+///
+/// 解析错误的种类
+///
+/// 这个枚举包含以下变体:
 /// - `IntError`: 在解析整数时发生错误。它包装了标准的`ParseIntError`，以提供更多上下文特定的错误信息。
 /// - `LengthError`: 当拆分操作的长度出现问题时返回的错误，表示输入或输出不符合预期的长度要求。
+/// - `EmptyIdentifier`: 先行版本号或编译信息中存在空的标识符，例如 `1.0.0-rc..1`。
+/// - `InvalidCharacter`: 标识符中出现了 `[0-9A-Za-z-]` 以外的字符。
+/// - `LeadingZero`: 数字标识符含有前导零，例如 `01` 或 `1.0.0-rc.01`。
+/// - `Overflow`: 数字超出了所能表示的范围，会指明溢出的组成部分与上限。
 ///
-#[derive(Error, Debug)]
-pub enum ParseError {
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
 
     #[error("解析数字失败: {0}")]
     IntError(#[from] ParseIntError),
 
     #[error("分割长度错误")]
-    LengthError
+    LengthError,
+
+    #[error("存在空的标识符")]
+    EmptyIdentifier,
+
+    #[error("标识符中存在非法字符 '{0}'")]
+    InvalidCharacter(char),
+
+    #[error("数字标识符 \"{0}\" 不能含有前导零")]
+    LeadingZero(String),
+
+    #[error("{component}溢出: 不能超过 {limit}")]
+    Overflow {
+        /// 发生溢出的组成部分
+        component: Component,
+        /// 该组成部分允许的最大值
+        limit: u64,
+    },
 }
 
-impl Version {
+impl ParseError {
 
+    fn new(kind: ParseErrorKind, input: &str, span: Range<usize>, component: Component, suggestion: Option<String>) -> ParseError {
+        ParseError { kind, input: input.to_string(), span, component: Some(component), suggestion }
+    }
 
-    /// 通过字符串构建 Version 结构体对象
-    ///
-    /// # 参数
-    /// `version` -
-    /// 字符串必须遵循此结构
-    /// ```"XX.XX.XX-YY"```
-    /// 或者
-    /// ```"XX.XX-YY"```
-    /// 其中 YY 部分可缺省，此时的形式为
-    /// ```"XX.XX.XX"```
-    ///
-    /// # 返回值
-    /// Ok(Version) - 版本号对象
-    /// Err(ParseError) - 解析错误
-    ///
-    /// # 示例
-    /// ```
-    /// use version::Version;
-    ///
-    /// let v = Version::build_string("1.0.0").unwrap();                // 主.副.补丁
-    /// let v_suffix = Version::build_string("2.0.0-beta").unwrap();    // 主.副.补丁-后缀
-    /// let v_major_minor = Version::build_string("1.2").unwrap();      // 主.副
-    /// ```
-    pub fn build_string(version: &str) -> Result<Version, ParseError> {
-        // 分割版本号和后缀
-        let version_suffix: Vec<&str> = version.split("-").collect();
-        // 分割版本号
-        let major_minor_patch: Vec<&str> = version_suffix[0].split(".").collect();
-        let suffix : String;
+    /// 错误的种类
+    pub fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
 
-        // 检查分割长度是否满足要求
-        if major_minor_patch.len() < 2 || major_minor_patch.len() > 3 {
-            // 如果不满足则报错
-            return Err(ParseError::LengthError)
+    /// 解析的输入
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// 出错片段在输入中的字节范围, 缺少内容时(例如 `1.0.0-`)为空范围
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// 出错片段的内容
+    pub fn token(&self) -> &str {
+        &self.input[self.span()]
+    }
+
+    /// 出错的组成部分, 整体格式错误时为 `None`
+    pub fn component(&self) -> Option<Component> {
+        self.component
+    }
+
+    /// 修正建议
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
+    /// 渲染带有 `^` 标记的诊断信息, 与 `format!("{err:#}")` 相同
+    pub fn render(&self) -> String {
+        format!("{self:#}")
+    }
+
+    /// 将相对于输入中某个片段的错误转换为相对于整个输入的错误, `offset` 为片段的起始位置
+    fn at(mut self, input: &str, offset: usize) -> ParseError {
+        self.input = input.to_string();
+        self.span = self.span.start + offset..self.span.end + offset;
+        self
+    }
+
+    /// 将错误的输入与位置替换为修正之前的输入及其中对应的范围
+    fn relocate(mut self, input: &str, span: Range<usize>) -> ParseError {
+        self.input = input.to_string();
+        self.span = span;
+        self
+    }
+
+    /// 写出错误信息, 数字解析失败时指明组成部分与出错的片段
+    fn write_message(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.kind, self.component) {
+            (ParseErrorKind::IntError(_), Some(component)) if self.span.is_empty() => write!(f, "缺少{component}"),
+            (ParseErrorKind::IntError(_), Some(component)) => write!(f, "{component} \"{}\" 不是合法的数字", self.token()),
+            (kind, _) => write!(f, "{kind}"),
         }
+    }
+}
 
-        // 检测版本号是否存在后缀
-        if version_suffix.len() == 1 {
-            suffix = "".to_string();
-        } else {
-            suffix = version_suffix[1].to_string(); // 后缀类型
+impl fmt::Display for ParseError {
+    /// 默认只输出错误信息, `{:#}` 输出带有 `^` 标记与修正建议的诊断信息
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !f.alternate() {
+            return self.write_message(f)
+        }
+        f.write_str("错误: ")?;
+        self.write_message(f)?;
+        // 宽字符占两列, 标记按照显示宽度对齐; 空范围时标记紧跟在前一个字符之后
+        let padding = display_width(&self.input[..self.span.start]);
+        let carets = display_width(self.token()).max(1);
+        write!(f, "\n  |\n  | {}\n  | {:padding$}{}", self.input, "", "^".repeat(carets))?;
+        if let Some(component) = self.component {
+            write!(f, " {component}")?;
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, "\n  |\n  = 建议: {suggestion}")?;
         }
+        Ok(())
+    }
+}
 
-        // 解析版本号为整数
-        // 错误将传递上层
-        let major = major_minor_patch[0].parse::<u8>()?;
-        let minor = major_minor_patch[1].parse::<u8>()?;
-        // 对缺失补丁版本号特殊处理
-        let patch : u8;
-        if major_minor_patch.len() > 2 {
-            patch = major_minor_patch[2].parse::<u8>()?;
-        } else {
-            patch = 0;
+impl std::error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ParseErrorKind::IntError(e) => Some(e),
+            _ => None,
         }
+    }
+}
 
-        // 返回Version对象
-        Ok(Version {
+impl From<ParseErrorKind> for ParseError {
+    /// 不带输入与位置的错误
+    fn from(kind: ParseErrorKind) -> ParseError {
+        let component = match kind {
+            ParseErrorKind::Overflow { component, .. } => Some(component),
+            _ => None,
+        };
+        ParseError { kind, input: String::new(), span: 0..0, component, suggestion: None }
+    }
+}
+
+/// 字符串在终端中的显示宽度, 中日韩文字与全角字符占两列
+fn display_width(s: &str) -> usize {
+    s.chars()
+        .map(|c| match c {
+            '\u{1100}'..='\u{115F}' | '\u{2E80}'..='\u{A4CF}' | '\u{AC00}'..='\u{D7A3}' | '\u{F900}'..='\u{FAFF}'
+            | '\u{FE30}'..='\u{FE4F}' | '\u{FF00}'..='\u{FF60}' | '\u{FFE0}'..='\u{FFE6}' | '\u{20000}'..='\u{3FFFD}' => 2,
+            _ => 1,
+        })
+        .sum()
+}
+
+impl Version {
+
+    /// 通过主版本号、副版本号与补丁版本号构建不含先行版本号与编译信息的 Version 对象
+    ///
+    /// # 示例
+    /// ```
+    /// use version::Version;
+    ///
+    /// let v = Version::new(2026, 10, 18);
+    /// assert_eq!(v.to_string(), "2026.10.18");
+    /// ```
+    pub fn new(major: u64, minor: u64, patch: u64) -> Version {
+        Version {
             major,
             minor,
             patch,
-            suffix,
-        })
+            pre: Vec::new(),
+            build: Vec::new(),
+        }
+    }
+
+    /// 主版本号
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// 副版本号
+    pub fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// 补丁版本号
+    pub fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// 先行版本号的标识符列表, 不存在时为空
+    pub fn pre(&self) -> &[Identifier] {
+        &self.pre
+    }
+
+    /// 版本编译信息的标识符列表, 不存在时为空
+    pub fn build(&self) -> &[String] {
+        &self.build
+    }
+
+    /// 通过字符串构建 Version 结构体对象
+    ///
+    /// 与 `version.parse::<Version>()` 等价, 语法说明见 [`FromStr`] 的实现
+    ///
+    /// # 返回值
+    /// Ok(Version) - 版本号对象
+    /// Err(ParseError) - 解析错误
+    #[deprecated(note = "请使用 `str::parse::<Version>()` 或 `Version::try_from(&str)`")]
+    pub fn build_string(version: &str) -> Result<Version, ParseError> {
+        version.parse()
     }
 
     /// 比较传入的版本号是否为最新版本
@@ -130,94 +452,521 @@ impl Version {
     /// `false` - 其他情况返回
     ///
     /// # 注意
-    /// 判断是否为新版本逻辑如下
+    /// 判断是否为新版本逻辑与 `Ord` 的实现一致, 即 `self < other`
     /// 1. 判断主版本号、副版本号、补丁版本号
-    /// 2. 判断两者之一是否有后缀，有后缀的版本号默认被认为是新版本
+    /// 2. 带有先行版本号的版本低于对应的正式版本, 例如 `1.0.0-beta < 1.0.0`
+    /// 3. 先行版本号逐个标识符比较, 版本编译信息不参与比较
     ///
     /// # 示例
     /// ```
     /// use version::Version;
     ///
-    /// let v_old = Version::build_string("1.0.0").unwrap();
-    /// let v_new = Version::build_string("2.0.0").unwrap();
+    /// let v_old = "1.0.0".parse::<Version>().unwrap();
+    /// let v_new = "2.0.0".parse::<Version>().unwrap();
     ///
     /// assert_eq!(v_old.is_newer(&v_new), true)
     /// ```
     pub fn is_newer(&self, other: &Version) -> bool {
-        self.major < other.major // 判断大版本
-            || (self.major == other.major && self.minor < other.minor // 判断小版本
-            || (self.major == other.major && self.minor == other.minor && self.patch < other.patch // 判断补丁版本
-            || (self.major == other.major && self.minor == other.minor && self.patch == other.patch &&
-            (self.suffix.trim().is_empty() && !other.suffix.trim().is_empty()) // 判断后缀
-        )))
+        self < other
     }
 
-    /// 将版本号转化为字符串。
-    ///
-    /// # 返回值
-    /// 以`[major].[minor].[patch]-[suffix]`或`[major].[minor].[patch]`形式输出
-    ///
-    ///
-    pub fn to_string(&self) -> String {
-        if self.suffix.trim().is_empty() {
-            format!("{}.{}.{}", self.major, self.minor, self.patch)
+    /// 以`[major].[minor].[patch]-[pre]+[build]`形式写出完整的版本号, 其中 `-[pre]` 与 `+[build]` 在为空时省略
+    fn write_canonical<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        write!(w, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        for (i, identifier) in self.pre.iter().enumerate() {
+            w.write_char(if i == 0 { '-' } else { '.' })?;
+            write!(w, "{identifier}")?;
+        }
+        for (i, identifier) in self.build.iter().enumerate() {
+            w.write_char(if i == 0 { '+' } else { '.' })?;
+            w.write_str(identifier)?;
+        }
+        Ok(())
+    }
+}
+
+/// 将版本号转化为字符串。
+///
+/// 以`[major].[minor].[patch]-[pre]+[build]`形式输出, 其中 `-[pre]` 与 `+[build]` 在为空时省略
+///
+/// `{}` 与格式化字符串一样支持填充、宽度与精度, 例如 `{:>12}` 或 `{:.5}`;
+/// `{:#}` 则忽略这些参数, 总是输出完整的版本号
+///
+/// # 示例
+/// ```
+/// use version::Version;
+///
+/// let v: Version = "1.0.0-rc.1+build.5".parse().unwrap();
+/// assert_eq!(v.to_string(), "1.0.0-rc.1+build.5");
+/// assert_eq!(format!("[{:>8}]", "1.2.3".parse::<Version>().unwrap()), "[   1.2.3]");
+/// assert_eq!(format!("{:.5}", v), "1.0.0");
+/// assert_eq!(format!("{:#.5}", v), "1.0.0-rc.1+build.5");
+/// ```
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            self.write_canonical(f)
         } else {
-            format!("{}.{}.{}-{}", self.major, self.minor, self.patch, self.suffix)
+            let mut s = String::new();
+            self.write_canonical(&mut s)?;
+            f.pad(&s)
         }
     }
 }
 
+/// 通过字符串构建 Version 结构体对象
+///
+/// # 参数
+/// `version` -
+/// 字符串必须遵循此结构
+/// ```"XX.XX.XX-YY+ZZ"```
+/// 或者
+/// ```"XX.XX-YY+ZZ"```
+/// 其中 YY(先行版本号) 与 ZZ(版本编译信息) 部分均可缺省，此时的形式为
+/// ```"XX.XX.XX"```
+///
+/// YY 与 ZZ 均为以 `.` 分隔的标识符, 标识符只能由 `[0-9A-Za-z-]` 组成且不能为空,
+/// 数字部分(XX 与 YY 中的纯数字标识符)不能含有前导零
+///
+/// # 返回值
+/// Ok(Version) - 版本号对象
+/// Err(ParseError) - 解析错误
+///
+/// # 示例
+/// ```
+/// use version::Version;
+///
+/// let v: Version = "1.0.0".parse().unwrap();                       // 主.副.补丁
+/// let v_suffix: Version = "2.0.0-beta".parse().unwrap();           // 主.副.补丁-先行版本号
+/// let v_major_minor = Version::try_from("1.2").unwrap();           // 主.副
+/// let v_build: Version = "1.0.0-rc-1+build.5".parse().unwrap();    // 主.副.补丁-先行版本号+编译信息
+/// assert_eq!(v_build.to_string(), "1.0.0-rc-1+build.5");
+/// ```
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(version: &str) -> Result<Version, ParseError> {
+        // 分割编译信息, 第一个 '+' 之后的内容均为编译信息
+        let (version_pre, build) = match version.split_once('+') {
+            Some((version_pre, build)) => {
+                let build = parse_build(build).map_err(|e| e.at(version, version_pre.len() + 1))?;
+                (version_pre, build)
+            }
+            None => (version, Vec::new()),
+        };
+        // 分割版本号和先行版本号, 第一个 '-' 之后的内容均为先行版本号
+        let (core, pre) = match version_pre.split_once('-') {
+            Some((core, pre)) => (core, parse_pre(pre).map_err(|e| e.at(version, core.len() + 1))?),
+            None => (version_pre, Vec::new()),
+        };
+        // 分割版本号
+        let major_minor_patch: Vec<&str> = core.split('.').collect();
+
+        // 检查分割长度是否满足要求
+        if major_minor_patch.len() < 2 || major_minor_patch.len() > 3 {
+            // 如果不满足则报错
+            return Err(length_error(version, core, &major_minor_patch))
+        }
+
+        // 解析版本号为整数, 错误的位置相对于整个输入
+        // 错误将传递上层
+        let mut numbers = [0; 3];
+        let mut offset = 0;
+        for (i, (part, component)) in major_minor_patch.iter().zip([Component::Major, Component::Minor, Component::Patch]).enumerate() {
+            numbers[i] = parse_number(part, component).map_err(|e| e.at(version, offset))?;
+            offset += part.len() + 1;
+        }
+        // 缺失的补丁版本号保持为 0
+        let [major, minor, patch] = numbers;
+
+        // 返回Version对象
+        Ok(Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        })
+    }
+
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(version: &str) -> Result<Version, ParseError> {
+        version.parse()
+    }
+}
+
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.major.cmp(&other.major) // 判断大版本
+            .then(self.minor.cmp(&other.minor)) // 判断小版本
+            .then(self.patch.cmp(&other.patch)) // 判断补丁版本
+            .then_with(|| cmp_pre(&self.pre, &other.pre)) // 判断先行版本号
+    }
+}
+
+/// 按照 SemVer 规则比较两个先行版本号, 空的先行版本号(即正式版本)优先级最高
+fn cmp_pre(a: &[Identifier], b: &[Identifier]) -> Ordering {
+    match (a.is_empty(), b.is_empty()) {
+        // 正式版本高于任何先行版本
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        // 逐个比较标识符, 前缀相同时标识符较多者优先级更高
+        (false, false) => a.cmp(b),
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致, 版本编译信息不参与哈希
+        self.major.hash(state);
+        self.minor.hash(state);
+        self.patch.hash(state);
+        self.pre.hash(state);
+    }
+}
+
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{n}"),
+            Identifier::AlphaNumeric(s) => f.write_str(s),
+        }
+    }
+}
+
+/// 版本号核心部分的个数错误, 标出缺少或多余的部分
+fn length_error(version: &str, core: &str, parts: &[&str]) -> ParseError {
+    let (span, suggestion) = if parts.len() > 3 {
+        // 从第三个部分之后的 '.' 开始均为多余的部分
+        let start = parts[..3].iter().map(|part| part.len() + 1).sum::<usize>() - 1;
+        let kept = &core[..start];
+        let extra = &core[start + 1..];
+        (start..core.len(), format!("版本号最多包含三个部分, 可以删除多余的部分, 或将其作为编译信息, 例如 \"{kept}+{extra}\""))
+    } else if !core.is_empty() && core.bytes().all(|b| b.is_ascii_digit()) {
+        (0..core.len(), format!("补全副版本号, 例如 \"{core}.0\""))
+    } else {
+        (0..core.len(), "版本号应为 \"主版本号.副版本号.补丁版本号\" 的形式, 例如 \"1.0.0\"".to_string())
+    };
+    ParseError { kind: ParseErrorKind::LengthError, input: version.to_string(), span, component: None, suggestion: Some(suggestion) }
+}
+
+/// 解析数字标识符, 不允许前导零, 溢出时返回指明组成部分的错误; 错误的位置相对于 `s`
+fn parse_number(s: &str, component: Component) -> Result<u64, ParseError> {
+    let error = |kind, suggestion| ParseError::new(kind, s, 0..s.len(), component, suggestion);
+    if s.len() > 1 && s.starts_with('0') && s.bytes().all(|b| b.is_ascii_digit()) {
+        let trimmed = s.trim_start_matches('0');
+        let trimmed = if trimmed.is_empty() { "0" } else { trimmed };
+        return Err(error(ParseErrorKind::LeadingZero(s.to_string()), Some(format!("去掉前导零, 改为 \"{trimmed}\""))))
+    }
+    s.parse::<u64>().map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow => error(ParseErrorKind::Overflow { component, limit: u64::MAX }, None),
+        _ => error(ParseErrorKind::IntError(e), Some(suggest_number(s, component))),
+    })
+}
+
+/// 根据无法解析为数字的片段给出修正建议
+fn suggest_number(s: &str, component: Component) -> String {
+    let digits = s.len() - s.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    match s {
+        "" => format!("补全{component}, 或删除多余的 '.'"),
+        "x" | "X" | "*" => format!("通配符 \"{s}\" 只能用于版本需求, 请改为具体的数字"),
+        _ if s.trim() != s => "去掉空白".to_string(),
+        _ if component == Component::Major && s.starts_with(['v', 'V']) && s.len() > 1 && s[1..].bytes().all(|b| b.is_ascii_digit()) => {
+            format!("去掉前缀 '{}', 或使用 Parser::lenient 解析", &s[..1])
+        }
+        _ if digits > 0 => format!("先行版本号需要以 '-' 分隔, 例如 \"{}-{}\"", &s[..digits], &s[digits..]),
+        _ => format!("{component}只能包含数字"),
+    }
+}
+
+/// 检查以 `.` 分隔的标识符是否非空且只包含 `[0-9A-Za-z-]`, 返回每个标识符及其起始位置
+fn split_identifiers(s: &str, component: Component) -> Result<Vec<(usize, &str)>, ParseError> {
+    let mut identifiers = Vec::new();
+    let mut offset = 0;
+    for part in s.split('.') {
+        if part.is_empty() {
+            let suggestion = if s.is_empty() {
+                let separator = if component == Component::Build { '+' } else { '-' };
+                format!("删除末尾的 '{separator}', 或在其后补全{component}")
+            } else if offset == s.len() {
+                "删除末尾的 '.'".to_string()
+            } else {
+                "删除多余的 '.'".to_string()
+            };
+            return Err(ParseError::new(ParseErrorKind::EmptyIdentifier, s, offset..offset, component, Some(suggestion)))
+        }
+        if let Some((i, c)) = part.char_indices().find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '-') {
+            let suggestion = match c {
+                '_' => "将 '_' 改为 '-'".to_string(),
+                '+' => "编译信息只能以一个 '+' 开头, 可以将之后的 '+' 改为 '.'".to_string(),
+                _ => format!("标识符只能包含 ASCII 字母、数字与 '-', 请删除 '{c}'"),
+            };
+            let span = offset + i..offset + i + c.len_utf8();
+            return Err(ParseError::new(ParseErrorKind::InvalidCharacter(c), s, span, component, Some(suggestion)))
+        }
+        identifiers.push((offset, part));
+        offset += part.len() + 1;
+    }
+    Ok(identifiers)
+}
+
+/// 解析以 `.` 分隔的先行版本号; 错误的位置相对于 `pre`
+fn parse_pre(pre: &str) -> Result<Vec<Identifier>, ParseError> {
+    split_identifiers(pre, Component::Pre)?
+        .into_iter()
+        .map(|(offset, part)| {
+            if part.bytes().all(|b| b.is_ascii_digit()) {
+                // 纯数字标识符不能含有前导零
+                let n = parse_number(part, Component::Pre).map_err(|e| e.at(pre, offset))?;
+                Ok(Identifier::Numeric(n))
+            } else {
+                Ok(Identifier::AlphaNumeric(part.to_string()))
+            }
+        })
+        .collect()
+}
+
+/// 解析以 `.` 分隔的版本编译信息, 编译信息中的数字允许前导零; 错误的位置相对于 `build`
+fn parse_build(build: &str) -> Result<Vec<String>, ParseError> {
+    Ok(split_identifiers(build, Component::Build)?
+        .into_iter()
+        .map(|(_, part)| part.to_string())
+        .collect())
+}
+
+#[cfg(test)]
 mod tests {
-    use crate::{Version};
+    use crate::{Component, ParseErrorKind, Version};
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    fn kind(s: &str) -> ParseErrorKind {
+        s.parse::<Version>().unwrap_err().kind().clone()
+    }
 
     /// 测试版本比较
     #[test]
     fn test_newer() {
-        let v_old = Version::build_string("1.0.0").unwrap();
-        let v_new = Version::build_string("1.1.0").unwrap();
+        let v_old = "1.0.0".parse::<Version>().unwrap();
+        let v_new = "1.1.0".parse::<Version>().unwrap();
 
         // 断言比较
-        assert_eq!(v_old.is_newer(&v_new), true);
+        assert!(v_old.is_newer(&v_new));
 
-        let v_new = Version::build_string("2.0.0").unwrap();
+        let v_new = "2.0.0".parse::<Version>().unwrap();
 
         // 断言比较
-        assert_eq!(v_old.is_newer(&v_new), true);
+        assert!(v_old.is_newer(&v_new));
     }
 
     /// 测试版本对象创建
     #[test]
     fn test_build() {
-        let v_not_suffix = Version::build_string("1.0.0").unwrap();
-        let v_has_suffix = Version::build_string("1.0.0-beta").unwrap();
-        let v_less_patch = Version::build_string("1.0-beta").unwrap();
+        let v_not_suffix = "1.0.0".parse::<Version>().unwrap();
+        let v_has_suffix = "1.0.0-beta".parse::<Version>().unwrap();
+        let v_less_patch = "1.0-beta".parse::<Version>().unwrap();
 
         println!("v_not_suffix: {}\nv_has_suffix: {}\nv_less_patch: {}\n",
-                 v_not_suffix.to_string(), v_has_suffix.to_string(), v_less_patch.to_string()
+                 v_not_suffix, v_has_suffix, v_less_patch
         )
     }
 
-    /// 测试版本无后缀优先于后缀
+    /// 测试先行版本低于对应的正式版本
     #[test]
     fn test_suffix() {
-        let v_has_suffix = Version::build_string("1.0.0").unwrap();
-        let v_has_not_suffix = Version::build_string("1.0.0-beta").unwrap();
+        let v_has_not_suffix = "1.0.0".parse::<Version>().unwrap();
+        let v_has_suffix = "1.0.0-beta".parse::<Version>().unwrap();
 
         // 断言比较
-        assert_eq!(v_has_suffix.is_newer(&v_has_not_suffix), true)
+        assert!(v_has_suffix.is_newer(&v_has_not_suffix));
+        assert!(!v_has_not_suffix.is_newer(&v_has_suffix));
+    }
+
+    /// 测试 SemVer 规范第 11 条中的优先级示例
+    #[test]
+    fn test_precedence() {
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "2.0.0",
+            "2.1.0",
+            "2.1.1",
+        ];
+        let versions: Vec<Version> = ordered.iter().map(|s| s.parse::<Version>().unwrap()).collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+
+        let mut shuffled = versions.clone();
+        shuffled.reverse();
+        shuffled.sort();
+        assert_eq!(shuffled, versions);
+        assert_eq!(versions.iter().max().unwrap().to_string(), "2.1.1");
+    }
+
+    /// 测试版本编译信息不参与比较与哈希
+    #[test]
+    fn test_build_metadata_ignored() {
+        let a = "1.0.0+build.1".parse::<Version>().unwrap();
+        let b = "1.0.0+build.2".parse::<Version>().unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+
+        let set: HashSet<Version> = [a, b].into_iter().collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    /// 测试标准转换 trait 与格式化参数
+    #[test]
+    fn test_conversion_traits() {
+        fn render<T: ToString>(value: T) -> String {
+            value.to_string()
+        }
+
+        let v = Version::try_from("1.2-rc.1+build.5").unwrap();
+        assert_eq!(v, "1.2.0-rc.1".parse().unwrap());
+        assert_eq!(render(&v), "1.2.0-rc.1+build.5");
+        assert_eq!(format!("{v}"), "1.2.0-rc.1+build.5");
+        assert_eq!(format!("{v:<12}|"), "1.2.0-rc.1+build.5|");
+        assert_eq!(format!("{:<8}|", Version::new(1, 2, 3)), "1.2.3   |");
+        assert_eq!(format!("{v:.5}"), "1.2.0");
+        assert_eq!(format!("{v:#.5}"), "1.2.0-rc.1+build.5");
+        assert!(Version::try_from("1.x").is_err());
+    }
+
+    /// 测试先行版本号与编译信息的往返转换
+    #[test]
+    fn test_round_trip() {
+        for s in [
+            "1.0.0-rc-1",
+            "1.0.0+build.5",
+            "1.0.0-alpha.1+001",
+            "1.0.0-x.7.z.92",
+            "1.0.0-alpha+20130313144700",
+            "1.0.0-0.3.7",
+            "1.0.0-x-y-z.--",
+            "1.0.0+exp.sha.5114f85",
+        ] {
+            assert_eq!(s.parse::<Version>().unwrap().to_string(), s);
+        }
+    }
+
+    /// 测试不符合 SemVer 语法的版本号
+    #[test]
+    fn test_invalid_grammar() {
+        assert!(matches!(kind("01.0.0"), ParseErrorKind::LeadingZero(_)));
+        assert!(matches!(kind("1.0.0-rc.01"), ParseErrorKind::LeadingZero(_)));
+        assert!(matches!(kind("1.0.0-rc..1"), ParseErrorKind::EmptyIdentifier));
+        assert!(matches!(kind("1.0.0-"), ParseErrorKind::EmptyIdentifier));
+        assert!(matches!(kind("1.0.0+"), ParseErrorKind::EmptyIdentifier));
+        assert!(matches!(kind("1.0.0-beta_1"), ParseErrorKind::InvalidCharacter('_')));
+        assert!(matches!(kind("1.0.0+build+1"), ParseErrorKind::InvalidCharacter('+')));
+        // 编译信息允许前导零
+        assert!("1.0.0+001".parse::<Version>().is_ok());
+    }
+
+    /// 测试超出 u8 范围的版本号与溢出错误
+    #[test]
+    fn test_wide_component() {
+        let v = "2026.10.18".parse::<Version>().unwrap();
+        assert_eq!((v.major(), v.minor(), v.patch()), (2026, 10, 18));
+        assert_eq!("1.255.300".parse::<Version>().unwrap().to_string(), "1.255.300");
+        assert_eq!("1.0.18446744073709551615".parse::<Version>().unwrap().patch(), u64::MAX);
+
+        match kind("1.18446744073709551616.0") {
+            ParseErrorKind::Overflow { component, limit } => {
+                assert_eq!(component, Component::Minor);
+                assert_eq!(limit, u64::MAX);
+            }
+            other => panic!("意外的结果: {:?}", other),
+        }
+        assert!(matches!(
+            kind("1.0.0-rc.99999999999999999999"),
+            ParseErrorKind::Overflow { component: Component::Pre, .. }
+        ));
+    }
+
+    /// 测试错误的位置、组成部分与修正建议
+    #[test]
+    fn test_error_span() {
+        let cases = [
+            ("1.x.3", 2..3, Some(Component::Minor), "通配符 \"x\" 只能用于版本需求, 请改为具体的数字"),
+            ("1.2.3beta", 4..9, Some(Component::Patch), "先行版本号需要以 '-' 分隔, 例如 \"3-beta\""),
+            ("1.02.3", 2..4, Some(Component::Minor), "去掉前导零, 改为 \"2\""),
+            ("1..3", 2..2, Some(Component::Minor), "补全副版本号, 或删除多余的 '.'"),
+            ("1.2.3.4+build", 5..7, None, "版本号最多包含三个部分, 可以删除多余的部分, 或将其作为编译信息, 例如 \"1.2.3+4\""),
+            ("7-rc", 0..1, None, "补全副版本号, 例如 \"7.0\""),
+            ("1.0.0-rc..1", 9..9, Some(Component::Pre), "删除多余的 '.'"),
+            ("1.0.0-rc.01", 9..11, Some(Component::Pre), "去掉前导零, 改为 \"1\""),
+            ("1.0.0-", 6..6, Some(Component::Pre), "删除末尾的 '-', 或在其后补全先行版本号"),
+            ("1.0.0+a.b+c", 9..10, Some(Component::Build), "编译信息只能以一个 '+' 开头, 可以将之后的 '+' 改为 '.'"),
+        ];
+        for (input, span, component, suggestion) in cases {
+            let err = input.parse::<Version>().unwrap_err();
+            assert_eq!(err.input(), input);
+            assert_eq!(err.span(), span, "{input}");
+            assert_eq!(err.component(), component, "{input}");
+            assert_eq!(err.suggestion(), Some(suggestion), "{input}");
+        }
+        assert_eq!("1..3".parse::<Version>().unwrap_err().to_string(), "缺少副版本号");
+        assert_eq!("1.0.0-rc.01".parse::<Version>().unwrap_err().to_string(), "数字标识符 \"01\" 不能含有前导零");
+    }
+
+    /// 测试诊断信息按照显示宽度对齐, 空范围时标记在前一个字符之后
+    #[test]
+    fn test_render() {
+        let err = "1.0.0-测试版".parse::<Version>().unwrap_err();
+        assert_eq!(err.span(), 6..9);
+        assert_eq!(err.token(), "测");
+        let lines: Vec<String> = err.render().lines().map(str::to_string).collect();
+        assert_eq!(lines[0], "错误: 标识符中存在非法字符 '测'");
+        assert_eq!(lines[2], "  | 1.0.0-测试版");
+        assert_eq!(lines[3], "  |       ^^ 先行版本号");
+
+        let err = "1.0.0-rc.".parse::<Version>().unwrap_err();
+        let rendered = err.render();
+        assert!(rendered.contains("\n  |          ^ 先行版本号\n"), "{rendered}");
+        assert!(rendered.ends_with("= 建议: 删除末尾的 '.'"), "{rendered}");
+        assert_eq!(err.to_string(), "存在空的标识符");
     }
 
     /// 测试错误的版本号数字
     #[test]
     #[should_panic]
     fn  test_error_number() {
-        let _ = Version::build_string("homo.114514.1919810").unwrap();
+        let _ = "homo.114514.1919810".parse::<Version>().unwrap();
     }
 
     /// 测试错误长度
     #[test]
     #[should_panic]
     fn test_error_length() {
-        let _ = Version::build_string("1-beta").unwrap();
+        let _ = "1-beta".parse::<Version>().unwrap();
     }
-}
\ No newline at end of file
+}
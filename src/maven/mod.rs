@@ -0,0 +1,414 @@
+//! Maven 使用的版本号, 比较规则与 `org.apache.maven.artifact.versioning.ComparableVersion` 一致
+//!
+//! 版本号由 `.` 与 `-` 分隔的数字和限定符组成, 数字与字母直接相连时也视为分隔。
+//! 已知限定符按 `alpha < beta < milestone < rc < snapshot < "" < sp` 排序,
+//! `ga`/`final`/`release` 等同于空限定符, `cr` 等同于 `rc`,
+//! 后面紧跟数字的 `a`/`b`/`m` 分别是 `alpha`/`beta`/`milestone` 的缩写,
+//! 未知的限定符排在所有已知限定符之后并按字典序比较
+//!
+//! ```
+//! use version::maven::Version;
+//!
+//! let v = |s: &str| s.parse::<Version>().unwrap();
+//! assert!(v("1.0-alpha-1") < v("1.0-SNAPSHOT"));
+//! assert!(v("1.0-SNAPSHOT") < v("1.0.RELEASE"));
+//! assert!(v("1.0.RELEASE") < v("1.0-sp1"));
+//! assert_eq!(v("1.0.0"), v("1"));
+//! ```
+//!
+//! [`VersionRange`] 用于表示 `[1.0,2.0)`、`(,1.0],[1.2,)` 这样的版本范围
+
+mod range;
+
+pub use range::{Restriction, VersionRange};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 Maven 版本号
+///
+/// 保留原始字符串用于输出, 比较时使用规范化后的结构, 因此 `1.0 == 1-0 == 1.ga`
+#[derive(Debug, Clone)]
+pub struct Version {
+    value: String,
+    items: Vec<Item>,
+}
+
+/// `ComparableVersion` 中的一项: 数字、限定符或由 `-` 开始的子列表
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Item {
+    /// 去掉前导零的十进制数字, 长度不限
+    Int(String),
+    /// 小写并替换别名后的限定符
+    Str(String),
+    List(Vec<Item>),
+}
+
+///
+/// 解析 Maven 版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("版本范围 \"{0}\" 缺少右括号")]
+    UnboundedRange(String),
+
+    #[error("版本范围 \"{0}\" 中的区间相互重叠或顺序错误")]
+    RangesOverlap(String),
+
+    #[error("版本范围 \"{0}\" 中包含多个区间时不能使用软性要求")]
+    MixedSoftRequirement(String),
+
+    #[error("版本范围 \"{0}\" 中的单个版本必须用 [] 包围")]
+    SingleVersionNotInclusive(String),
+
+    #[error("版本范围 \"{0}\" 的上下边界相同")]
+    IdenticalBoundaries(String),
+
+    #[error("版本范围 \"{0}\" 的下边界大于上边界")]
+    DefiesOrdering(String),
+}
+
+/// 已知限定符, 按从旧到新的顺序排列
+const QUALIFIERS: [&str; 7] = ["alpha", "beta", "milestone", "rc", "snapshot", "", "sp"];
+
+impl Version {
+
+    /// 规范化后的版本号, 与 `ComparableVersion::getCanonical` 的结果一致
+    ///
+    /// ```
+    /// use version::maven::Version;
+    ///
+    /// let v: Version = "1.0.0a1".parse().unwrap();
+    /// assert_eq!(v.canonical(), "1-alpha-1");
+    /// ```
+    pub fn canonical(&self) -> String {
+        let mut out = String::new();
+        write_list(&mut out, &self.items);
+        out
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_lists(&self.items, &other.items)
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 规范化之后结构相同当且仅当比较相等
+        self.items.hash(state);
+    }
+}
+
+/// 输出原始字符串
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+/// 解析 Maven 版本号
+///
+/// 与 `ComparableVersion` 一样接受任意非空字符串, 比较时不区分大小写
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+        Ok(Version {
+            value: s.to_string(),
+            items: parse_items(s),
+        })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+/// 按 `ComparableVersion::parseVersion` 的方式拆分版本号
+///
+/// 遇到 `-` 或数字与字母的切换时开始一个新的子列表, 新的子列表总是当前列表的最后一项,
+/// 因此只需要记录当前列表的嵌套深度
+fn parse_items(version: &str) -> Vec<Item> {
+    let version = version.to_lowercase();
+    let mut root = Vec::new();
+    let mut depth = 0;
+    let mut is_digit = false;
+    let mut start = 0;
+
+    for (i, c) in version.char_indices() {
+        match c {
+            '.' => {
+                let item = if i == start { Item::Int("0".to_string()) } else { parse_item(is_digit, &version[start..i]) };
+                current(&mut root, depth).push(item);
+                start = i + 1;
+            }
+            '-' => {
+                let item = if i == start { Item::Int("0".to_string()) } else { parse_item(is_digit, &version[start..i]) };
+                current(&mut root, depth).push(item);
+                start = i + 1;
+                push_list(&mut root, &mut depth);
+            }
+            c if c.is_ascii_digit() => {
+                if !is_digit && i > start {
+                    // 1.0.0.X1 < 1.0.0-X2, 把 .X 视为 -X
+                    if !current(&mut root, depth).is_empty() {
+                        push_list(&mut root, &mut depth);
+                    }
+                    current(&mut root, depth).push(Item::Str(qualifier(&version[start..i], true)));
+                    start = i;
+                    push_list(&mut root, &mut depth);
+                }
+                is_digit = true;
+            }
+            _ => {
+                if is_digit && i > start {
+                    current(&mut root, depth).push(parse_item(true, &version[start..i]));
+                    start = i;
+                    push_list(&mut root, &mut depth);
+                }
+                is_digit = false;
+            }
+        }
+    }
+
+    if version.len() > start {
+        if !is_digit && !current(&mut root, depth).is_empty() {
+            push_list(&mut root, &mut depth);
+        }
+        current(&mut root, depth).push(parse_item(is_digit, &version[start..]));
+    }
+
+    normalize(&mut root);
+    root
+}
+
+fn current(root: &mut Vec<Item>, depth: usize) -> &mut Vec<Item> {
+    let mut list = root;
+    for _ in 0..depth {
+        list = match list.last_mut() {
+            Some(Item::List(inner)) => inner,
+            _ => unreachable!("当前列表总是上一级列表的最后一项"),
+        };
+    }
+    list
+}
+
+fn push_list(root: &mut Vec<Item>, depth: &mut usize) {
+    current(root, *depth).push(Item::List(Vec::new()));
+    *depth += 1;
+}
+
+fn parse_item(is_digit: bool, buf: &str) -> Item {
+    if is_digit {
+        let trimmed = buf.trim_start_matches('0');
+        Item::Int(if trimmed.is_empty() { "0" } else { trimmed }.to_string())
+    } else {
+        Item::Str(qualifier(buf, false))
+    }
+}
+
+/// 展开缩写与别名
+fn qualifier(value: &str, followed_by_digit: bool) -> String {
+    let value = match value {
+        "a" if followed_by_digit => "alpha",
+        "b" if followed_by_digit => "beta",
+        "m" if followed_by_digit => "milestone",
+        "ga" | "final" | "release" => "",
+        "cr" => "rc",
+        value => value,
+    };
+    value.to_string()
+}
+
+/// 从内到外去掉每个列表末尾的空项(`0`、空限定符、空列表), 遇到非空的非列表项时停止
+fn normalize(list: &mut Vec<Item>) {
+    for item in list.iter_mut() {
+        if let Item::List(inner) = item {
+            normalize(inner);
+        }
+    }
+    let mut i = list.len();
+    while i > 0 {
+        i -= 1;
+        if is_null(&list[i]) {
+            list.remove(i);
+        } else if !matches!(list[i], Item::List(_)) {
+            break;
+        }
+    }
+}
+
+fn is_null(item: &Item) -> bool {
+    match item {
+        Item::Int(n) => n == "0",
+        Item::Str(s) => qualifier_key(s) == qualifier_key(""),
+        Item::List(list) => list.is_empty(),
+    }
+}
+
+/// 限定符的排序键: 已知限定符按其位置, 未知限定符排在最后并按字典序比较
+fn qualifier_key(s: &str) -> (usize, &str) {
+    match QUALIFIERS.iter().position(|q| *q == s) {
+        Some(i) => (i, ""),
+        None => (QUALIFIERS.len(), s),
+    }
+}
+
+/// 比较一项与缺失的项, 例如 `1.0` 与 `1` 的第二项
+fn cmp_null(item: &Item) -> Ordering {
+    match item {
+        Item::Int(n) => if n == "0" { Ordering::Equal } else { Ordering::Greater },
+        Item::Str(s) => qualifier_key(s).cmp(&qualifier_key("")),
+        Item::List(list) => list.iter().map(cmp_null).find(|o| o.is_ne()).unwrap_or(Ordering::Equal),
+    }
+}
+
+fn cmp_item(a: &Item, b: &Item) -> Ordering {
+    match (a, b) {
+        (Item::Int(a), Item::Int(b)) => a.len().cmp(&b.len()).then_with(|| a.cmp(b)),
+        (Item::Str(a), Item::Str(b)) => qualifier_key(a).cmp(&qualifier_key(b)),
+        (Item::List(a), Item::List(b)) => cmp_lists(a, b),
+        // 1.1 > 1-sp, 1.1 > 1-1, 1-1 > 1-sp
+        (Item::Int(_), _) | (Item::List(_), Item::Str(_)) => Ordering::Greater,
+        (_, Item::Int(_)) | (Item::Str(_), Item::List(_)) => Ordering::Less,
+    }
+}
+
+fn cmp_lists(a: &[Item], b: &[Item]) -> Ordering {
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(l), Some(r)) => cmp_item(l, r),
+            (Some(l), None) => cmp_null(l),
+            (None, Some(r)) => cmp_null(r).reverse(),
+            (None, None) => unreachable!(),
+        };
+        if ordering.is_ne() {
+            return ordering
+        }
+    }
+    Ordering::Equal
+}
+
+fn write_list(out: &mut String, list: &[Item]) {
+    for item in list {
+        if !out.is_empty() {
+            out.push(if matches!(item, Item::List(_)) { '-' } else { '.' });
+        }
+        match item {
+            Item::Int(s) | Item::Str(s) => out.push_str(s),
+            Item::List(inner) => {
+                let mut nested = String::new();
+                write_list(&mut nested, inner);
+                out.push_str(&nested);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Version;
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    fn check_order(versions: &[&str]) {
+        for (i, a) in versions.iter().enumerate() {
+            for b in &versions[i + 1..] {
+                assert!(v(a) < v(b), "{a} < {b}");
+                assert!(v(b) > v(a), "{b} > {a}");
+            }
+        }
+    }
+
+    /// 用例取自 Maven 的 ComparableVersionTest
+    #[test]
+    fn test_qualifier_order() {
+        check_order(&[
+            "1-alpha2snapshot", "1-alpha2", "1-alpha-123", "1-beta-2", "1-beta123", "1-m2", "1-m11", "1-rc",
+            "1-cr2", "1-rc123", "1-SNAPSHOT", "1", "1-sp", "1-sp2", "1-sp123", "1-abc", "1-def", "1-pom-1",
+            "1-1-snapshot", "1-1", "1-2", "1-123",
+        ]);
+    }
+
+    #[test]
+    fn test_number_order() {
+        check_order(&[
+            "2.0", "2.0.a", "2-1", "2.0.2", "2.0.123", "2.1.0", "2.1-a", "2.1b", "2.1-c", "2.1-1", "2.1.0.1",
+            "2.2", "2.123", "11.a2", "11.a11", "11.b2", "11.b11", "11.m2", "11.m11", "11", "11.a", "11b", "11c",
+            "11m",
+        ]);
+        // MNG-5568
+        check_order(&["6.1.0rc3", "6.1.0", "6.1H.5-beta"]);
+        check_order(&["1", "12345678901234567890", "123456789012345678901"]);
+    }
+
+    #[test]
+    fn test_equal() {
+        let groups: &[&[&str]] = &[
+            &["1", "1.0", "1.0.0", "1-0", "1.0-0", "1ga", "1release", "1final", "1GA", "1RELeaSE", "1FinaL"],
+            &["1a", "1-a", "1.0-a", "1.0.0-a", "1.0a", "1.0.0a", "1A"],
+            &["1x", "1-x", "1.0-x", "1.0.0-x", "1.0x", "1.0.0x", "1X"],
+            &["1cr", "1rc", "1Cr", "1rC"],
+            &["1a1", "1-alpha-1", "1A1"],
+            &["1b2", "1-beta-2"],
+            &["1m3", "1-milestone-3", "1Milestone3", "1MILESTONE3"],
+            &["1.0.0.RC1", "1.0.0-RC1", "1-rc-1"],
+            &["01.002", "1.2"],
+        ];
+        for group in groups {
+            let set: HashSet<Version> = group.iter().map(|s| v(s)).collect();
+            assert_eq!(set.len(), 1, "{group:?}");
+        }
+    }
+
+    #[test]
+    fn test_canonical() {
+        let cases = [
+            ("1.0.RELEASE", "1"),
+            ("1.0-SNAPSHOT", "1-snapshot"),
+            ("1.0-alpha-1", "1-alpha-1"),
+            ("1.0-sp1", "1-sp-1"),
+            ("2.0.a", "2-a"),
+            ("1-1-snapshot", "1-1-snapshot"),
+            ("1.2.3", "1.2.3"),
+        ];
+        for (input, canonical) in cases {
+            assert_eq!(v(input).canonical(), canonical, "{input}");
+            assert_eq!(v(input).to_string(), input);
+        }
+        assert!("".parse::<Version>().is_err());
+    }
+}
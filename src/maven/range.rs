@@ -0,0 +1,395 @@
+//! Maven 版本范围, 例如 pom.xml 依赖中的 `[1.0,2.0)`、`(,1.0],[1.2,)` 与软性要求 `1.0`
+
+use super::{ParseError, Version};
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+///
+/// Maven 的版本范围, 规则与 `org.apache.maven.artifact.versioning.VersionRange` 一致
+///
+/// 支持以下写法:
+/// - `[1.0,2.0)`: 区间, `[`/`]` 包含边界, `(`/`)` 不包含边界, 边界可以省略
+/// - `[1.0]`: 只允许 1.0
+/// - `(,1.0],[1.2,)`: 多个互不重叠的区间, 满足任意一个即可
+/// - `1.0`: 软性要求, 推荐使用 1.0, 但任何版本都满足
+///
+/// ```
+/// use version::maven::{Version, VersionRange};
+///
+/// let range: VersionRange = "(,1.0],[1.2,)".parse().unwrap();
+/// assert!(range.contains(&"1.0".parse::<Version>().unwrap()));
+/// assert!(!range.contains(&"1.1".parse::<Version>().unwrap()));
+/// assert!(range.contains(&"1.2.1".parse::<Version>().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    recommended: Option<Version>,
+    restrictions: Vec<Restriction>,
+}
+
+///
+/// 版本范围中的单个区间, 边界为 `None` 时表示不限
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Restriction {
+    lower: Option<Version>,
+    lower_inclusive: bool,
+    upper: Option<Version>,
+    upper_inclusive: bool,
+}
+
+impl VersionRange {
+
+    /// 软性要求中推荐的版本
+    pub fn recommended_version(&self) -> Option<&Version> {
+        self.recommended.as_ref()
+    }
+
+    /// 所有区间, 按从低到高的顺序排列
+    pub fn restrictions(&self) -> &[Restriction] {
+        &self.restrictions
+    }
+
+    /// 是否为硬性要求, 即不是 `1.0` 这样的软性要求
+    pub fn has_restrictions(&self) -> bool {
+        !self.restrictions.is_empty() && self.recommended.is_none()
+    }
+
+    /// 判断版本号是否落在任意一个区间内
+    pub fn contains(&self, version: &Version) -> bool {
+        self.restrictions.iter().any(|r| r.contains(version))
+    }
+
+    /// 计算两个范围的交集, 与 `VersionRange::restrict` 一致
+    ///
+    /// 交集为空时结果不包含任何版本。推荐版本优先保留自身的, 其次是 `other` 的,
+    /// 并且只在落入交集时保留
+    ///
+    /// ```
+    /// use version::maven::VersionRange;
+    ///
+    /// let a: VersionRange = "[1.0,1.5),(1.6,1.8]".parse().unwrap();
+    /// let b: VersionRange = "[1.1,1.7]".parse().unwrap();
+    /// assert_eq!(a.intersect(&b).to_string(), "[1.1,1.5),(1.6,1.7]");
+    /// ```
+    pub fn intersect(&self, other: &VersionRange) -> VersionRange {
+        let restrictions = if self.restrictions.is_empty() || other.restrictions.is_empty() {
+            Vec::new()
+        } else {
+            intersection(&self.restrictions, &other.restrictions)
+        };
+
+        let recommended = if !restrictions.is_empty() {
+            let in_range = |v: &Option<Version>| v.as_ref().filter(|v| restrictions.iter().any(|r| r.contains(v))).cloned();
+            in_range(&self.recommended).or_else(|| in_range(&other.recommended))
+        } else {
+            self.recommended.clone().or_else(|| other.recommended.clone())
+        };
+
+        VersionRange { recommended, restrictions }
+    }
+}
+
+impl Restriction {
+
+    /// 不限版本的区间 `(,)`
+    fn everything() -> Restriction {
+        Restriction { lower: None, lower_inclusive: false, upper: None, upper_inclusive: false }
+    }
+
+    /// 下边界
+    pub fn lower_bound(&self) -> Option<&Version> {
+        self.lower.as_ref()
+    }
+
+    /// 下边界是否包含在区间内
+    pub fn is_lower_bound_inclusive(&self) -> bool {
+        self.lower_inclusive
+    }
+
+    /// 上边界
+    pub fn upper_bound(&self) -> Option<&Version> {
+        self.upper.as_ref()
+    }
+
+    /// 上边界是否包含在区间内
+    pub fn is_upper_bound_inclusive(&self) -> bool {
+        self.upper_inclusive
+    }
+
+    /// 判断版本号是否落在区间内
+    pub fn contains(&self, version: &Version) -> bool {
+        if let Some(lower) = &self.lower {
+            match lower.cmp(version) {
+                Ordering::Greater => return false,
+                Ordering::Equal if !self.lower_inclusive => return false,
+                _ => {}
+            }
+        }
+        if let Some(upper) = &self.upper {
+            match upper.cmp(version) {
+                Ordering::Less => return false,
+                Ordering::Equal if !self.upper_inclusive => return false,
+                _ => {}
+            }
+        }
+        true
+    }
+}
+
+/// 同时遍历两组有序区间, 求出所有重叠部分
+fn intersection(r1: &[Restriction], r2: &[Restriction]) -> Vec<Restriction> {
+    let mut restrictions = Vec::with_capacity(r1.len() + r2.len());
+    let (mut i1, mut i2) = (0, 0);
+
+    while i1 < r1.len() && i2 < r2.len() {
+        let (res1, res2) = (&r1[i1], &r2[i2]);
+
+        let below = matches!((&res1.lower, &res2.upper), (Some(l), Some(u)) if l > u);
+        if below {
+            i2 += 1;
+            continue;
+        }
+        let above = matches!((&res1.upper, &res2.lower), (Some(u), Some(l)) if u < l);
+        if above {
+            i1 += 1;
+            continue;
+        }
+
+        let (lower, lower_inclusive) = match (&res1.lower, &res2.lower) {
+            (None, _) => (res2.lower.clone(), res2.lower_inclusive),
+            (_, None) => (res1.lower.clone(), res1.lower_inclusive),
+            (Some(a), Some(b)) => match a.cmp(b) {
+                Ordering::Less => (res2.lower.clone(), res2.lower_inclusive),
+                Ordering::Equal => (res1.lower.clone(), res1.lower_inclusive && res2.lower_inclusive),
+                Ordering::Greater => (res1.lower.clone(), res1.lower_inclusive),
+            },
+        };
+        // 上边界取自哪一方, 就推进哪一方
+        let (upper, upper_inclusive, from_res2) = match (&res1.upper, &res2.upper) {
+            (None, _) => (res2.upper.clone(), res2.upper_inclusive, true),
+            (_, None) => (res1.upper.clone(), res1.upper_inclusive, false),
+            (Some(a), Some(b)) => match a.cmp(b) {
+                Ordering::Less => (res1.upper.clone(), res1.upper_inclusive, false),
+                Ordering::Equal => (res1.upper.clone(), res1.upper_inclusive && res2.upper_inclusive, false),
+                Ordering::Greater => (res2.upper.clone(), res2.upper_inclusive, true),
+            },
+        };
+
+        // 上下边界相同时, 只有两端都包含才不为空
+        let single = matches!((&lower, &upper), (Some(l), Some(u)) if l == u);
+        if !single || (lower_inclusive && upper_inclusive) {
+            restrictions.push(Restriction { lower, lower_inclusive, upper, upper_inclusive });
+        }
+
+        if from_res2 {
+            i2 += 1;
+        } else {
+            i1 += 1;
+        }
+    }
+    restrictions
+}
+
+/// 软性要求输出推荐版本, 否则依次输出各个区间, 以 `,` 分隔
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(recommended) = &self.recommended {
+            return write!(f, "{recommended}")
+        }
+        for (i, restriction) in self.restrictions.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{restriction}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Restriction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.lower_inclusive { "[" } else { "(" })?;
+        if let Some(lower) = &self.lower {
+            write!(f, "{lower}")?;
+        }
+        f.write_str(",")?;
+        if let Some(upper) = &self.upper {
+            write!(f, "{upper}")?;
+        }
+        f.write_str(if self.upper_inclusive { "]" } else { ")" })
+    }
+}
+
+/// 解析版本范围
+///
+/// 区间必须按从低到高的顺序排列且互不重叠, 软性要求不能与区间混用
+impl FromStr for VersionRange {
+    type Err = ParseError;
+
+    fn from_str(spec: &str) -> Result<VersionRange, ParseError> {
+        let mut restrictions: Vec<Restriction> = Vec::new();
+        let mut process = spec.trim();
+        if process.is_empty() {
+            return Err(ParseError::Empty)
+        }
+
+        while process.starts_with(['[', '(']) {
+            let index = match (process.find(')'), process.find(']')) {
+                (Some(paren), Some(bracket)) => paren.min(bracket),
+                (paren, bracket) => paren.or(bracket).ok_or_else(|| ParseError::UnboundedRange(spec.to_string()))?,
+            };
+
+            let restriction = parse_restriction(&process[..=index], spec)?;
+            if let Some(previous) = restrictions.last().and_then(|r| r.upper.as_ref()) {
+                let overlaps = restriction.lower.as_ref().is_none_or(|lower| lower < previous);
+                if overlaps {
+                    return Err(ParseError::RangesOverlap(spec.to_string()))
+                }
+            }
+            restrictions.push(restriction);
+
+            process = process[index + 1..].trim_start();
+            if let Some(rest) = process.strip_prefix(',') {
+                process = rest.trim_start();
+            }
+        }
+
+        if process.is_empty() {
+            return Ok(VersionRange { recommended: None, restrictions })
+        }
+        if !restrictions.is_empty() {
+            return Err(ParseError::MixedSoftRequirement(spec.to_string()))
+        }
+        Ok(VersionRange {
+            recommended: Some(process.parse()?),
+            restrictions: vec![Restriction::everything()],
+        })
+    }
+}
+
+fn parse_restriction(s: &str, spec: &str) -> Result<Restriction, ParseError> {
+    let lower_inclusive = s.starts_with('[');
+    let upper_inclusive = s.ends_with(']');
+    let process = s[1..s.len() - 1].trim();
+
+    let Some((lower, upper)) = process.split_once(',') else {
+        if !lower_inclusive || !upper_inclusive {
+            return Err(ParseError::SingleVersionNotInclusive(spec.to_string()))
+        }
+        let version: Version = process.parse()?;
+        return Ok(Restriction { lower: Some(version.clone()), lower_inclusive, upper: Some(version), upper_inclusive })
+    };
+
+    let (lower, upper) = (lower.trim(), upper.trim());
+    if lower == upper {
+        return Err(ParseError::IdenticalBoundaries(spec.to_string()))
+    }
+    let lower = Some(lower).filter(|s| !s.is_empty()).map(str::parse::<Version>).transpose()?;
+    let upper = Some(upper).filter(|s| !s.is_empty()).map(str::parse::<Version>).transpose()?;
+    if let (Some(lower), Some(upper)) = (&lower, &upper) && upper < lower {
+        return Err(ParseError::DefiesOrdering(spec.to_string()))
+    }
+    Ok(Restriction { lower, lower_inclusive, upper, upper_inclusive })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ParseError, Version};
+    use super::VersionRange;
+
+    fn range(s: &str) -> VersionRange {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    /// 用例取自 Maven 的 VersionRangeTest
+    #[test]
+    fn test_parse_and_contains() {
+        let r = range("(,1.0]");
+        assert_eq!(r.restrictions().len(), 1);
+        assert_eq!(r.restrictions()[0].lower_bound(), None);
+        assert_eq!(r.restrictions()[0].upper_bound(), Some(&v("1.0")));
+        assert!(r.restrictions()[0].is_upper_bound_inclusive());
+        assert!(r.has_restrictions());
+
+        let r = range("1.0");
+        assert_eq!(r.recommended_version(), Some(&v("1.0")));
+        assert!(!r.has_restrictions());
+        assert!(r.contains(&v("0.1")) && r.contains(&v("99")));
+        assert_eq!(r.restrictions()[0].to_string(), "(,)");
+
+        let cases = [
+            ("[1.0]", "1.0", true), ("[1.0]", "1.0.1", false),
+            ("[1.2,1.3]", "1.2", true), ("[1.2,1.3]", "1.3", true), ("[1.2,1.3]", "1.3.1", false),
+            ("[1.0,2.0)", "2.0", false), ("[1.0,2.0)", "2.0-SNAPSHOT", true), ("[1.0,2.0)", "1.0", true),
+            ("(1.0,2.0]", "1.0", false),
+            ("[1.5,)", "1.5", true), ("[1.5,)", "1.4", false), ("[1.5,)", "100", true),
+            ("(,1.0],[1.2,)", "1.1", false), ("(,1.0],[1.2,)", "0.9", true), ("(,1.0],[1.2,)", "1.2", true),
+            ("[1.0,1.1-SNAPSHOT]", "1.1-SNAPSHOT", true), ("[1.0,1.1-SNAPSHOT]", "1.1", false),
+            ("[5.0.9.0,5.0.10.0)", "5.0.9.0", true),
+            (" [ 1.0 , 2.0 ) ", "1.5", true),
+        ];
+        for (spec, version, expected) in cases {
+            assert_eq!(range(spec).contains(&v(version)), expected, "{version} in {spec}");
+        }
+        assert_eq!(range("[1.0]").to_string(), "[1.0,1.0]");
+        assert_eq!(range("(,1.0] , [1.2,)").to_string(), "(,1.0],[1.2,)");
+    }
+
+    #[test]
+    fn test_invalid() {
+        let cases = [
+            ("(1.0)", "SingleVersionNotInclusive"),
+            ("[1.0)", "SingleVersionNotInclusive"),
+            ("(1.0]", "SingleVersionNotInclusive"),
+            ("(1.0,1.0]", "IdenticalBoundaries"),
+            ("[1.0,1.0)", "IdenticalBoundaries"),
+            ("(1.0,1.0)", "IdenticalBoundaries"),
+            ("[1.1,1.0]", "DefiesOrdering"),
+            ("[1.0,1.2),1.3", "MixedSoftRequirement"),
+            ("[1.0,1.2),(1.1,1.3]", "RangesOverlap"),
+            ("[1.1,1.3),(1.0,1.2]", "RangesOverlap"),
+            ("(1.1,1.2],[1.0,1.1)", "RangesOverlap"),
+            ("[1.0,", "UnboundedRange"),
+            ("", "Empty"),
+        ];
+        for (spec, kind) in cases {
+            let err = spec.parse::<VersionRange>().unwrap_err();
+            assert!(format!("{err:?}").starts_with(kind), "{spec}: {err:?}");
+        }
+        assert_eq!("[]".parse::<VersionRange>().unwrap_err(), ParseError::Empty);
+    }
+
+    #[test]
+    fn test_intersect() {
+        let cases = [
+            ("1.0", "1.1", "1.0", "(,)"),
+            ("[1.0,)", "1.1", "1.1", "[1.0,)"),
+            ("[1.1,)", "1.0", "", "[1.1,)"),
+            ("[1.0,1.2]", "[1.1,1.3]", "", "[1.1,1.2]"),
+            ("[1.1,1.3]", "[1.0,1.2]", "", "[1.1,1.2]"),
+            ("[1.0,)", "(,1.1]", "", "[1.0,1.1]"),
+            ("[1.0,1.2]", "[1.2,1.3]", "", "[1.2,1.2]"),
+            ("[1.0,1.2)", "[1.2,1.3]", "", ""),
+            ("(,1.1),(1.1,)", "1.1", "", "(,1.1),(1.1,)"),
+            ("[1.0,1.5),(1.6,1.8]", "[1.1,1.7]", "", "[1.1,1.5),(1.6,1.7]"),
+            ("[1.0,1.1],[1.3,1.4]", "[1.2,1.2.5]", "", ""),
+        ];
+        for (a, b, recommended, restrictions) in cases {
+            let merged = range(a).intersect(&range(b));
+            assert_eq!(merged.recommended_version().map(ToString::to_string).unwrap_or_default(), recommended, "{a} & {b}");
+            let joined: Vec<String> = merged.restrictions().iter().map(ToString::to_string).collect();
+            assert_eq!(joined.join(","), restrictions, "{a} & {b}");
+        }
+
+        // 审计: 补丁版本是否同时满足两个依赖方的要求
+        let allowed = range("[2.17,3)").intersect(&range("[2.15.0,2.17.1]"));
+        assert!(allowed.contains(&v("2.17.1")));
+        assert!(!allowed.contains(&v("2.16")));
+        assert!(range("[1.0,1.2)").intersect(&range("[1.2,1.3]")).restrictions().is_empty());
+    }
+}
@@ -0,0 +1,672 @@
+//! npm (node-semver) 风格的版本范围
+//!
+//! 支持 `1.2.3 - 2.3.4` 连字符范围、`||` 并集、`1.x` 通配范围、`~`/`^` 范围以及比较运算符,
+//! 解析后统一转换为由 `>=`、`<` 等基本比较器组成的比较器集合, 匹配规则与 node-semver 保持一致
+//!
+//! ```
+//! use version::npm::Range;
+//! use version::Version;
+//!
+//! let range: Range = "^0.0.1 || 1.2.3 - 2.3".parse().unwrap();
+//! assert_eq!(range.to_string(), ">=0.0.1 <0.0.2-0||>=1.2.3 <2.4.0-0");
+//! assert!(range.matches(&"2.3.9".parse::<Version>().unwrap()));
+//! ```
+
+use crate::{parse_build, parse_number, parse_pre, Component, Identifier, Version};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// npm 版本范围, 由若干个以 `||` 连接的比较器集合组成
+///
+/// 任意一个比较器集合中的全部比较器均满足时, 版本满足该范围
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    set: Vec<Vec<Comparator>>,
+    include_prerelease: bool,
+}
+
+///
+/// 解析与匹配时使用的选项, 对应 node-semver 的 `options`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Options {
+    /// 对应 `includePrerelease`, 为 `true` 时先行版本与正式版本一视同仁
+    pub include_prerelease: bool,
+}
+
+///
+/// 规范化后的基本比较器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Comparator {
+    /// 匹配任意版本, 对应 node-semver 中的 `ANY`
+    Any,
+    /// 由运算符与版本号组成的比较器, 版本号不含编译信息
+    Op(Op, Version),
+}
+
+///
+/// 基本比较器的运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `=` 或不带运算符
+    Eq,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+}
+
+///
+/// 解析 npm 版本范围时可能发生的错误
+#[derive(Error, Debug)]
+pub enum ParseError {
+
+    #[error("版本号解析失败: {0}")]
+    Version(#[from] crate::ParseError),
+
+    #[error("非法的比较器 \"{0}\"")]
+    InvalidComparator(String),
+}
+
+impl Range {
+
+    /// 使用指定的选项解析版本范围
+    ///
+    /// # 参数
+    /// - `range` - 版本范围字符串
+    /// - `options` - 解析与匹配选项
+    ///
+    /// # 示例
+    /// ```
+    /// use version::npm::{Options, Range};
+    /// use version::Version;
+    ///
+    /// let options = Options { include_prerelease: true };
+    /// let range = Range::parse_with("^1.2.3", options).unwrap();
+    /// assert_eq!(range.to_string(), ">=1.2.3 <2.0.0-0");
+    /// assert!(range.matches(&"1.5.0-beta.1".parse::<Version>().unwrap()));
+    /// ```
+    pub fn parse_with(range: &str, options: Options) -> Result<Range, ParseError> {
+        let include_prerelease = options.include_prerelease;
+        let mut set: Vec<Vec<Comparator>> = range
+            .split("||")
+            .map(|part| parse_set(part.trim(), include_prerelease))
+            .collect::<Result<_, _>>()?;
+
+        // 存在非空集时去掉空集, 存在 `*` 时整个范围等价于 `*`
+        if set.len() > 1 {
+            set.retain(|comparators| !is_null_set(comparators));
+            if set.is_empty() {
+                set.push(vec![null_set()]);
+            }
+            if let Some(any) = set.iter().position(|comparators| comparators == &[Comparator::Any]) {
+                set = vec![set.swap_remove(any)];
+            }
+        }
+        Ok(Range { set, include_prerelease })
+    }
+
+    /// 判断版本号是否满足此范围, 等价于 node-semver 的 `satisfies`
+    ///
+    /// 未开启 `include_prerelease` 时, 先行版本只有在同一个比较器集合中存在
+    /// 主.副.补丁 相同且带有先行版本号的比较器时才会匹配
+    pub fn matches(&self, version: &Version) -> bool {
+        self.set.iter().any(|comparators| self.test_set(comparators, version))
+    }
+
+    /// 规范化后的比较器集合
+    pub fn comparator_sets(&self) -> &[Vec<Comparator>] {
+        &self.set
+    }
+
+    fn test_set(&self, comparators: &[Comparator], version: &Version) -> bool {
+        if !comparators.iter().all(|cmp| cmp.test(version)) {
+            return false
+        }
+        if version.pre.is_empty() || self.include_prerelease {
+            return true
+        }
+        comparators.iter().any(|cmp| match cmp {
+            Comparator::Any => false,
+            Comparator::Op(_, allowed) => {
+                !allowed.pre.is_empty()
+                    && allowed.major == version.major
+                    && allowed.minor == version.minor
+                    && allowed.patch == version.patch
+            }
+        })
+    }
+}
+
+impl Comparator {
+
+    /// 判断版本号是否满足此比较器, 不考虑先行版本的选择规则
+    pub fn test(&self, version: &Version) -> bool {
+        match self {
+            Comparator::Any => true,
+            Comparator::Op(op, bound) => match op {
+                Op::Eq => version == bound,
+                Op::Gt => version > bound,
+                Op::Ge => version >= bound,
+                Op::Lt => version < bound,
+                Op::Le => version <= bound,
+            },
+        }
+    }
+}
+
+impl FromStr for Range {
+    type Err = ParseError;
+
+    fn from_str(range: &str) -> Result<Range, ParseError> {
+        Range::parse_with(range, Options::default())
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, comparators) in self.set.iter().enumerate() {
+            if i > 0 {
+                f.write_str("||")?;
+            }
+            for (j, cmp) in comparators.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{cmp}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Comparator::Any => f.write_str("*"),
+            Comparator::Op(op, version) => {
+                f.write_str(match op {
+                    Op::Eq => "",
+                    Op::Gt => ">",
+                    Op::Ge => ">=",
+                    Op::Lt => "<",
+                    Op::Le => "<=",
+                })?;
+                write!(f, "{version}")
+            }
+        }
+    }
+}
+
+/// 可能带有通配符的版本号, `None` 表示 `x`、`X` 或 `*`
+///
+/// 某个部分为通配时, 其后的部分同样视为通配
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<Identifier>,
+}
+
+/// 解析一个以空白分隔的比较器集合
+fn parse_set(set: &str, include_prerelease: bool) -> Result<Vec<Comparator>, ParseError> {
+    let mut tokens: Vec<&str> = set.split_whitespace().collect();
+
+    // 连字符范围 `a - b`
+    if tokens.len() == 3 && tokens[1] == "-" {
+        return hyphen(&parse_partial(tokens[0])?, &parse_partial(tokens[2])?, include_prerelease)
+    }
+
+    // 运算符与版本号之间允许存在空白, 例如 `>= 1.2.3`
+    let mut merged: Vec<String> = Vec::with_capacity(tokens.len());
+    tokens.reverse();
+    while let Some(token) = tokens.pop() {
+        if token.trim_start_matches(['<', '>', '=', '~', '^']).is_empty() {
+            match tokens.pop() {
+                Some(next) => merged.push(format!("{token}{next}")),
+                None => return Err(ParseError::InvalidComparator(token.to_string())),
+            }
+        } else {
+            merged.push(token.to_string());
+        }
+    }
+
+    let mut comparators: Vec<Comparator> = Vec::new();
+    for token in &merged {
+        for cmp in parse_comparator(token, include_prerelease)? {
+            // 空集吸收整个集合
+            if is_null_set(std::slice::from_ref(&cmp)) {
+                return Ok(vec![cmp])
+            }
+            if !comparators.contains(&cmp) {
+                comparators.push(cmp);
+            }
+        }
+    }
+    // 多个比较器时去掉 `*`
+    if comparators.len() > 1 {
+        comparators.retain(|cmp| *cmp != Comparator::Any);
+    }
+    if comparators.is_empty() {
+        comparators.push(Comparator::Any);
+    }
+    Ok(comparators)
+}
+
+/// 将单个比较器展开为基本比较器
+fn parse_comparator(token: &str, include_prerelease: bool) -> Result<Vec<Comparator>, ParseError> {
+    if let Some(rest) = token.strip_prefix("~>").or_else(|| token.strip_prefix('~')) {
+        return tilde(&parse_partial(rest)?)
+    }
+    if let Some(rest) = token.strip_prefix('^') {
+        return caret(&parse_partial(rest)?, include_prerelease)
+    }
+    let (op, rest) = [">=", "<=", ">", "<", "="]
+        .iter()
+        .find_map(|op| token.strip_prefix(op).map(|rest| (*op, rest)))
+        .unwrap_or(("", token));
+    primitive(op, &parse_partial(rest)?, include_prerelease)
+}
+
+/// 解析可能带有通配符的版本号, 允许以 `v` 或 `=` 开头
+fn parse_partial(s: &str) -> Result<Partial, ParseError> {
+    let invalid = || ParseError::InvalidComparator(s.to_string());
+    let rest = s.trim_start_matches(['v', '=']);
+    if rest.is_empty() {
+        return Err(invalid())
+    }
+
+    // 编译信息不参与比较
+    let rest = match rest.split_once('+') {
+        Some((rest, build)) => {
+            parse_build(build)?;
+            rest
+        }
+        None => rest,
+    };
+    let (core, pre) = match rest.split_once('-') {
+        Some((core, pre)) => (core, parse_pre(pre)?),
+        None => (rest, Vec::new()),
+    };
+
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.len() > 3 || (!pre.is_empty() && parts.len() < 3) {
+        return Err(invalid())
+    }
+    let components = [Component::Major, Component::Minor, Component::Patch];
+    let mut numbers: [Option<u64>; 3] = [None; 3];
+    let mut wildcard = false;
+    for (i, (part, component)) in parts.iter().zip(components).enumerate() {
+        if matches!(*part, "x" | "X" | "*") {
+            wildcard = true;
+        } else if !wildcard {
+            numbers[i] = Some(parse_number(part, component)?);
+        } else if !part.bytes().all(|b| b.is_ascii_digit()) || part.is_empty() {
+            // 通配符之后的数字会被忽略, 但仍需是合法的数字
+            return Err(invalid())
+        }
+    }
+    Ok(Partial { major: numbers[0], minor: numbers[1], patch: numbers[2], pre })
+}
+
+/// `~1.2.3` := `>=1.2.3 <1.3.0-0`, `~1.2` := `>=1.2.0 <1.3.0-0`, `~1` := `>=1.0.0 <2.0.0-0`
+fn tilde(p: &Partial) -> Result<Vec<Comparator>, ParseError> {
+    let Some(major) = p.major else { return Ok(vec![Comparator::Any]) };
+    let Some(minor) = p.minor else {
+        return Ok(vec![ge(major, 0, 0, Vec::new()), lt(inc(major, Component::Major)?, 0, 0)])
+    };
+    let upper = lt(major, inc(minor, Component::Minor)?, 0);
+    match p.patch {
+        None => Ok(vec![ge(major, minor, 0, Vec::new()), upper]),
+        Some(patch) => Ok(vec![ge(major, minor, patch, p.pre.clone()), upper]),
+    }
+}
+
+/// `^` 范围: 从左往右第一个非零的部分不能改变
+fn caret(p: &Partial, include_prerelease: bool) -> Result<Vec<Comparator>, ParseError> {
+    let z = zero_pre(include_prerelease);
+    let Some(major) = p.major else { return Ok(vec![Comparator::Any]) };
+    let Some(minor) = p.minor else {
+        return Ok(vec![ge(major, 0, 0, z), lt(inc(major, Component::Major)?, 0, 0)])
+    };
+    let Some(patch) = p.patch else {
+        let upper = if major == 0 {
+            lt(major, inc(minor, Component::Minor)?, 0)
+        } else {
+            lt(inc(major, Component::Major)?, 0, 0)
+        };
+        return Ok(vec![ge(major, minor, 0, z), upper])
+    };
+
+    let upper = if major == 0 && minor == 0 {
+        lt(major, minor, inc(patch, Component::Patch)?)
+    } else if major == 0 {
+        lt(major, inc(minor, Component::Minor)?, 0)
+    } else {
+        lt(inc(major, Component::Major)?, 0, 0)
+    };
+    // 与 node-semver 一致, 主版本号非零时下界不追加 `-0`
+    let lower_pre = if !p.pre.is_empty() {
+        p.pre.clone()
+    } else if major == 0 {
+        z
+    } else {
+        Vec::new()
+    };
+    Ok(vec![ge(major, minor, patch, lower_pre), upper])
+}
+
+/// 比较运算符与通配范围, 例如 `>1.2`、`<=1`、`1.x`、`1.2.3`
+fn primitive(op: &str, p: &Partial, include_prerelease: bool) -> Result<Vec<Comparator>, ParseError> {
+    let pr = zero_pre(include_prerelease);
+    let any_x = p.patch.is_none();
+    let op = if op == "=" && any_x { "" } else { op };
+
+    let Some(major) = p.major else {
+        return Ok(vec![if op == ">" || op == "<" { null_set() } else { Comparator::Any }])
+    };
+    if !op.is_empty() && any_x {
+        let minor_x = p.minor.is_none();
+        let minor = p.minor.unwrap_or(0);
+        // 将缺省部分提升到下一个版本, 例如 `>1.2` := `>=1.3.0`, `<=1.2` := `<1.3.0-0`
+        let bumped = || -> Result<(u64, u64), ParseError> {
+            if minor_x {
+                Ok((inc(major, Component::Major)?, 0))
+            } else {
+                Ok((major, inc(minor, Component::Minor)?))
+            }
+        };
+        return Ok(vec![match op {
+            ">" => {
+                let (major, minor) = bumped()?;
+                ge(major, minor, 0, pr)
+            }
+            "<=" => {
+                let (major, minor) = bumped()?;
+                lt(major, minor, 0)
+            }
+            "<" => lt(major, minor, 0),
+            _ => ge(major, minor, 0, pr),
+        }])
+    }
+    match (p.minor, p.patch) {
+        (None, _) => Ok(vec![ge(major, 0, 0, pr), lt(inc(major, Component::Major)?, 0, 0)]),
+        (Some(minor), None) => Ok(vec![ge(major, minor, 0, pr), lt(major, inc(minor, Component::Minor)?, 0)]),
+        (Some(minor), Some(patch)) => {
+            let op = match op {
+                ">" => Op::Gt,
+                ">=" => Op::Ge,
+                "<" => Op::Lt,
+                "<=" => Op::Le,
+                _ => Op::Eq,
+            };
+            Ok(vec![Comparator::Op(op, version(major, minor, patch, p.pre.clone()))])
+        }
+    }
+}
+
+/// 连字符范围 `a - b` := `>=a <=b`, 缺省部分按通配处理
+fn hyphen(from: &Partial, to: &Partial, include_prerelease: bool) -> Result<Vec<Comparator>, ParseError> {
+    let mut comparators = Vec::with_capacity(2);
+    if let Some(major) = from.major {
+        comparators.push(match (from.minor, from.patch) {
+            (None, _) => ge(major, 0, 0, zero_pre(include_prerelease)),
+            (Some(minor), None) => ge(major, minor, 0, zero_pre(include_prerelease)),
+            (Some(minor), Some(patch)) if !from.pre.is_empty() => ge(major, minor, patch, from.pre.clone()),
+            (Some(minor), Some(patch)) => ge(major, minor, patch, zero_pre(include_prerelease)),
+        });
+    }
+    if let Some(major) = to.major {
+        comparators.push(match (to.minor, to.patch) {
+            (None, _) => lt(inc(major, Component::Major)?, 0, 0),
+            (Some(minor), None) => lt(major, inc(minor, Component::Minor)?, 0),
+            (Some(minor), Some(patch)) if !to.pre.is_empty() => {
+                Comparator::Op(Op::Le, version(major, minor, patch, to.pre.clone()))
+            }
+            (Some(minor), Some(patch)) if include_prerelease => lt(major, minor, inc(patch, Component::Patch)?),
+            (Some(minor), Some(patch)) => Comparator::Op(Op::Le, version(major, minor, patch, Vec::new())),
+        });
+    }
+    if comparators.is_empty() {
+        comparators.push(Comparator::Any);
+    }
+    Ok(comparators)
+}
+
+fn version(major: u64, minor: u64, patch: u64, pre: Vec<Identifier>) -> Version {
+    Version { major, minor, patch, pre, build: Vec::new() }
+}
+
+/// `>=major.minor.patch[-pre]`
+fn ge(major: u64, minor: u64, patch: u64, pre: Vec<Identifier>) -> Comparator {
+    Comparator::Op(Op::Ge, version(major, minor, patch, pre))
+}
+
+/// `<major.minor.patch-0`, 排除上界的所有先行版本
+fn lt(major: u64, minor: u64, patch: u64) -> Comparator {
+    Comparator::Op(Op::Lt, version(major, minor, patch, zero_pre(true)))
+}
+
+/// 不匹配任何版本的比较器 `<0.0.0-0`
+fn null_set() -> Comparator {
+    lt(0, 0, 0)
+}
+
+fn is_null_set(comparators: &[Comparator]) -> bool {
+    comparators.first() == Some(&null_set())
+}
+
+/// 开启时返回先行版本号 `0`, 即 `-0` 后缀
+fn zero_pre(enabled: bool) -> Vec<Identifier> {
+    if enabled { vec![Identifier::Numeric(0)] } else { Vec::new() }
+}
+
+/// 将版本号的某个部分加一, 溢出时返回错误
+fn inc(n: u64, component: Component) -> Result<u64, ParseError> {
+    n.checked_add(1).ok_or(ParseError::Version(crate::ParseErrorKind::Overflow { component, limit: u64::MAX - 1 }.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Options, ParseError, Range};
+    use crate::Version;
+
+    /// 取自 node-semver 测试用例的范围规范化结果
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("1.0.0 - 2.0.0", ">=1.0.0 <=2.0.0"),
+            ("1.2 - 2.3.4", ">=1.2.0 <=2.3.4"),
+            ("1.2.3 - 2.3", ">=1.2.3 <2.4.0-0"),
+            ("1.2.3 - 2", ">=1.2.3 <3.0.0-0"),
+            ("1.0.0", "1.0.0"),
+            (">=*", "*"),
+            ("", "*"),
+            ("*", "*"),
+            (">=1.0.0", ">=1.0.0"),
+            (">1.0.0", ">1.0.0"),
+            ("<=2.0.0", "<=2.0.0"),
+            ("<    2.0.0", "<2.0.0"),
+            (">= 1.0.0", ">=1.0.0"),
+            ("0.1.20 || 1.2.4", "0.1.20||1.2.4"),
+            (">=0.2.3 || <0.0.1", ">=0.2.3||<0.0.1"),
+            ("2.x.x", ">=2.0.0 <3.0.0-0"),
+            ("1.2.x", ">=1.2.0 <1.3.0-0"),
+            ("1.2.x || 2.x", ">=1.2.0 <1.3.0-0||>=2.0.0 <3.0.0-0"),
+            ("x", "*"),
+            ("2.*.*", ">=2.0.0 <3.0.0-0"),
+            ("2", ">=2.0.0 <3.0.0-0"),
+            ("2.3", ">=2.3.0 <2.4.0-0"),
+            ("~2.4", ">=2.4.0 <2.5.0-0"),
+            ("~>3.2.1", ">=3.2.1 <3.3.0-0"),
+            ("~1", ">=1.0.0 <2.0.0-0"),
+            ("~> 1", ">=1.0.0 <2.0.0-0"),
+            ("~1.0", ">=1.0.0 <1.1.0-0"),
+            ("^0", ">=0.0.0 <1.0.0-0"),
+            ("^ 1", ">=1.0.0 <2.0.0-0"),
+            ("^0.1", ">=0.1.0 <0.2.0-0"),
+            ("^1.0", ">=1.0.0 <2.0.0-0"),
+            ("^1.2", ">=1.2.0 <2.0.0-0"),
+            ("^0.0.1", ">=0.0.1 <0.0.2-0"),
+            ("^0.0.1-beta", ">=0.0.1-beta <0.0.2-0"),
+            ("^0.1.2", ">=0.1.2 <0.2.0-0"),
+            ("^1.2.3", ">=1.2.3 <2.0.0-0"),
+            ("^1.2.3-beta.4", ">=1.2.3-beta.4 <2.0.0-0"),
+            ("<1", "<1.0.0-0"),
+            ("< 1", "<1.0.0-0"),
+            (">=1", ">=1.0.0"),
+            ("<1.2", "<1.2.0-0"),
+            (">1", ">=2.0.0"),
+            (">1.2", ">=1.3.0"),
+            ("<=1.2", "<1.3.0-0"),
+            ("=1.2", ">=1.2.0 <1.3.0-0"),
+            (">*", "<0.0.0-0"),
+            ("<*", "<0.0.0-0"),
+            (">1.2.3 <*", "<0.0.0-0"),
+            (">1.2.3 <* || 1.x", ">=1.0.0 <2.0.0-0"),
+            ("1.2.3 - *", ">=1.2.3"),
+            ("* - 1.2.3", "<=1.2.3"),
+            ("v1.2.3", "1.2.3"),
+            ("=v1.2.3+build.5", "1.2.3"),
+        ];
+        for (range, expected) in cases {
+            let parsed: Range = range.parse().unwrap_or_else(|e| panic!("{range}: {e}"));
+            assert_eq!(parsed.to_string(), expected, "{range}");
+        }
+    }
+
+    /// 开启 includePrerelease 后的规范化结果
+    #[test]
+    fn test_normalize_include_prerelease() {
+        let options = Options { include_prerelease: true };
+        let cases = [
+            ("1.2.3 - 2.3.4", ">=1.2.3-0 <2.3.5-0"),
+            ("1.2 - 2.3.4-beta", ">=1.2.0-0 <=2.3.4-beta"),
+            ("^0.0.1", ">=0.0.1-0 <0.0.2-0"),
+            ("^1.2.x", ">=1.2.0-0 <2.0.0-0"),
+            ("^1.2.3", ">=1.2.3 <2.0.0-0"),
+            ("1.x", ">=1.0.0-0 <2.0.0-0"),
+            (">1", ">=2.0.0-0"),
+        ];
+        for (range, expected) in cases {
+            assert_eq!(Range::parse_with(range, options).unwrap().to_string(), expected, "{range}");
+        }
+    }
+
+    /// 取自 node-semver 测试用例的匹配结果
+    #[test]
+    fn test_satisfies() {
+        let included = [
+            ("1.0.0 - 2.0.0", "1.2.3"),
+            ("^1.2.3+build", "1.3.0"),
+            ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3"),
+            ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3-pre.2"),
+            ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "2.4.3-alpha"),
+            ("*", "1.2.3"),
+            (">=1.0.0", "1.0.0"),
+            ("<=2.0.0", "0.2.9"),
+            ("0.1.20 || 1.2.4", "1.2.4"),
+            (">=0.2.3 || <0.0.1", "0.0.0"),
+            ("2.x.x", "2.1.3"),
+            ("1.2.x || 2.x", "2.1.3"),
+            ("~2.4", "2.4.5"),
+            ("~>3.2.1", "3.2.2"),
+            ("~1", "1.2.3"),
+            ("~1.0", "1.0.2"),
+            (">=1", "1.0.0"),
+            ("<1.2", "1.1.1"),
+            ("~v0.5.4-pre", "0.5.5"),
+            ("~v0.5.4-pre", "0.5.4"),
+            ("=0.7.x", "0.7.2"),
+            (">=0.7.x", "0.7.2"),
+            ("<=0.7.x", "0.6.2"),
+            ("~1.2.1 >=1.2.3", "1.2.3"),
+            (">=1.2.1 1.2.3", "1.2.3"),
+            ("^1.2.3", "1.8.1"),
+            ("^0.1.2", "0.1.2"),
+            ("^0.1", "0.1.2"),
+            ("^1.2.3-alpha", "1.2.3-pre"),
+            ("^1.2.0-alpha", "1.2.0-pre"),
+            ("^0.0.1-alpha", "0.0.1-beta"),
+            ("^0.1.1-alpha", "0.1.1-beta"),
+            ("^x", "1.2.3"),
+            ("<=7.x", "7.9.9"),
+        ];
+        for (range, v) in included {
+            let range: Range = range.parse().unwrap();
+            assert!(range.matches(&v.parse::<Version>().unwrap()), "{range} 应当匹配 {v}");
+        }
+
+        let excluded = [
+            ("1.0.0 - 2.0.0", "2.2.3"),
+            ("1.2.3+asdf - 2.4.3+asdf", "1.2.3-pre.2"),
+            ("1.2.3+asdf - 2.4.3+asdf", "2.4.3-alpha"),
+            ("^1.2.3+build", "2.0.0"),
+            ("^1.2.3+build", "1.2.0"),
+            ("^1.2.3", "1.2.3-pre"),
+            ("^1.2", "1.2.0-pre"),
+            (">1.2", "1.3.0-beta"),
+            ("<=1.2.3", "1.2.3-beta"),
+            ("^1.2.3", "1.2.3-beta"),
+            ("=0.7.x", "0.7.0-asdf"),
+            (">=0.7.x", "0.7.0-asdf"),
+            ("1", "1.0.0-beta"),
+            ("<1", "1.0.0-beta"),
+            ("< 1", "1.0.0-beta"),
+            ("1.0.0", "1.0.1"),
+            (">=1.0.0", "0.0.0"),
+            ("<=2.0.0", "3.0.0"),
+            ("0.1.20 || 1.2.4", "1.2.3"),
+            ("2.x.x", "3.1.3"),
+            ("1.2.x", "1.3.3"),
+            ("2.*.*", "1.1.3"),
+            ("~2.4", "2.5.0"),
+            ("~>3.2.1", "3.3.2"),
+            ("~1", "2.2.3"),
+            ("<1", "1.0.0"),
+            (">=1.2", "1.1.1"),
+            ("~v0.5.4-beta", "0.5.4-alpha"),
+            ("=0.7.x", "0.8.2"),
+            ("<0.7.x", "0.7.2"),
+            ("^0.0.1", "0.0.2"),
+            ("^1.2.3", "2.0.0-alpha"),
+            ("^1.2.3", "1.2.2"),
+            ("^1.2", "1.1.9"),
+            ("*", "1.2.3-foo"),
+            ("^1.0.0", "2.0.0-rc1"),
+            (">1.2.3 <*", "1.3.0"),
+        ];
+        for (range, v) in excluded {
+            let range: Range = range.parse().unwrap();
+            assert!(!range.matches(&v.parse::<Version>().unwrap()), "{range} 不应当匹配 {v}");
+        }
+    }
+
+    /// 测试 includePrerelease 选项下的匹配
+    #[test]
+    fn test_include_prerelease() {
+        let options = Options { include_prerelease: true };
+        let included = [("*", "1.0.0-rc1"), ("^1.0.0", "1.0.1-rc1"), (">=1.2.3-beta.2", "1.5.0-alpha"), ("1.x", "1.0.0-0")];
+        for (range, v) in included {
+            let range = Range::parse_with(range, options).unwrap();
+            assert!(range.matches(&v.parse::<Version>().unwrap()), "{range} 应当匹配 {v}");
+        }
+        let range = Range::parse_with("^1.0.0", options).unwrap();
+        assert!(!range.matches(&"2.0.0-rc1".parse::<Version>().unwrap()));
+        assert!(!range.matches(&"1.0.0-rc1".parse::<Version>().unwrap()));
+    }
+
+    /// 测试非法的范围
+    #[test]
+    fn test_invalid() {
+        for range in [">=", "1.2.3.4", "blerg", "1.2.3-", ">=01.0.0", "~1.2-beta", "1.2.3 - "] {
+            assert!(range.parse::<Range>().is_err(), "{range}");
+        }
+        assert!(matches!("1.y".parse::<Range>(), Err(ParseError::Version(_))));
+    }
+}
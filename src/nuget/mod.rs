@@ -0,0 +1,352 @@
+//! NuGet 使用的版本号, 规则与 `NuGet.Versioning.NuGetVersion` 一致
+//!
+//! 在 SemVer 2 的基础上允许第 4 个部分 Revision, 也允许只写 1 到 2 个部分。
+//! 先行版本号比较时不区分大小写, 比较与判等都忽略元数据。
+//! [`VersionRange`] 支持 `[1.0, 2.0)` 这样的区间写法与 `1.*`、`1.0.0-*` 这样的浮动版本
+//!
+//! ```
+//! use version::nuget::Version;
+//!
+//! let v: Version = "01.2.0.0-Beta.1+sha.5".parse().unwrap();
+//! assert_eq!(v.to_string(), "1.2.0-Beta.1+sha.5");
+//! assert_eq!(v.to_normalized_string(), "1.2.0-Beta.1");
+//! assert_eq!(v, "1.2-beta.1".parse().unwrap());
+//! ```
+
+mod range;
+
+pub use range::{FloatBehavior, FloatRange, VersionRange};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::num::IntErrorKind;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 NuGet 版本号
+#[derive(Debug, Clone)]
+pub struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+    revision: u64,
+    release_labels: Vec<String>,
+    metadata: Option<String>,
+}
+
+///
+/// 解析 NuGet 版本号或版本范围时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("版本号有 {0} 个数字部分, 最多只能有 4 个")]
+    TooManyComponents(usize),
+
+    #[error("非法的数字部分 \"{0}\"")]
+    InvalidNumber(String),
+
+    #[error("第 {0} 个数字部分超过了最大值 {max}", max = u64::MAX)]
+    Overflow(usize),
+
+    #[error("先行版本号或元数据中存在空的部分")]
+    EmptyLabel,
+
+    #[error("先行版本号或元数据中存在非法字符 '{0}'")]
+    InvalidCharacter(char),
+
+    #[error("非法的浮动版本 \"{0}\"")]
+    InvalidFloat(String),
+
+    #[error("非法的版本范围 \"{0}\"")]
+    InvalidRange(String),
+
+    #[error("版本范围 \"{0}\" 的下界大于上界")]
+    MinGreaterThanMax(String),
+}
+
+impl Version {
+
+    /// 通过 4 个数字部分构建版本号
+    pub fn new(major: u64, minor: u64, patch: u64, revision: u64) -> Version {
+        Version { major, minor, patch, revision, release_labels: Vec::new(), metadata: None }
+    }
+
+    /// 主版本号
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// 次版本号
+    pub fn minor(&self) -> u64 {
+        self.minor
+    }
+
+    /// 修订号
+    pub fn patch(&self) -> u64 {
+        self.patch
+    }
+
+    /// 第 4 个部分, 未写出时为 0
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// 先行版本号的各个部分
+    pub fn release_labels(&self) -> &[String] {
+        &self.release_labels
+    }
+
+    /// 以 `.` 连接的先行版本号, 没有时为空字符串
+    pub fn release(&self) -> String {
+        self.release_labels.join(".")
+    }
+
+    /// 元数据
+    pub fn metadata(&self) -> Option<&str> {
+        self.metadata.as_deref()
+    }
+
+    /// 是否为先行版本
+    pub fn is_prerelease(&self) -> bool {
+        !self.release_labels.is_empty()
+    }
+
+    /// 是否使用了第 4 个部分, 对应 `IsLegacyVersion`
+    pub fn is_legacy(&self) -> bool {
+        self.revision != 0
+    }
+
+    /// 是否需要 SemVer 2 才能表示, 即先行版本号包含多个部分或带有元数据
+    pub fn is_semver2(&self) -> bool {
+        self.release_labels.len() > 1 || self.metadata.is_some()
+    }
+
+    /// 不含元数据的规范形式, 对应 `ToNormalizedString`
+    ///
+    /// 数字部分去掉前导零, Revision 为 0 时省略, 只有 1 到 2 个部分时补齐为 3 个
+    pub fn to_normalized_string(&self) -> String {
+        let mut s = format!("{}.{}.{}", self.major, self.minor, self.patch);
+        if self.revision != 0 {
+            s.push_str(&format!(".{}", self.revision));
+        }
+        if self.is_prerelease() {
+            s.push('-');
+            s.push_str(&self.release());
+        }
+        s
+    }
+
+    /// 四个数字部分
+    fn numbers(&self) -> [u64; 4] {
+        [self.major, self.minor, self.patch, self.revision]
+    }
+}
+
+/// 比较先行版本号的一个部分: 都是数字时按数值比较, 数字低于非数字, 非数字之间不区分大小写
+fn cmp_label(a: &str, b: &str) -> Ordering {
+    let numeric = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    match (numeric(a), numeric(b)) {
+        (true, true) => {
+            let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        }
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.bytes().map(|b| b.to_ascii_lowercase()).cmp(b.bytes().map(|b| b.to_ascii_lowercase())),
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 依次比较 4 个数字部分与先行版本号, 忽略元数据, 与 `VersionComparer.Default` 一致
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.numbers().cmp(&other.numbers()).then_with(|| {
+            match (self.is_prerelease(), other.is_prerelease()) {
+                (false, false) => Ordering::Equal,
+                (false, true) => Ordering::Greater,
+                (true, false) => Ordering::Less,
+                (true, true) => self.release_labels.iter().zip(&other.release_labels)
+                    .map(|(a, b)| cmp_label(a, b))
+                    .find(|o| o.is_ne())
+                    .unwrap_or_else(|| self.release_labels.len().cmp(&other.release_labels.len())),
+            }
+        })
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致, 忽略元数据、先行版本号的大小写与数字的前导零
+        self.numbers().hash(state);
+        for label in &self.release_labels {
+            if label.bytes().all(|b| b.is_ascii_digit()) {
+                label.trim_start_matches('0').hash(state);
+            } else {
+                label.to_ascii_lowercase().hash(state);
+            }
+        }
+    }
+}
+
+/// 输出带元数据的规范形式, 对应 `ToFullString`
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_normalized_string())?;
+        if let Some(metadata) = &self.metadata {
+            write!(f, "+{metadata}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 NuGet 版本号
+///
+/// 数字部分有 1 到 4 个, 允许前导零; 先行版本号与元数据的每个部分都不能为空,
+/// 只能包含字母、数字与 `-`
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+        let (rest, metadata) = match s.split_once('+') {
+            Some((rest, metadata)) => (rest, Some(metadata)),
+            None => (s, None),
+        };
+        let (numbers, release) = match rest.split_once('-') {
+            Some((numbers, release)) => (numbers, Some(release)),
+            None => (rest, None),
+        };
+
+        let parts: Vec<&str> = numbers.split('.').collect();
+        if parts.len() > 4 {
+            return Err(ParseError::TooManyComponents(parts.len()))
+        }
+        let mut components = [0; 4];
+        for (i, part) in parts.iter().enumerate() {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(ParseError::InvalidNumber(part.to_string()))
+            }
+            components[i] = part.parse().map_err(|e: std::num::ParseIntError| match e.kind() {
+                IntErrorKind::PosOverflow => ParseError::Overflow(i + 1),
+                _ => ParseError::InvalidNumber(part.to_string()),
+            })?;
+        }
+
+        let labels = |s: &str| -> Result<Vec<String>, ParseError> {
+            s.split('.').map(|label| {
+                if label.is_empty() {
+                    return Err(ParseError::EmptyLabel)
+                }
+                if let Some(c) = label.chars().find(|c| !c.is_ascii_alphanumeric() && *c != '-') {
+                    return Err(ParseError::InvalidCharacter(c))
+                }
+                Ok(label.to_string())
+            }).collect()
+        };
+        let release_labels = release.map(labels).transpose()?.unwrap_or_default();
+        if let Some(metadata) = metadata {
+            labels(metadata)?;
+        }
+
+        let [major, minor, patch, revision] = components;
+        Ok(Version { major, minor, patch, revision, release_labels, metadata: metadata.map(str::to_string) })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Version};
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 用例参考 NuGet.Versioning 的 VersionComparerTests
+    #[test]
+    fn test_compare() {
+        let ordered = [
+            "0.9", "1.0.0-0", "1.0.0-2", "1.0.0-10", "1.0.0-alpha", "1.0.0-ALPHA.1", "1.0.0-alpha.beta",
+            "1.0.0-beta", "1.0.0-beta.2", "1.0.0-beta.11", "1.0.0-rc.1", "1.0.0", "1.0.0.1", "1.0.1", "1.10",
+        ];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+
+        let equal = [("1.0", "1.0.0.0"), ("1.0.0-BETA", "1.0.0-beta"), ("1.0.0+a", "1.0.0+b"), ("01.002.0", "1.2")];
+        for (a, b) in equal {
+            assert_eq!(v(a), v(b), "{a} == {b}");
+        }
+        let set: HashSet<Version> = ["1.0.0-Beta.01", "1.0-beta.1", "1.0.0.0-BETA.1+meta"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("1", "1.0.0"),
+            ("1.0", "1.0.0"),
+            ("1.0.0.0", "1.0.0"),
+            ("1.0.0.4", "1.0.0.4"),
+            ("01.02.003.0", "1.2.3"),
+            ("1.0.0-Beta.1+Meta.2", "1.0.0-Beta.1+Meta.2"),
+            ("1.2.3.0-rc+build", "1.2.3-rc+build"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(v(input).to_string(), expected, "{input}");
+        }
+        assert_eq!(v("1.0.0.0-rc+build").to_normalized_string(), "1.0.0-rc");
+        assert!(v("1.0.0.1").is_legacy());
+        assert!(v("1.0.0-rc.1").is_semver2());
+        assert!(v("1.0.0+sha").is_semver2());
+        assert!(!v("1.0.0-rc1").is_semver2());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            ("1.2.3.4.5", ParseError::TooManyComponents(5)),
+            ("1..2", ParseError::InvalidNumber(String::new())),
+            ("1.a", ParseError::InvalidNumber("a".to_string())),
+            ("-beta", ParseError::InvalidNumber(String::new())),
+            ("1.18446744073709551616", ParseError::Overflow(2)),
+            ("1.0.0-", ParseError::EmptyLabel),
+            ("1.0.0-beta..1", ParseError::EmptyLabel),
+            ("1.0.0+", ParseError::EmptyLabel),
+            ("1.0.0-beta_1", ParseError::InvalidCharacter('_')),
+            ("1.0.0+meta$", ParseError::InvalidCharacter('$')),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+    }
+}
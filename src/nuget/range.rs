@@ -0,0 +1,448 @@
+//! NuGet 版本范围, 例如 `1.0`(最低版本)、`[1.0, 2.0)`、`(, 1.0]`、`[1.0]` 以及浮动版本 `1.*`、`[1.0.0-beta*, 2.0)`;
+//! `[`/`]` 包含边界, `(`/`)` 不包含边界
+
+use super::{ParseError, Version};
+use std::fmt;
+use std::str::FromStr;
+
+///
+/// NuGet 的版本范围, 规则与 `NuGet.Versioning.VersionRange` 一致
+///
+/// 支持以下写法:
+/// - `1.0`: 最低版本, 等同于 `[1.0, )`
+/// - `[1.0, 2.0)`: 区间, `[`/`]` 包含边界, `(`/`)` 不包含边界, 边界可以省略
+/// - `[1.0]`: 只允许 1.0
+/// - `1.*`、`1.0.0-*`、`[1.*, 2.0)`: 下界为浮动版本, 见 [`FloatRange`]
+///
+/// ```
+/// use version::nuget::{Version, VersionRange};
+///
+/// let v = |s: &str| s.parse::<Version>().unwrap();
+/// let range: VersionRange = "[1.0, 2.0)".parse().unwrap();
+/// assert!(range.contains(&v("1.5")));
+/// assert!(!range.contains(&v("2.0")));
+///
+/// let floating: VersionRange = "1.*".parse().unwrap();
+/// let available = [v("0.9"), v("1.0"), v("1.4.2"), v("2.0")];
+/// assert_eq!(floating.find_best_match(&available), Some(&v("1.4.2")));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionRange {
+    min: Option<Version>,
+    min_inclusive: bool,
+    max: Option<Version>,
+    max_inclusive: bool,
+    float: Option<FloatRange>,
+}
+
+///
+/// 浮动版本, 例如 `1.*`、`1.0.0-beta*`, 还原时选择匹配的最高版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FloatRange {
+    behavior: FloatBehavior,
+    min: Version,
+    release_prefix: String,
+}
+
+///
+/// 浮动版本中 `*` 所在的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FloatBehavior {
+    /// `1.0.0-beta*`, 只浮动先行版本号
+    Prerelease,
+    /// `1.0.0.*`
+    Revision,
+    /// `1.0.*`
+    Patch,
+    /// `1.*`
+    Minor,
+    /// `*`
+    Major,
+    /// `*-*`, 任何版本, 包括先行版本
+    AbsoluteLatest,
+    /// `1.0.0.*-*`
+    PrereleaseRevision,
+    /// `1.0.*-*`
+    PrereleasePatch,
+    /// `1.*-*`
+    PrereleaseMinor,
+    /// `*-rc*` 这样数字部分只有 `*` 且带有先行版本号前缀的写法
+    PrereleaseMajor,
+}
+
+impl VersionRange {
+
+    /// 下界, 浮动版本时为浮动版本的最低版本
+    pub fn min_version(&self) -> Option<&Version> {
+        self.min.as_ref()
+    }
+
+    /// 是否包含下界
+    pub fn is_min_inclusive(&self) -> bool {
+        self.min_inclusive
+    }
+
+    /// 上界
+    pub fn max_version(&self) -> Option<&Version> {
+        self.max.as_ref()
+    }
+
+    /// 是否包含上界
+    pub fn is_max_inclusive(&self) -> bool {
+        self.max_inclusive
+    }
+
+    /// 下界使用的浮动版本
+    pub fn float_range(&self) -> Option<&FloatRange> {
+        self.float.as_ref()
+    }
+
+    /// 判断版本号是否落在上下界之间, 与 `VersionRange.Satisfies` 一致
+    pub fn contains(&self, version: &Version) -> bool {
+        let above_min = match &self.min {
+            Some(min) if self.min_inclusive => version >= min,
+            Some(min) => version > min,
+            None => true,
+        };
+        let below_max = match &self.max {
+            Some(max) if self.max_inclusive => version <= max,
+            Some(max) => version < max,
+            None => true,
+        };
+        above_min && below_max
+    }
+
+    /// 是否接受先行版本, 即边界或浮动版本本身涉及先行版本
+    pub fn allows_prerelease(&self) -> bool {
+        self.min.as_ref().is_some_and(Version::is_prerelease)
+            || self.max.as_ref().is_some_and(Version::is_prerelease)
+            || self.float.as_ref().is_some_and(FloatRange::includes_prerelease)
+    }
+
+    /// 从候选版本中选出还原时使用的版本
+    ///
+    /// 只考虑落在范围内的版本, 范围不涉及先行版本时忽略先行版本。
+    /// 没有浮动版本时选择最低的版本; 有浮动版本时选择匹配浮动版本的最高版本,
+    /// 没有匹配的版本时退回到最低的版本
+    pub fn find_best_match<'a, I>(&self, versions: I) -> Option<&'a Version>
+    where
+        I: IntoIterator<Item = &'a Version>,
+    {
+        let allows_prerelease = self.allows_prerelease();
+        let candidates: Vec<&Version> = versions
+            .into_iter()
+            .filter(|v| self.contains(v) && (allows_prerelease || !v.is_prerelease()))
+            .collect();
+        self.float
+            .as_ref()
+            .and_then(|float| candidates.iter().copied().filter(|v| float.matches(v)).max())
+            .or_else(|| candidates.iter().copied().min())
+    }
+}
+
+impl FloatRange {
+
+    /// 浮动方式
+    pub fn behavior(&self) -> FloatBehavior {
+        self.behavior
+    }
+
+    /// 浮动版本能匹配到的最低版本, `*` 替换为 0
+    pub fn min_version(&self) -> &Version {
+        &self.min
+    }
+
+    /// `*` 之前的先行版本号前缀, 例如 `1.0.0-beta*` 的 `beta`
+    pub fn release_prefix(&self) -> &str {
+        &self.release_prefix
+    }
+
+    /// 是否会匹配先行版本
+    pub fn includes_prerelease(&self) -> bool {
+        !matches!(self.behavior, FloatBehavior::Revision | FloatBehavior::Patch | FloatBehavior::Minor | FloatBehavior::Major)
+    }
+
+    /// 判断版本号是否匹配浮动版本, 与 `FloatRange.Satisfies` 一致
+    ///
+    /// 数字部分中 `*` 之前的部分必须相同; 浮动先行版本号时先行版本号必须以前缀开头 (不区分大小写),
+    /// 否则不能是先行版本
+    pub fn matches(&self, version: &Version) -> bool {
+        let fixed = match self.behavior {
+            FloatBehavior::Prerelease => 4,
+            FloatBehavior::Revision | FloatBehavior::PrereleaseRevision => 3,
+            FloatBehavior::Patch | FloatBehavior::PrereleasePatch => 2,
+            FloatBehavior::Minor | FloatBehavior::PrereleaseMinor => 1,
+            FloatBehavior::Major | FloatBehavior::PrereleaseMajor | FloatBehavior::AbsoluteLatest => 0,
+        };
+        let release_matches = if self.includes_prerelease() {
+            version.release().to_ascii_lowercase().starts_with(&self.release_prefix.to_ascii_lowercase())
+        } else {
+            !version.is_prerelease()
+        };
+        *version >= self.min && version.numbers()[..fixed] == self.min.numbers()[..fixed] && release_matches
+    }
+
+    /// 数字部分的写法, 例如 `1.*`、`1.0.0`
+    fn numbers_str(&self) -> String {
+        let fixed = match self.behavior {
+            FloatBehavior::Prerelease => {
+                let mut min = self.min.clone();
+                min.release_labels.clear();
+                return min.to_normalized_string()
+            }
+            FloatBehavior::Revision | FloatBehavior::PrereleaseRevision => 3,
+            FloatBehavior::Patch | FloatBehavior::PrereleasePatch => 2,
+            FloatBehavior::Minor | FloatBehavior::PrereleaseMinor => 1,
+            FloatBehavior::Major | FloatBehavior::PrereleaseMajor | FloatBehavior::AbsoluteLatest => 0,
+        };
+        let mut parts: Vec<String> = self.min.numbers()[..fixed].iter().map(ToString::to_string).collect();
+        parts.push("*".to_string());
+        parts.join(".")
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let min = match (&self.float, &self.min) {
+            (Some(float), _) => float.to_string(),
+            (None, Some(min)) => min.to_normalized_string(),
+            (None, None) => String::new(),
+        };
+        let max = self.max.as_ref().map(Version::to_normalized_string).unwrap_or_default();
+        if self.min.is_some() && self.min == self.max && self.min_inclusive && self.max_inclusive && self.float.is_none() {
+            return write!(f, "[{min}]")
+        }
+        write!(
+            f,
+            "{}{min}, {max}{}",
+            if self.min_inclusive { '[' } else { '(' },
+            if self.max_inclusive { ']' } else { ')' },
+        )
+    }
+}
+
+impl fmt::Display for FloatRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.numbers_str())?;
+        if self.includes_prerelease() {
+            write!(f, "-{}*", self.release_prefix)?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析版本范围, 边界两侧允许存在空白; 浮动版本只能作为下界
+impl FromStr for VersionRange {
+    type Err = ParseError;
+
+    fn from_str(spec: &str) -> Result<VersionRange, ParseError> {
+        let invalid = || ParseError::InvalidRange(spec.to_string());
+        let s = spec.trim();
+        if s.is_empty() {
+            return Err(invalid())
+        }
+
+        let min_inclusive = match s.as_bytes()[0] {
+            b'[' => true,
+            b'(' => false,
+            _ => {
+                let (min, float) = parse_bound(s)?;
+                return Ok(VersionRange { min: Some(min), min_inclusive: true, max: None, max_inclusive: false, float })
+            }
+        };
+        let max_inclusive = match s.as_bytes()[s.len() - 1] {
+            b']' if s.len() > 1 => true,
+            b')' if s.len() > 1 => false,
+            _ => return Err(invalid()),
+        };
+        let inner = &s[1..s.len() - 1];
+
+        let parts: Vec<&str> = inner.split(',').map(str::trim).collect();
+        match *parts.as_slice() {
+            [exact] => {
+                if exact.is_empty() || !min_inclusive || !max_inclusive || exact.contains('*') {
+                    return Err(invalid())
+                }
+                let version: Version = exact.parse()?;
+                Ok(VersionRange { min: Some(version.clone()), min_inclusive, max: Some(version), max_inclusive, float: None })
+            }
+            [min, max] => {
+                let (min, float) = if min.is_empty() {
+                    (None, None)
+                } else {
+                    let (min, float) = parse_bound(min)?;
+                    (Some(min), float)
+                };
+                if max.contains('*') {
+                    return Err(invalid())
+                }
+                let max = if max.is_empty() { None } else { Some(max.parse::<Version>()?) };
+                if let (Some(min), Some(max)) = (&min, &max)
+                    && min > max
+                {
+                    return Err(ParseError::MinGreaterThanMax(spec.to_string()))
+                }
+                Ok(VersionRange { min, min_inclusive, max, max_inclusive, float })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// 解析作为下界的版本号或浮动版本
+fn parse_bound(s: &str) -> Result<(Version, Option<FloatRange>), ParseError> {
+    if s.contains('*') {
+        let float: FloatRange = s.parse()?;
+        Ok((float.min.clone(), Some(float)))
+    } else {
+        Ok((s.parse()?, None))
+    }
+}
+
+/// 解析浮动版本, 数字部分的 `*` 只能是最后一个部分, 先行版本号的 `*` 只能在末尾
+///
+/// 数字部分浮动时, 先行版本号要么不写, 要么也是浮动的
+impl FromStr for FloatRange {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<FloatRange, ParseError> {
+        let invalid = || ParseError::InvalidFloat(s.to_string());
+        let (numbers, release) = match s.split_once('-') {
+            Some((numbers, release)) => (numbers, Some(release)),
+            None => (s, None),
+        };
+
+        let release_prefix = match release {
+            Some(release) => match release.strip_suffix('*') {
+                Some(prefix) if !prefix.contains('*') => Some(prefix),
+                _ => return Err(invalid()),
+            },
+            None => None,
+        };
+
+        let fixed = match numbers.strip_suffix('*') {
+            Some("") => Some(0),
+            Some(head) => match head.strip_suffix('.') {
+                Some(head) if !head.contains('*') => Some(head.split('.').count()),
+                _ => return Err(invalid()),
+            },
+            None if numbers.contains('*') => return Err(invalid()),
+            None => None,
+        };
+
+        let behavior = match (fixed, release_prefix) {
+            (None, None) => return Err(invalid()),
+            (None, Some(_)) => FloatBehavior::Prerelease,
+            (Some(0), None) => FloatBehavior::Major,
+            (Some(1), None) => FloatBehavior::Minor,
+            (Some(2), None) => FloatBehavior::Patch,
+            (Some(3), None) => FloatBehavior::Revision,
+            (Some(0), Some("")) => FloatBehavior::AbsoluteLatest,
+            (Some(0), Some(_)) => FloatBehavior::PrereleaseMajor,
+            (Some(1), Some(_)) => FloatBehavior::PrereleaseMinor,
+            (Some(2), Some(_)) => FloatBehavior::PrereleasePatch,
+            (Some(3), Some(_)) => FloatBehavior::PrereleaseRevision,
+            (Some(_), _) => return Err(invalid()),
+        };
+
+        // `*` 替换为 0 得到最低版本; 前缀为空或以 `.` 结尾时补一个 0 作为先行版本号
+        let mut min = match numbers.strip_suffix('*') {
+            Some("") => "0".to_string(),
+            Some(head) => format!("{head}0"),
+            None => numbers.to_string(),
+        };
+        if let Some(prefix) = release_prefix {
+            min.push('-');
+            min.push_str(prefix);
+            if prefix.is_empty() || prefix.ends_with('.') {
+                min.push('0');
+            }
+        }
+        let min: Version = min.parse().map_err(|_| invalid())?;
+        if min.metadata.is_some() {
+            return Err(invalid())
+        }
+
+        Ok(FloatRange { behavior, min, release_prefix: release_prefix.unwrap_or_default().to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FloatBehavior, FloatRange, VersionRange};
+    use crate::nuget::{ParseError, Version};
+
+    fn range(s: &str) -> VersionRange {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_contains() {
+        let cases = [
+            ("1.0", "[1.0.0, )", &["1.0", "1.0.0.0", "5.0"][..], &["0.9", "1.0.0-beta"][..]),
+            ("[1.0, 2.0)", "[1.0.0, 2.0.0)", &["1.0", "1.9.9.9"], &["2.0", "0.9"]),
+            ("( 1.0 , 2.0 ]", "(1.0.0, 2.0.0]", &["1.0.1", "2.0"], &["1.0", "2.0.1"]),
+            ("[1.0]", "[1.0.0]", &["1.0.0.0"], &["1.0.1"]),
+            ("(,2.0]", "(, 2.0.0]", &["0.1", "2.0"], &["2.0.1"]),
+            ("(1.0,)", "(1.0.0, )", &["1.0.1"], &["1.0"]),
+            ("(,)", "(, )", &["0.0.1", "99.0"], &[]),
+            ("1.*", "[1.*, )", &["1.0", "2.0"], &["0.9"]),
+            ("[1.0.0-*, 2.0)", "[1.0.0-*, 2.0.0)", &["1.0.0-alpha", "1.5"], &["2.0"]),
+        ];
+        for (input, normalized, included, excluded) in cases {
+            let r = range(input);
+            assert_eq!(r.to_string(), normalized, "{input}");
+            for version in included {
+                assert!(r.contains(&v(version)), "{input} 应当包含 {version}");
+            }
+            for version in excluded {
+                assert!(!r.contains(&v(version)), "{input} 不应包含 {version}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_float() {
+        let cases = [
+            ("*", FloatBehavior::Major, "0.0.0"),
+            ("1.*", FloatBehavior::Minor, "1.0.0"),
+            ("1.2.*", FloatBehavior::Patch, "1.2.0"),
+            ("1.2.3.*", FloatBehavior::Revision, "1.2.3"),
+            ("1.0.0-*", FloatBehavior::Prerelease, "1.0.0-0"),
+            ("1.0.0-beta.*", FloatBehavior::Prerelease, "1.0.0-beta.0"),
+            ("1.0.0-beta*", FloatBehavior::Prerelease, "1.0.0-beta"),
+            ("1.*-*", FloatBehavior::PrereleaseMinor, "1.0.0-0"),
+            ("*-*", FloatBehavior::AbsoluteLatest, "0.0.0-0"),
+        ];
+        for (input, behavior, min) in cases {
+            let float: FloatRange = input.parse().unwrap();
+            assert_eq!(float.behavior(), behavior, "{input}");
+            assert_eq!(float.min_version().to_string(), min, "{input}");
+            assert_eq!(float.to_string(), input);
+        }
+
+        let available = ["1.0.0", "1.0.1-beta.1", "1.0.1-beta.2", "1.0.1-rc.1", "1.2.0", "1.3.0-alpha", "2.0.0"].map(v);
+        let best = |r: &str| range(r).find_best_match(&available).map(ToString::to_string);
+        assert_eq!(best("1.0"), Some("1.0.0".to_string()));
+        assert_eq!(best("1.*"), Some("1.2.0".to_string()));
+        assert_eq!(best("1.0.*"), Some("1.0.0".to_string()));
+        assert_eq!(best("1.0.1-beta*"), Some("1.0.1-beta.2".to_string()));
+        assert_eq!(best("1.*-*"), Some("1.3.0-alpha".to_string()));
+        assert_eq!(best("3.*"), None);
+        assert_eq!(best("[1.5, 3.0)"), Some("2.0.0".to_string()));
+        assert_eq!(best("[1.1.*, 3.0)"), Some("1.2.0".to_string()));
+    }
+
+    #[test]
+    fn test_invalid() {
+        for input in ["", "[1.0", "1.0]", "(1.0)", "[]", "[1.0, 2.0, 3.0]", "[1.*]", "[1.0, 2.*)", "1.*.0", "1.*-beta", "1.0.0-*beta", "**", "1.0.0-beta*+meta"] {
+            assert!(input.parse::<VersionRange>().is_err(), "{input}");
+        }
+        assert_eq!("[2.0, 1.0]".parse::<VersionRange>(), Err(ParseError::MinGreaterThanMax("[2.0, 1.0]".to_string())));
+        assert_eq!("1.*-beta".parse::<FloatRange>(), Err(ParseError::InvalidFloat("1.*-beta".to_string())));
+    }
+}
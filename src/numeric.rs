@@ -0,0 +1,299 @@
+//! 任意长度的纯数字版本号, 例如 Windows 文件版本 `10.0.19041.1`、Chrome 的 `130.0.6723.91`
+//!
+//! 版本号逐个部分按数值比较。默认情况下末尾的零不影响比较, 因此 `1.2 == 1.2.0.0`;
+//! 需要区分时使用 [`Version::cmp_with`] 并传入 [`Padding::Significant`]
+//!
+//! ```
+//! use version::numeric::{Padding, Version};
+//!
+//! let chrome: Version = "130.0.6723.91".parse().unwrap();
+//! assert!(chrome > "130.0.6723.58".parse().unwrap());
+//!
+//! let short: Version = "1.2".parse().unwrap();
+//! let long: Version = "1.2.0.0".parse().unwrap();
+//! assert_eq!(short, long);
+//! assert!(short.cmp_with(&long, Padding::Significant).is_lt());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::num::IntErrorKind;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个由任意多个数字部分组成的版本号
+#[derive(Debug, Clone)]
+pub struct Version {
+    components: Vec<u64>,
+}
+
+///
+/// 比较时如何对待长度不同的版本号
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Padding {
+    /// 较短的一方在末尾补零, `1.2 == 1.2.0.0`
+    #[default]
+    Zero,
+    /// 补零后仍相等时, 较长的一方更大, `1.2 < 1.2.0.0`
+    Significant,
+}
+
+///
+/// 解析数字版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("第 {0} 个部分为空")]
+    EmptyComponent(usize),
+
+    #[error("版本号中存在非法字符 '{0}'")]
+    InvalidCharacter(char),
+
+    #[error("\"{0}\" 不能以 0 开头")]
+    LeadingZero(String),
+
+    #[error("第 {0} 个部分超过了最大值 {max}", max = u64::MAX)]
+    Overflow(usize),
+}
+
+///
+/// 与 [`crate::Version`] 相互转换时, 无法无损表示的情况
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+
+    #[error("数字版本号不支持先行版本号 \"{0}\"")]
+    Prerelease(String),
+
+    #[error("数字版本号不支持编译元数据 \"{0}\"")]
+    BuildMetadata(String),
+
+    #[error("版本号有 {0} 个部分, SemVer 版本号最多只有 3 个")]
+    TooManyComponents(usize),
+}
+
+impl Version {
+
+    /// 通过各个部分构建版本号
+    ///
+    /// # Panics
+    /// `components` 为空时 panic
+    ///
+    /// # 示例
+    /// ```
+    /// use version::numeric::Version;
+    ///
+    /// assert_eq!(Version::new([10, 0, 19041, 1]).to_string(), "10.0.19041.1");
+    /// ```
+    pub fn new(components: impl Into<Vec<u64>>) -> Version {
+        let components = components.into();
+        assert!(!components.is_empty(), "版本号至少需要一个部分");
+        Version { components }
+    }
+
+    /// 所有部分
+    pub fn components(&self) -> &[u64] {
+        &self.components
+    }
+
+    /// 按照指定的补零方式比较两个版本号
+    pub fn cmp_with(&self, other: &Version, padding: Padding) -> Ordering {
+        let len = self.components.len().max(other.components.len());
+        let padded = (0..len)
+            .map(|i| {
+                let a = self.components.get(i).copied().unwrap_or(0);
+                let b = other.components.get(i).copied().unwrap_or(0);
+                a.cmp(&b)
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal);
+        match padding {
+            Padding::Zero => padded,
+            Padding::Significant => padded.then_with(|| self.components.len().cmp(&other.components.len())),
+        }
+    }
+
+    /// 按照指定的补零方式判断两个版本号是否相等
+    pub fn eq_with(&self, other: &Version, padding: Padding) -> bool {
+        self.cmp_with(other, padding).is_eq()
+    }
+
+    /// 去掉末尾的零之后的部分, 至少保留一个
+    fn trimmed(&self) -> &[u64] {
+        let len = self.components.iter().rposition(|n| *n != 0).map_or(1, |i| i + 1);
+        &self.components[..len]
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.eq_with(other, Padding::Zero)
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.cmp_with(other, Padding::Zero)
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致, 忽略末尾的零
+        self.trimmed().hash(state);
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, n) in self.components.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{n}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析以 `.` 分隔的数字版本号, 每个部分都不能有前导零
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+        let components = s.split('.').enumerate().map(|(i, part)| {
+            let index = i + 1;
+            if part.is_empty() {
+                return Err(ParseError::EmptyComponent(index))
+            }
+            if let Some(c) = part.chars().find(|c| !c.is_ascii_digit()) {
+                return Err(ParseError::InvalidCharacter(c))
+            }
+            if part.len() > 1 && part.starts_with('0') {
+                return Err(ParseError::LeadingZero(part.to_string()))
+            }
+            part.parse::<u64>().map_err(|e| match e.kind() {
+                IntErrorKind::PosOverflow => ParseError::Overflow(index),
+                _ => ParseError::InvalidCharacter(part.chars().next().unwrap_or_default()),
+            })
+        }).collect::<Result<Vec<u64>, ParseError>>()?;
+        Ok(Version { components })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+/// SemVer 版本号只有不带先行版本号与编译元数据时才能转换
+impl TryFrom<&crate::Version> for Version {
+    type Error = ConversionError;
+
+    fn try_from(v: &crate::Version) -> Result<Version, ConversionError> {
+        if !v.pre.is_empty() {
+            let pre: Vec<String> = v.pre.iter().map(ToString::to_string).collect();
+            return Err(ConversionError::Prerelease(pre.join(".")))
+        }
+        if !v.build.is_empty() {
+            return Err(ConversionError::BuildMetadata(v.build.join(".")))
+        }
+        Ok(Version::new([v.major, v.minor, v.patch]))
+    }
+}
+
+/// 与 [`Padding::Zero`] 一致, 末尾的零不影响结果: 去掉末尾的零之后不足 3 个部分时补零,
+/// 仍然超过 3 个部分时转换失败, 例如 `1.2.3.0` 转换为 `1.2.3`, `1.2.3.4` 转换失败
+impl TryFrom<&Version> for crate::Version {
+    type Error = ConversionError;
+
+    fn try_from(v: &Version) -> Result<crate::Version, ConversionError> {
+        let significant = v.components.iter().rposition(|n| *n != 0).map_or(0, |i| i + 1);
+        match v.components[..significant.max(1)] {
+            [major] => Ok(crate::Version::new(major, 0, 0)),
+            [major, minor] => Ok(crate::Version::new(major, minor, 0)),
+            [major, minor, patch] => Ok(crate::Version::new(major, minor, patch)),
+            ref components => Err(ConversionError::TooManyComponents(components.len())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConversionError, Padding, ParseError, Version};
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 测试逐个部分比较
+    #[test]
+    fn test_compare() {
+        let ordered = ["0.9", "1", "1.0.0.1", "1.2", "1.2.3.4.5", "1.2.3.10", "1.10", "130.0.6723.58", "130.0.6723.91"];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+            assert!(v(pair[0]).cmp_with(&v(pair[1]), Padding::Significant).is_lt());
+        }
+
+        assert_eq!(v("1.2"), v("1.2.0.0"));
+        assert!(!v("1.2").eq_with(&v("1.2.0.0"), Padding::Significant));
+        assert!(v("1.2.0.0").cmp_with(&v("1.2"), Padding::Significant).is_gt());
+        assert!(v("1.2").eq_with(&v("1.2"), Padding::Significant));
+
+        let set: HashSet<Version> = ["1.2", "1.2.0", "1.2.0.0.0"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+        assert_eq!(v("10.0.19041.1").components(), [10, 0, 19041, 1]);
+        assert_eq!(v("1.2.0.0").to_string(), "1.2.0.0");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            ("1..2", ParseError::EmptyComponent(2)),
+            ("1.2.", ParseError::EmptyComponent(3)),
+            ("1.2a", ParseError::InvalidCharacter('a')),
+            ("1.-2", ParseError::InvalidCharacter('-')),
+            ("1.02", ParseError::LeadingZero("02".to_string())),
+            ("1.2.3.18446744073709551616", ParseError::Overflow(4)),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+    }
+
+    /// 测试与 SemVer 版本号的转换
+    #[test]
+    fn test_conversion() {
+        let semver = |s: &str| s.parse::<crate::Version>().unwrap();
+        assert_eq!(Version::try_from(&semver("1.2.3")), Ok(v("1.2.3")));
+        assert_eq!(Version::try_from(&semver("1.2.3-rc.1")), Err(ConversionError::Prerelease("rc.1".to_string())));
+        assert_eq!(Version::try_from(&semver("1.2.3+build.5")), Err(ConversionError::BuildMetadata("build.5".to_string())));
+
+        assert_eq!(crate::Version::try_from(&v("7")), Ok(semver("7.0.0")));
+        assert_eq!(crate::Version::try_from(&v("1.2")), Ok(semver("1.2.0")));
+        assert_eq!(crate::Version::try_from(&v("1.2.3")), Ok(semver("1.2.3")));
+        assert_eq!(crate::Version::try_from(&v("1.2.3.0")), Ok(semver("1.2.3")));
+        assert_eq!(crate::Version::try_from(&v("0.0.0.0")), Ok(semver("0.0.0")));
+        assert_eq!(crate::Version::try_from(&v("1.2.3.4.0")), Err(ConversionError::TooManyComponents(4)));
+    }
+}
@@ -0,0 +1,420 @@
+//! Arch Linux `pacman` 软件包版本号, 格式为 `[epoch:]pkgver[-pkgrel]`
+//!
+//! 比较规则与 `vercmp` (libalpm 的 `alpm_pkg_vercmp`) 一致:
+//! 字母与数字分段比较, 数字段总是比字母段新, 末尾多出的字母段比没有更旧(`1.0a` < `1.0`),
+//! 分隔符的个数也参与比较; [`vercmp`] 只有双方都写了发布号时才比较发布号,
+//! [`Version`] 为了保证全序, 将没有发布号的版本排在同一 `pkgver` 的任何发布号之前
+//!
+//! ```
+//! use version::pacman::Version;
+//!
+//! let v: Version = "1:2.0rc1-3".parse().unwrap();
+//! assert_eq!((v.epoch(), v.pkgver(), v.pkgrel()), (1, "2.0rc1", Some("3")));
+//! assert!(v < "1:2.0-1".parse().unwrap());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 pacman 版本号
+///
+/// pacman 认为未写发布号的版本与同一 `pkgver` 的任何发布号相等, 但这样的相等关系不满足传递性;
+/// 为了满足 `Ord` 的全序要求, 这里把未写发布号的版本排在任何发布号之前(`1.5` < `1.5-1` < `1.5-2`),
+/// 需要 pacman 原本的比较结果时使用 [`vercmp`]
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u32,
+    pkgver: String,
+    pkgrel: Option<String>,
+}
+
+///
+/// 解析 pacman 版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("纪元必须是不超过 {} 的数字", u32::MAX)]
+    InvalidEpoch,
+
+    #[error("pkgver 为空")]
+    PkgverEmpty,
+
+    #[error("pkgrel 为空")]
+    PkgrelEmpty,
+
+    #[error("版本号中存在非法字符 '{0}'")]
+    InvalidCharacter(char),
+}
+
+impl Version {
+
+    /// 纪元, 未指定时为 0
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// 上游版本 `pkgver`
+    pub fn pkgver(&self) -> &str {
+        &self.pkgver
+    }
+
+    /// 发布号 `pkgrel`
+    pub fn pkgrel(&self) -> Option<&str> {
+        self.pkgrel.as_deref()
+    }
+
+    /// 按照 [`vercmp`] 的规则比较, 只有双方都有发布号时才比较发布号
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use version::pacman::Version;
+    ///
+    /// let (a, b): (Version, Version) = ("1.5".parse().unwrap(), "1.5-1".parse().unwrap());
+    /// assert_eq!(a.vercmp(&b), Ordering::Equal);
+    /// assert!(a < b);
+    /// ```
+    pub fn vercmp(&self, other: &Version) -> Ordering {
+        self.epoch.cmp(&other.epoch)
+            .then_with(|| rpmvercmp(&self.pkgver, &other.pkgver))
+            .then_with(|| match (&self.pkgrel, &other.pkgrel) {
+                (Some(a), Some(b)) => rpmvercmp(a, b),
+                _ => Ordering::Equal,
+            })
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch.cmp(&other.epoch)
+            .then_with(|| rpmvercmp(&self.pkgver, &other.pkgver))
+            .then_with(|| match (&self.pkgrel, &other.pkgrel) {
+                (Some(a), Some(b)) => rpmvercmp(a, b),
+                (a, b) => a.is_some().cmp(&b.is_some()),
+            })
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致, 按照分段后的形式哈希, 忽略分隔符的写法与数字的前导零
+        self.epoch.hash(state);
+        segments(&self.pkgver).hash(state);
+        self.pkgrel.as_deref().map(segments).hash(state);
+    }
+}
+
+/// 以 `[epoch:]pkgver[-pkgrel]` 形式输出, 纪元为 0 时省略
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+        f.write_str(&self.pkgver)?;
+        if let Some(pkgrel) = &self.pkgrel {
+            write!(f, "-{pkgrel}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 pacman 版本号
+///
+/// 开头的数字后紧跟 `:` 时为纪元, 最后一个 `-` 之后为发布号;
+/// 与 makepkg 的要求一致, 各部分不能含有空白与 `:/-`
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+
+        let (epoch, rest) = match s.split_once(':') {
+            Some((epoch, rest)) => {
+                if epoch.is_empty() || !epoch.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseError::InvalidEpoch)
+                }
+                (epoch.parse().map_err(|_| ParseError::InvalidEpoch)?, rest)
+            }
+            None => (0, s),
+        };
+
+        let (pkgver, pkgrel) = match rest.rsplit_once('-') {
+            Some((_, "")) => return Err(ParseError::PkgrelEmpty),
+            Some((pkgver, pkgrel)) => (pkgver, Some(pkgrel)),
+            None => (rest, None),
+        };
+        if pkgver.is_empty() {
+            return Err(ParseError::PkgverEmpty)
+        }
+        if let Some(c) = pkgver.chars().chain(pkgrel.unwrap_or_default().chars()).find(|c| !c.is_ascii_graphic() || ":/-".contains(*c)) {
+            return Err(ParseError::InvalidCharacter(c))
+        }
+
+        Ok(Version {
+            epoch,
+            pkgver: pkgver.to_string(),
+            pkgrel: pkgrel.map(str::to_string),
+        })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+///
+/// pacman 的 `vercmp`, 比较两个完整的版本号字符串, 不做任何校验
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use version::pacman::vercmp;
+///
+/// assert_eq!(vercmp("1.0a", "1.0"), Ordering::Less);
+/// assert_eq!(vercmp("1:1.0", "2.0"), Ordering::Greater);
+/// assert_eq!(vercmp("1.5-1", "1.5"), Ordering::Equal);
+/// ```
+pub fn vercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal
+    }
+    let (epoch_a, ver_a, rel_a) = parse_evr(a);
+    let (epoch_b, ver_b, rel_b) = parse_evr(b);
+    rpmvercmp(epoch_a, epoch_b)
+        .then_with(|| rpmvercmp(ver_a, ver_b))
+        .then_with(|| match (rel_a, rel_b) {
+            (Some(a), Some(b)) => rpmvercmp(a, b),
+            _ => Ordering::Equal,
+        })
+}
+
+/// libalpm 的 `parseEVR`: 开头的数字后紧跟 `:` 时为纪元, 之后最后一个 `-` 之后为发布号
+fn parse_evr(s: &str) -> (&str, &str, Option<&str>) {
+    let digits = s.bytes().take_while(|b| b.is_ascii_digit()).count();
+    let (epoch, rest) = match s[digits..].strip_prefix(':') {
+        Some(rest) if digits > 0 => (&s[..digits], rest),
+        Some(rest) => ("0", rest),
+        None => ("0", s),
+    };
+    match rest.rsplit_once('-') {
+        Some((version, release)) => (epoch, version, Some(release)),
+        None => (epoch, rest, None),
+    }
+}
+
+/// libalpm 中的 `rpmvercmp`, 与 rpm 的同名函数不同, 没有 `~` 与 `^` 的特殊规则, 但分隔符的个数参与比较
+fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    // one/two 为当前位置, end_a/end_b 为上一段的结尾
+    let (mut one, mut two) = (0, 0);
+    let (mut end_a, mut end_b) = (0, 0);
+
+    while one < a.len() && two < b.len() {
+        while one < a.len() && !a[one].is_ascii_alphanumeric() {
+            one += 1;
+        }
+        while two < b.len() && !b[two].is_ascii_alphanumeric() {
+            two += 1;
+        }
+        if one >= a.len() || two >= b.len() {
+            break;
+        }
+        // 分隔符个数不同时, 分隔符少的一方更旧
+        if one - end_a != two - end_b {
+            return (one - end_a).cmp(&(two - end_b))
+        }
+
+        let is_num = a[one].is_ascii_digit();
+        let class = |c: &u8| if is_num { c.is_ascii_digit() } else { c.is_ascii_alphabetic() };
+        end_a = one + a[one..].iter().take_while(|c| class(c)).count();
+        end_b = two + b[two..].iter().take_while(|c| class(c)).count();
+
+        // 类型不同时数字段更新
+        if end_b == two {
+            return if is_num { Ordering::Greater } else { Ordering::Less }
+        }
+
+        let (mut seg_a, mut seg_b) = (&a[one..end_a], &b[two..end_b]);
+        if is_num {
+            seg_a = trim_zeros(seg_a);
+            seg_b = trim_zeros(seg_b);
+            if seg_a.len() != seg_b.len() {
+                return seg_a.len().cmp(&seg_b.len())
+            }
+        }
+        let ordering = seg_a.cmp(seg_b);
+        if ordering.is_ne() {
+            return ordering
+        }
+        one = end_a;
+        two = end_b;
+    }
+
+    // 所有段都相同时: 都已结束则相等; 剩余字母段的一方更旧, 否则剩余内容的一方更新
+    let (rest_a, rest_b) = (a.get(one), b.get(two));
+    match (rest_a, rest_b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(c)) if !c.is_ascii_alphabetic() => Ordering::Less,
+        (Some(c), _) if c.is_ascii_alphabetic() => Ordering::Less,
+        _ => Ordering::Greater,
+    }
+}
+
+fn trim_zeros(s: &[u8]) -> &[u8] {
+    let zeros = s.iter().take_while(|c| **c == b'0').count();
+    &s[zeros..]
+}
+
+/// 参与比较的分段, 用于生成与 `rpmvercmp` 相等关系一致的哈希
+#[derive(Hash)]
+enum Segment<'a> {
+    /// 分段之前的分隔符个数
+    Separators(usize),
+    Numeric(&'a [u8]),
+    Alpha(&'a [u8]),
+    /// 末尾还有分隔符
+    Trailing,
+}
+
+fn segments(s: &str) -> Vec<Segment<'_>> {
+    let s = s.as_bytes();
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < s.len() {
+        let separators = s[i..].iter().take_while(|c| !c.is_ascii_alphanumeric()).count();
+        i += separators;
+        if i >= s.len() {
+            segments.push(Segment::Trailing);
+            break;
+        }
+        segments.push(Segment::Separators(separators));
+        if s[i].is_ascii_digit() {
+            let len = s[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            segments.push(Segment::Numeric(trim_zeros(&s[i..i + len])));
+            i += len;
+        } else {
+            let len = s[i..].iter().take_while(|c| c.is_ascii_alphabetic()).count();
+            segments.push(Segment::Alpha(&s[i..i + len]));
+            i += len;
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{vercmp, ParseError, Version};
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 比较结果取自 pacman 的 test/util/vercmptest.sh
+    #[test]
+    fn test_vercmp_table() {
+        let cases = [
+            ("1.5.0", "1.5.0", 0), ("1.5.1", "1.5.0", 1), ("1.5.1", "1.5", 1),
+            ("1.5.0-1", "1.5.0-1", 0), ("1.5.0-1", "1.5.0-2", -1), ("1.5.0-1", "1.5.1-1", -1),
+            ("1.5.0-2", "1.5.1-1", -1), ("1.5-1", "1.5.1-1", -1), ("1.5-2", "1.5.1-1", -1),
+            ("1.5-2", "1.5.1-2", -1),
+            ("1.5", "1.5-1", 0), ("1.5-1", "1.5", 0), ("1.1-1", "1.1", 0),
+            ("1.0-1", "1.1", -1), ("1.1-1", "1.0", 1),
+            ("1.5b-1", "1.5-1", -1), ("1.5b", "1.5", -1), ("1.5b-1", "1.5", -1), ("1.5b", "1.5.1", -1),
+            ("1.0a", "1.0alpha", -1), ("1.0alpha", "1.0b", -1), ("1.0b", "1.0beta", -1),
+            ("1.0beta", "1.0rc", -1), ("1.0rc", "1.0", -1),
+            ("1.5.a", "1.5", 1), ("1.5.b", "1.5.a", 1), ("1.5.1", "1.5.b", 1),
+            ("1.5.b-1", "1.5.b", 0), ("1.5-1", "1.5.b", -1),
+            ("2.0", "2_0", 0), ("2.0_a", "2_0.a", 0), ("2.0a", "2.0.a", -1), ("2___a", "2_a", 1),
+            ("0:1.0", "0:1.0", 0), ("0:1.0", "0:1.1", -1), ("1:1.0", "0:1.0", 1),
+            ("1:1.0", "0:1.1", 1), ("1:1.0", "2:1.1", -1),
+            ("1:1.0", "0:1.0-1", 1), ("1:1.0-1", "0:1.1-1", 1),
+            ("0:1.0", "1.0", 0), ("0:1.0", "1.1", -1), ("0:1.1", "1.0", 1),
+            ("1:1.0", "1.0", 1), ("1:1.0", "1.1", 1), ("1:1.1", "1.1", 1),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(vercmp(a, b), expected.cmp(&0), "{a} <=> {b}");
+            assert_eq!(vercmp(b, a), 0.cmp(&expected), "{b} <=> {a}");
+            // 只有一方写了发布号时, Version 的顺序与 vercmp 不同
+            if v(a).pkgrel().is_some() == v(b).pkgrel().is_some() {
+                assert_eq!(v(a).cmp(&v(b)), expected.cmp(&0), "{a} <=> {b}");
+            }
+        }
+    }
+
+    /// 测试没有发布号的版本排在发布号之前, 保证全序
+    #[test]
+    fn test_total_order() {
+        assert!(v("1.5") < v("1.5-1") && v("1.5-1") < v("1.5-2"));
+        assert!(v("1.5-2") < v("1.5.1"));
+        assert_ne!(v("1.5"), v("1.5-1"));
+
+        let mut versions: Vec<Version> = ["1.5-2", "1.5", "1.5.1", "1.5-1", "1:0.1"].iter().map(|s| v(s)).collect();
+        versions.sort();
+        let sorted: Vec<String> = versions.iter().map(Version::to_string).collect();
+        assert_eq!(sorted, ["1.5", "1.5-1", "1.5-2", "1.5.1", "1:0.1"]);
+    }
+
+    /// 测试各个组成部分与哈希
+    #[test]
+    fn test_components() {
+        let version = v("2:1.0.r12.g3abc-1.1");
+        assert_eq!((version.epoch(), version.pkgver(), version.pkgrel()), (2, "1.0.r12.g3abc", Some("1.1")));
+        assert_eq!(version.to_string(), "2:1.0.r12.g3abc-1.1");
+        assert_eq!(v("0:1.0").to_string(), "1.0");
+
+        let set: HashSet<Version> = ["1.0-1", "1_0-1", "1.00-01", "0:01.0-1"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+        assert_ne!(v("1.0"), v("1.0."));
+        assert_eq!(v("1.0."), v("1.0.."));
+    }
+
+    /// 测试解析错误
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            (":1.0", ParseError::InvalidEpoch),
+            ("x:1.0", ParseError::InvalidEpoch),
+            ("99999999999:1.0", ParseError::InvalidEpoch),
+            ("1:", ParseError::PkgverEmpty),
+            ("-1", ParseError::PkgverEmpty),
+            ("1.0-", ParseError::PkgrelEmpty),
+            ("1.0 2", ParseError::InvalidCharacter(' ')),
+            ("1.0-1-2", ParseError::InvalidCharacter('-')),
+            ("1:1.0:2", ParseError::InvalidCharacter(':')),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+    }
+}
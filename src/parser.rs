@@ -0,0 +1,642 @@
+//! 可配置的版本号解析器: 严格模式与 `str::parse::<Version>()` 一致, 宽松模式修正 `v1.2`、`1_rc1`、
+//! `１．２．３`、`1.2.0-测试版` 等常见写法, 并记录做出的每一项修正
+
+use crate::{ParseError, Version};
+use std::fmt;
+use std::ops::Range;
+
+///
+/// 解析选项, 用于构建 [`Parser`]
+///
+/// [`ParseOptions::strict`] 与 `str::parse::<Version>()` 完全一致,
+/// [`ParseOptions::lenient`] 开启所有宽松规则, 也可以单独开启其中的某几项
+///
+/// ```
+/// use version::{Coercion, ParseOptions};
+///
+/// let parser = ParseOptions::strict().prefix(true).build();
+/// let parsed = parser.parse("v1.2.3").unwrap();
+/// assert_eq!(parsed.coercions(), [Coercion::Prefix('v')]);
+/// assert!(parser.parse(" v1.2.3").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptions {
+    full_width: bool,
+    whitespace: bool,
+    prefix: bool,
+    short: bool,
+    pre_separator: bool,
+    stage_words: StageWords,
+}
+
+///
+/// 按照 [`ParseOptions`] 解析版本号的解析器
+///
+/// ```
+/// use version::{Coercion, Parser};
+///
+/// let parsed = Parser::lenient().parse(" =v1_beta.2 ").unwrap();
+/// assert_eq!(parsed.version().to_string(), "1.0.0-beta.2");
+/// assert_eq!(parsed.coercions(), [
+///     Coercion::Whitespace,
+///     Coercion::Prefix('='),
+///     Coercion::Prefix('v'),
+///     Coercion::MissingMinor,
+///     Coercion::PreSeparator('_'),
+/// ]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Parser {
+    options: ParseOptions,
+}
+
+///
+/// 宽松解析时对输入做出的修正, 调用方可以据此给出警告
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Coercion {
+    /// 去掉了首尾的空白
+    Whitespace,
+    /// 去掉了前缀 `v`、`V` 或 `=`
+    Prefix(char),
+    /// 只有主版本号, 补全了副版本号
+    MissingMinor,
+    /// 先行版本号之前的分隔符是 `_` 或 `.`, 视为 `-`
+    PreSeparator(char),
+    /// 将全角字符转换为了半角字符
+    FullWidth,
+    /// 将先行版本号中表示发布阶段的词语替换为了标准的标识符
+    Stage {
+        /// 输入中的词语
+        word: String,
+        /// 对应的发布阶段
+        stage: Stage,
+    },
+}
+
+///
+/// 发布阶段, 按照从早到晚的顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Stage {
+    /// 替换为先行版本号 `alpha`
+    Alpha,
+    /// 替换为先行版本号 `beta`
+    Beta,
+    /// 替换为先行版本号 `rc`
+    Rc,
+    /// 正式版本, 去掉先行版本号
+    Release,
+}
+
+///
+/// 发布阶段词语表, 将先行版本号中的词语(例如 `测试版`)映射为 [`Stage`]
+///
+/// 先行版本号中的标识符与某个词语完全相同, 或者是词语后跟数字时才会替换,
+/// 例如 `测试版2` 替换为 `beta.2`; 正式版本的词语只能单独作为先行版本号出现
+///
+/// ```
+/// use version::{ParseOptions, Stage, StageWords};
+///
+/// let words = StageWords::chinese().insert("灰度版", Stage::Rc);
+/// let parser = ParseOptions::lenient().stage_words(words).build();
+/// let v = |s: &str| parser.parse(s).unwrap().into_version();
+/// assert_eq!(v("1.2.0-灰度版1").to_string(), "1.2.0-rc.1");
+/// assert!(v("1.2.0-内测版") < v("1.2.0-测试版"));
+/// assert!(v("1.2.0-测试版2") < v("1.2.0-正式版"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct StageWords {
+    words: Vec<(String, Stage)>,
+}
+
+///
+/// 解析结果, 包含版本号与解析时做出的修正
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Parsed {
+    version: Version,
+    coercions: Vec<Coercion>,
+}
+
+impl ParseOptions {
+
+    /// 严格模式, 不做任何修正
+    pub fn strict() -> ParseOptions {
+        ParseOptions {
+            full_width: false,
+            whitespace: false,
+            prefix: false,
+            short: false,
+            pre_separator: false,
+            stage_words: StageWords::new(),
+        }
+    }
+
+    /// 宽松模式, 开启所有修正, 并使用 [`StageWords::chinese`] 词语表
+    pub fn lenient() -> ParseOptions {
+        ParseOptions {
+            full_width: true,
+            whitespace: true,
+            prefix: true,
+            short: true,
+            pre_separator: true,
+            stage_words: StageWords::chinese(),
+        }
+    }
+
+    /// 是否将全角字符转换为半角字符, 例如输入法输入的 `１．２．３`、`－`,
+    /// 句号 `。` 与全角空格也分别视为 `.` 与空格
+    pub fn full_width(mut self, enabled: bool) -> ParseOptions {
+        self.full_width = enabled;
+        self
+    }
+
+    /// 是否去掉首尾的空白
+    pub fn whitespace(mut self, enabled: bool) -> ParseOptions {
+        self.whitespace = enabled;
+        self
+    }
+
+    /// 是否接受 `v`、`V` 与 `=` 前缀, `=` 之后可以再跟 `v`
+    pub fn prefix(mut self, enabled: bool) -> ParseOptions {
+        self.prefix = enabled;
+        self
+    }
+
+    /// 是否接受只有主版本号的版本, 例如 `1`
+    pub fn short(mut self, enabled: bool) -> ParseOptions {
+        self.short = enabled;
+        self
+    }
+
+    /// 是否接受 `_` 或 `.` 作为先行版本号之前的分隔符, 例如 `1.2.3_rc1`、`1.2.3.beta`
+    ///
+    /// `.` 只在主版本号、副版本号与补丁版本号齐全且之后的标识符以字母开头时生效,
+    /// `1.x.3`、`1.2.x` 这样的通配符写法仍然是错误
+    pub fn pre_separator(mut self, enabled: bool) -> ParseOptions {
+        self.pre_separator = enabled;
+        self
+    }
+
+    /// 替换先行版本号中发布阶段词语时使用的词语表, 为空时不替换
+    pub fn stage_words(mut self, words: StageWords) -> ParseOptions {
+        self.stage_words = words;
+        self
+    }
+
+    /// 构建解析器
+    pub fn build(self) -> Parser {
+        Parser { options: self }
+    }
+}
+
+impl Parser {
+
+    /// 严格模式的解析器, 与 `str::parse::<Version>()` 一致
+    pub fn strict() -> Parser {
+        ParseOptions::strict().build()
+    }
+
+    /// 宽松模式的解析器
+    pub fn lenient() -> Parser {
+        ParseOptions::lenient().build()
+    }
+
+    /// 解析器使用的选项
+    pub fn options(&self) -> &ParseOptions {
+        &self.options
+    }
+
+    /// 解析版本号
+    ///
+    /// 先按照开启的选项把输入修正为标准写法, 再按照 `str::parse::<Version>()` 的规则解析,
+    /// 因此无法修正的输入返回与严格模式相同的错误; 错误中的输入与位置对应的是修正之前的 `s`
+    pub fn parse(&self, s: &str) -> Result<Parsed, ParseError> {
+        let options = &self.options;
+        let mut coercions = Vec::new();
+
+        let converted = if options.full_width { to_half_width(s) } else { None };
+        if converted.is_some() {
+            coercions.push(Coercion::FullWidth);
+        }
+        let input = converted.as_ref().map_or(s, |converted| converted.text.as_str());
+        let mut rest = input;
+
+        if options.whitespace && rest.trim() != rest {
+            rest = rest.trim();
+            coercions.push(Coercion::Whitespace);
+        }
+        if options.prefix {
+            if let Some(stripped) = rest.strip_prefix('=') {
+                rest = if options.whitespace { stripped.trim_start() } else { stripped };
+                coercions.push(Coercion::Prefix('='));
+            }
+            if let Some(c) = rest.chars().next().filter(|c| *c == 'v' || *c == 'V')
+                && rest[1..].starts_with(|c: char| c.is_ascii_digit())
+            {
+                rest = &rest[1..];
+                coercions.push(Coercion::Prefix(c));
+            }
+        }
+
+        // 版本编译信息不受影响
+        let (rest, build) = match rest.split_once('+') {
+            Some((rest, build)) => (rest, Some(build)),
+            None => (rest, None),
+        };
+
+        // 数字部分: 由数字与后面紧跟数字的 `.` 组成
+        let bytes = rest.as_bytes();
+        let mut core_len = 0;
+        while core_len < bytes.len()
+            && (bytes[core_len].is_ascii_digit() || (bytes[core_len] == b'.' && bytes.get(core_len + 1).is_some_and(u8::is_ascii_digit)))
+        {
+            core_len += 1;
+        }
+        let (core, tail) = rest.split_at(core_len);
+
+        // `.` 只有在三个数字部分齐全、且之后的标识符以字母(通配符 `x` 除外)开头时才视为分隔符,
+        // 避免把 `1.x.3`、`1.2.x` 这样的写法当作先行版本号
+        let separator = match tail.chars().next() {
+            Some(c @ '_') if options.pre_separator => Some(c),
+            Some(c @ '.') if options.pre_separator
+                && core.matches('.').count() == 2
+                && tail[1..].starts_with(|c: char| c.is_alphabetic() && !matches!(c, 'x' | 'X')) => Some(c),
+            _ => None,
+        };
+
+        let mut normalized = Normalized::new(input);
+        normalized.copy(core);
+        if options.short && !core.is_empty() && !core.contains('.')
+            && (tail.is_empty() || tail.starts_with('-') || separator.is_some())
+        {
+            let end = normalized.offset(tail);
+            normalized.push(".0", end..end);
+            coercions.push(Coercion::MissingMinor);
+        }
+        match (tail.chars().next(), separator) {
+            (Some('-'), _) => self.push_pre(&mut normalized, &tail[..1], &tail[1..], &mut coercions),
+            (_, Some(c)) => {
+                coercions.push(Coercion::PreSeparator(c));
+                self.push_pre(&mut normalized, &tail[..1], &tail[1..], &mut coercions);
+            }
+            _ => normalized.copy(tail),
+        }
+        if let Some(build) = build {
+            let start = normalized.offset(build) - 1;
+            normalized.push("+", start..start + 1);
+            normalized.copy(build);
+        }
+
+        let version = normalized.text.parse().map_err(|e: ParseError| {
+            // 将错误的位置依次映射回转换全角字符之前的输入
+            let span = normalized.remap(e.span());
+            let span = converted.as_ref().map_or(span.clone(), |converted| converted.remap(span));
+            e.relocate(s, span)
+        })?;
+        Ok(Parsed { version, coercions })
+    }
+
+    /// 写出先行版本号, 并替换其中的发布阶段词语; 先行版本号只有正式版本的词语时省略
+    fn push_pre<'a>(&self, normalized: &mut Normalized<'a>, separator: &'a str, pre: &'a str, coercions: &mut Vec<Coercion>) {
+        let words = &self.options.stage_words;
+        if let Some((word, Stage::Release, "")) = words.lookup(pre) {
+            coercions.push(Coercion::Stage { word: word.to_string(), stage: Stage::Release });
+            return
+        }
+        let start = normalized.offset(separator);
+        normalized.push("-", start..start + separator.len());
+        for (i, identifier) in pre.split('.').enumerate() {
+            let start = normalized.offset(identifier);
+            if i > 0 {
+                normalized.push(".", start - 1..start);
+            }
+            match words.lookup(identifier) {
+                Some((word, stage, number)) if stage != Stage::Release => {
+                    coercions.push(Coercion::Stage { word: word.to_string(), stage });
+                    normalized.push(stage.as_str(), start..start + word.len());
+                    if !number.is_empty() {
+                        let number_start = start + word.len();
+                        normalized.push(".", number_start..number_start);
+                        normalized.copy(number);
+                    }
+                }
+                _ => normalized.copy(identifier),
+            }
+        }
+    }
+}
+
+/// 修正后的字符串, 记录每个字节来自修正前输入中的哪一段, 用于把错误的位置映射回输入
+struct Normalized<'a> {
+    input: &'a str,
+    text: String,
+    sources: Vec<Range<usize>>,
+}
+
+impl<'a> Normalized<'a> {
+
+    fn new(input: &'a str) -> Normalized<'a> {
+        Normalized { input, text: String::new(), sources: Vec::new() }
+    }
+
+    /// 片段在输入中的起始位置, `part` 必须是 `input` 的子串
+    fn offset(&self, part: &str) -> usize {
+        part.as_ptr() as usize - self.input.as_ptr() as usize
+    }
+
+    /// 写入替换了输入中 `source` 范围的文本
+    fn push(&mut self, text: &str, source: Range<usize>) {
+        self.text.push_str(text);
+        self.sources.extend(std::iter::repeat_n(source, text.len()));
+    }
+
+    /// 原样写入输入中的片段
+    fn copy(&mut self, part: &'a str) {
+        let offset = self.offset(part);
+        for (i, c) in part.char_indices() {
+            self.push(&part[i..i + c.len_utf8()], offset + i..offset + i + c.len_utf8());
+        }
+    }
+
+    /// 将修正后字符串中的范围映射回输入, 空范围映射为对应的位置
+    fn remap(&self, span: Range<usize>) -> Range<usize> {
+        let end = self.sources.last().map_or(0, |source| source.end);
+        let start = self.sources.get(span.start).map_or(end, |source| source.start);
+        if span.is_empty() {
+            start..start
+        } else {
+            start..self.sources[span.end - 1].end.max(start)
+        }
+    }
+}
+
+impl Stage {
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Stage::Alpha => "alpha",
+            Stage::Beta => "beta",
+            Stage::Rc => "rc",
+            Stage::Release => "release",
+        }
+    }
+}
+
+impl StageWords {
+
+    /// 空的词语表
+    pub fn new() -> StageWords {
+        StageWords::default()
+    }
+
+    /// 内置的中文词语表
+    ///
+    /// | 发布阶段 | 词语 |
+    /// |----------|------|
+    /// | [`Stage::Alpha`] | 内测版、内测、预览版、开发版 |
+    /// | [`Stage::Beta`] | 测试版、测试、公测版、公测、体验版 |
+    /// | [`Stage::Rc`] | 候选版、发布候选版、预发布版、预发布 |
+    /// | [`Stage::Release`] | 正式版、稳定版 |
+    pub fn chinese() -> StageWords {
+        [
+            ("内测版", Stage::Alpha), ("内测", Stage::Alpha), ("预览版", Stage::Alpha), ("开发版", Stage::Alpha),
+            ("测试版", Stage::Beta), ("测试", Stage::Beta), ("公测版", Stage::Beta), ("公测", Stage::Beta), ("体验版", Stage::Beta),
+            ("候选版", Stage::Rc), ("发布候选版", Stage::Rc), ("预发布版", Stage::Rc), ("预发布", Stage::Rc),
+            ("正式版", Stage::Release), ("稳定版", Stage::Release),
+        ]
+        .into_iter()
+        .fold(StageWords::new(), |words, (word, stage)| words.insert(word, stage))
+    }
+
+    /// 添加词语, 已存在时替换其发布阶段
+    pub fn insert(mut self, word: impl Into<String>, stage: Stage) -> StageWords {
+        let word = word.into();
+        match self.words.iter_mut().find(|(w, _)| *w == word) {
+            Some(entry) => entry.1 = stage,
+            None => self.words.push((word, stage)),
+        }
+        self
+    }
+
+    /// 删除词语
+    pub fn remove(mut self, word: &str) -> StageWords {
+        self.words.retain(|(w, _)| w != word);
+        self
+    }
+
+    /// 查询词语对应的发布阶段
+    pub fn get(&self, word: &str) -> Option<Stage> {
+        self.words.iter().find(|(w, _)| w == word).map(|(_, stage)| *stage)
+    }
+
+    /// 词语表是否为空
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// 查找标识符开头最长的词语, 词语之后只能是数字; 返回词语、发布阶段与之后的数字
+    fn lookup<'a>(&self, identifier: &'a str) -> Option<(&str, Stage, &'a str)> {
+        self.words.iter()
+            .filter_map(|(word, stage)| {
+                let number = identifier.strip_prefix(word.as_str())?;
+                number.bytes().all(|b| b.is_ascii_digit()).then_some((word.as_str(), *stage, number))
+            })
+            .max_by_key(|(word, _, _)| word.len())
+    }
+}
+
+/// 将全角 ASCII 字符、全角空格与句号 `。` 转换为半角, 没有需要转换的字符时返回 `None`
+fn to_half_width(s: &str) -> Option<Normalized<'_>> {
+    let convert = |c: char| match c {
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0),
+        '\u{3000}' => Some(' '),
+        '\u{3002}' => Some('.'),
+        _ => None,
+    };
+    if !s.chars().any(|c| convert(c).is_some()) {
+        return None
+    }
+    let mut converted = Normalized::new(s);
+    for (i, c) in s.char_indices() {
+        let source = i..i + c.len_utf8();
+        match convert(c) {
+            Some(half) => converted.push(half.encode_utf8(&mut [0; 4]), source),
+            None => converted.push(&s[source.clone()], source),
+        }
+    }
+    Some(converted)
+}
+
+impl Default for ParseOptions {
+    /// 与 [`ParseOptions::strict`] 相同
+    fn default() -> ParseOptions {
+        ParseOptions::strict()
+    }
+}
+
+impl Parsed {
+
+    /// 解析得到的版本号
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// 解析时做出的修正, 按照在输入中对应位置的顺序排列, 严格模式下总是为空
+    pub fn coercions(&self) -> &[Coercion] {
+        &self.coercions
+    }
+
+    /// 输入是否无需修正即为标准写法
+    pub fn is_exact(&self) -> bool {
+        self.coercions.is_empty()
+    }
+
+    /// 取出版本号
+    pub fn into_version(self) -> Version {
+        self.version
+    }
+}
+
+impl fmt::Display for Coercion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Coercion::Whitespace => f.write_str("去掉了首尾的空白"),
+            Coercion::Prefix(c) => write!(f, "去掉了前缀 '{c}'"),
+            Coercion::MissingMinor => f.write_str("补全了缺失的副版本号"),
+            Coercion::PreSeparator(c) => write!(f, "将先行版本号之前的 '{c}' 视为 '-'"),
+            Coercion::FullWidth => f.write_str("将全角字符转换为了半角字符"),
+            Coercion::Stage { word, stage: Stage::Release } => write!(f, "将 \"{word}\" 视为正式版本"),
+            Coercion::Stage { word, stage } => write!(f, "将 \"{word}\" 视为 {}", stage.as_str()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coercion, ParseOptions, Parser, Stage, StageWords};
+    use crate::ParseErrorKind;
+
+    /// 测试宽松模式接受的写法及记录的修正
+    #[test]
+    fn test_lenient() {
+        let cases: [(&str, &str, &[Coercion]); 9] = [
+            ("1.2.3", "1.2.3", &[]),
+            ("v1.2.3", "1.2.3", &[Coercion::Prefix('v')]),
+            ("V1.2", "1.2.0", &[Coercion::Prefix('V')]),
+            ("=1.2.3", "1.2.3", &[Coercion::Prefix('=')]),
+            ("\t1.2.3\n", "1.2.3", &[Coercion::Whitespace]),
+            ("1", "1.0.0", &[Coercion::MissingMinor]),
+            ("1.2.3_rc1", "1.2.3-rc1", &[Coercion::PreSeparator('_')]),
+            ("1.2.3.beta.1+exp.sha", "1.2.3-beta.1+exp.sha", &[Coercion::PreSeparator('.')]),
+            ("= v2-alpha", "2.0.0-alpha", &[Coercion::Prefix('='), Coercion::Prefix('v'), Coercion::MissingMinor]),
+        ];
+        let parser = Parser::lenient();
+        for (input, expected, coercions) in cases {
+            let parsed = parser.parse(input).unwrap_or_else(|e| panic!("{input}: {e}"));
+            assert_eq!(parsed.version().to_string(), expected, "{input}");
+            assert_eq!(parsed.coercions(), coercions, "{input}");
+            assert_eq!(parsed.is_exact(), coercions.is_empty(), "{input}");
+        }
+
+        // 通配符与不完整的数字部分之后的 `.` 不是先行版本号的分隔符
+        for input in ["1.x.3", "1.2.x", "v1.x", "1.2.beta", "1.2.3.X"] {
+            assert!(parser.parse(input).is_err(), "{input}");
+        }
+    }
+
+    /// 测试严格模式与 `str::parse` 一致, 以及单独开启的选项
+    #[test]
+    fn test_strict_and_options() {
+        let strict = Parser::strict();
+        assert_eq!(strict, Parser::default());
+        assert!(strict.parse("1.2.3-rc.1").unwrap().is_exact());
+        for input in ["v1.2.3", " 1.2.3", "=1.2.3", "1", "1.2.3_rc1", "1.2.3.beta"] {
+            assert!(strict.parse(input).is_err(), "{input}");
+        }
+
+        let parser = ParseOptions::strict().short(true).build();
+        assert_eq!(parser.parse("7").unwrap().version().to_string(), "7.0.0");
+        assert!(parser.parse("v7").is_err());
+        assert_eq!(parser.options(), &ParseOptions::strict().short(true));
+    }
+
+    /// 测试全角字符与发布阶段词语
+    #[test]
+    fn test_full_width_and_stages() {
+        let stage = |word: &str, stage| Coercion::Stage { word: word.to_string(), stage };
+        let cases = [
+            ("１．２．３", "1.2.3", vec![Coercion::FullWidth]),
+            ("1.2.3－beta", "1.2.3-beta", vec![Coercion::FullWidth]),
+            ("\u{3000}ｖ1。2", "1.2.0", vec![Coercion::FullWidth, Coercion::Whitespace, Coercion::Prefix('v')]),
+            ("1.2.0-测试版", "1.2.0-beta", vec![stage("测试版", Stage::Beta)]),
+            ("1.2.0_内测版2", "1.2.0-alpha.2", vec![Coercion::PreSeparator('_'), stage("内测版", Stage::Alpha)]),
+            ("1.2.0-正式版", "1.2.0", vec![stage("正式版", Stage::Release)]),
+            ("1.2.0－候选版.1", "1.2.0-rc.1", vec![Coercion::FullWidth, stage("候选版", Stage::Rc)]),
+        ];
+        let parser = Parser::lenient();
+        for (input, expected, coercions) in cases {
+            let parsed = parser.parse(input).unwrap_or_else(|e| panic!("{input}: {e}"));
+            assert_eq!(parsed.version().to_string(), expected, "{input}");
+            assert_eq!(parsed.coercions(), coercions, "{input}");
+        }
+
+        let v = |s: &str| parser.parse(s).unwrap().into_version();
+        assert!(v("1.2.0-内测版") < v("1.2.0-测试版"));
+        assert!(v("1.2.0-测试版") < v("1.2.0-候选版"));
+        assert!(v("1.2.0-候选版") < v("1.2.0-正式版"));
+        assert!(parser.parse("1.2.0-正式版.1").is_err());
+        assert!(parser.parse("1.2.0-测试版x").is_err());
+        assert_eq!(stage("正式版", Stage::Release).to_string(), "将 \"正式版\" 视为正式版本");
+    }
+
+    /// 测试自定义词语表与单独关闭的选项
+    #[test]
+    fn test_stage_words() {
+        let words = StageWords::new().insert("灰度", Stage::Beta).insert("灰度", Stage::Rc);
+        assert_eq!(words.get("灰度"), Some(Stage::Rc));
+        assert_eq!(StageWords::chinese().remove("测试版").get("测试版"), None);
+
+        let parser = ParseOptions::strict().stage_words(words).build();
+        assert_eq!(parser.parse("2.0.0-灰度3").unwrap().version().to_string(), "2.0.0-rc.3");
+        assert!(parser.parse("2.0.0-测试版").is_err());
+
+        let parser = ParseOptions::lenient().full_width(false).stage_words(StageWords::new()).build();
+        assert!(parser.parse("１．２．３").is_err());
+        assert!(parser.parse("1.2.0-测试版").is_err());
+        assert!(Parser::strict().parse("1.2.3－beta").is_err());
+    }
+
+    /// 测试无法修正的输入返回与严格模式相同的错误
+    #[test]
+    fn test_errors() {
+        let parser = Parser::lenient();
+        assert!(matches!(parser.parse("1.2.3.4").unwrap_err().kind(), ParseErrorKind::LengthError));
+        assert!(matches!(parser.parse("v01.2").unwrap_err().kind(), ParseErrorKind::LeadingZero(_)));
+        assert!(matches!(parser.parse("1.2.3_").unwrap_err().kind(), ParseErrorKind::EmptyIdentifier));
+        assert!(matches!(parser.parse("1.2.3 beta").unwrap_err().kind(), ParseErrorKind::IntError(_)));
+        assert!(matches!(parser.parse("version").unwrap_err().kind(), ParseErrorKind::LengthError));
+        assert_eq!(Coercion::Prefix('v').to_string(), "去掉了前缀 'v'");
+    }
+
+    /// 测试宽松解析失败时错误的输入与位置对应修正之前的字符串
+    #[test]
+    fn test_error_span() {
+        let parser = Parser::lenient();
+        let cases = [
+            ("  v01.2", 3..5),
+            ("１．０２．３", 6..12),
+            ("1.2.3－", 8..8),
+            ("v1.x.3", 3..4),
+            ("=1_内测版.01", 13..15),
+            ("1.2.3.beta..1+b", 11..11),
+            ("1.2.3+a+b", 7..8),
+        ];
+        for (input, span) in cases {
+            let err = parser.parse(input).unwrap_err();
+            assert_eq!(err.input(), input);
+            assert_eq!(err.span(), span, "{input}");
+        }
+
+        let rendered = parser.parse("１．０２．３").unwrap_err().render();
+        assert!(rendered.contains("\n  | １．０２．３\n  |     ^^^^ 副版本号\n"), "{rendered}");
+    }
+}
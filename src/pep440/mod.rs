@@ -0,0 +1,673 @@
+//! Python 使用的 [PEP 440](https://peps.python.org/pep-0440/) 版本号
+//!
+//! 支持纪元(`1!`)、先行版本(`a`/`b`/`rc`)、后发布版本(`.post`)、开发版本(`.dev`)与本地版本(`+local`),
+//! 解析时按照 PEP 440 的规则进行规范化, 例如 `1.0-RC1` 规范化为 `1.0rc1`。
+//! [`SpecifierSet`] 用于判断版本号是否满足 `requires-python` 等版本说明符
+//!
+//! ```
+//! use version::pep440::Version;
+//!
+//! let v: Version = "1!2.0.post1.dev3".parse().unwrap();
+//! assert_eq!(v.epoch(), 1);
+//! assert!("1.0-RC1".parse::<Version>().unwrap() < "1.0".parse::<Version>().unwrap());
+//! ```
+
+mod specifier;
+
+pub use specifier::{Operator, Specifier, SpecifierSet};
+
+use crate::Identifier;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::num::ParseIntError;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 PEP 440 版本号
+///
+/// 版本号之间按照 PEP 440 规定的顺序比较, 发布版本号末尾的零不影响比较,
+/// 因此 `1.0 == 1.0.0`
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u64,
+    release: Vec<u64>,
+    pre: Option<(PreKind, u64)>,
+    post: Option<u64>,
+    dev: Option<u64>,
+    local: Vec<LocalSegment>,
+}
+
+///
+/// 先行版本的类型, 按 `a < b < rc` 的顺序排序
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PreKind {
+    /// `a`, 也接受 `alpha`
+    Alpha,
+    /// `b`, 也接受 `beta`
+    Beta,
+    /// `rc`, 也接受 `c`、`pre` 与 `preview`
+    Rc,
+}
+
+///
+/// 本地版本号中的单个部分
+///
+/// 纯字母数字的部分低于纯数字的部分, 与 PEP 440 的规定一致
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LocalSegment {
+    /// 包含字母的部分, 统一为小写
+    Alpha(String),
+    /// 纯数字部分
+    Numeric(u64),
+}
+
+///
+/// 解析 PEP 440 版本号时可能发生的错误
+#[derive(Error, Debug)]
+pub enum ParseError {
+
+    #[error("解析数字失败: {0}")]
+    IntError(#[from] ParseIntError),
+
+    #[error("非法的 PEP 440 版本号 \"{0}\"")]
+    InvalidVersion(String),
+
+    #[error("非法的版本说明符 \"{0}\"")]
+    InvalidSpecifier(String),
+}
+
+///
+/// 在 PEP 440 版本号与 SemVer 版本号之间转换时可能发生的错误
+///
+/// 只有不丢失信息的转换才会成功
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+
+    #[error("SemVer 版本号不支持纪元")]
+    Epoch,
+
+    #[error("发布版本号超过三个部分")]
+    TooManyReleaseSegments,
+
+    #[error("SemVer 版本号不支持后发布版本")]
+    PostRelease,
+
+    #[error("SemVer 版本号不支持开发版本")]
+    DevRelease,
+
+    #[error("本地版本号与编译信息的比较规则不同, 无法转换")]
+    Local,
+
+    #[error("先行版本号 \"{0}\" 无法与 PEP 440 对应, 仅支持 alpha.N、beta.N 与 rc.N")]
+    Prerelease(String),
+}
+
+impl Version {
+
+    /// 通过发布版本号构建不含其他部分的版本号
+    ///
+    /// # 示例
+    /// ```
+    /// use version::pep440::Version;
+    ///
+    /// assert_eq!(Version::new([3, 12]).to_string(), "3.12");
+    /// ```
+    pub fn new(release: impl Into<Vec<u64>>) -> Version {
+        Version {
+            epoch: 0,
+            release: release.into(),
+            pre: None,
+            post: None,
+            dev: None,
+            local: Vec::new(),
+        }
+    }
+
+    /// 纪元, 未指定时为 0
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// 发布版本号, 例如 `1.2.3` 对应 `[1, 2, 3]`
+    pub fn release(&self) -> &[u64] {
+        &self.release
+    }
+
+    /// 先行版本的类型与序号
+    pub fn pre(&self) -> Option<(PreKind, u64)> {
+        self.pre
+    }
+
+    /// 后发布版本序号
+    pub fn post(&self) -> Option<u64> {
+        self.post
+    }
+
+    /// 开发版本序号
+    pub fn dev(&self) -> Option<u64> {
+        self.dev
+    }
+
+    /// 本地版本号, 不存在时为空
+    pub fn local(&self) -> &[LocalSegment] {
+        &self.local
+    }
+
+    /// 是否为先行版本, 开发版本同样视为先行版本
+    pub fn is_prerelease(&self) -> bool {
+        self.pre.is_some() || self.dev.is_some()
+    }
+
+    /// 是否为后发布版本
+    pub fn is_postrelease(&self) -> bool {
+        self.post.is_some()
+    }
+
+    /// 是否为开发版本
+    pub fn is_devrelease(&self) -> bool {
+        self.dev.is_some()
+    }
+
+    /// 去掉本地版本号后的公开版本号
+    pub fn public(&self) -> Version {
+        Version { local: Vec::new(), ..self.clone() }
+    }
+
+    /// 只保留纪元与发布版本号的基础版本号, 例如 `1!2.0rc1.post3` 的基础版本号为 `1!2.0`
+    pub fn base(&self) -> Version {
+        Version { epoch: self.epoch, ..Version::new(self.release.clone()) }
+    }
+
+    /// 去掉发布版本号末尾的零, 用于比较与哈希
+    fn trimmed_release(&self) -> &[u64] {
+        let len = self.release.iter().rposition(|n| *n != 0).map_or(0, |i| i + 1);
+        &self.release[..len]
+    }
+
+    /// 先行版本部分的排序键, 仅有开发版本时排在所有先行版本之前, 正式版本排在最后
+    fn pre_key(&self) -> (u8, Option<(PreKind, u64)>) {
+        match (self.pre, self.post, self.dev) {
+            (None, None, Some(_)) => (0, None),
+            (Some(pre), _, _) => (1, Some(pre)),
+            (None, _, _) => (2, None),
+        }
+    }
+
+    /// 开发版本部分的排序键, 非开发版本排在最后
+    fn dev_key(&self) -> (bool, Option<u64>) {
+        (self.dev.is_none(), self.dev)
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch.cmp(&other.epoch)
+            .then_with(|| self.trimmed_release().cmp(other.trimmed_release()))
+            .then_with(|| self.pre_key().cmp(&other.pre_key()))
+            // 没有后发布版本时排在最前, Option 的 None 本身就小于 Some
+            .then_with(|| self.post.cmp(&other.post))
+            .then_with(|| self.dev_key().cmp(&other.dev_key()))
+            // 没有本地版本号时排在最前, 空列表本身就小于非空列表
+            .then_with(|| self.local.cmp(&other.local))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致, 发布版本号末尾的零不参与哈希
+        self.epoch.hash(state);
+        self.trimmed_release().hash(state);
+        self.pre.hash(state);
+        self.post.hash(state);
+        self.dev.hash(state);
+        self.local.hash(state);
+    }
+}
+
+/// 以规范化的形式输出版本号, 例如 `1!2.0rc1.post2.dev3+ubuntu.1`
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}!", self.epoch)?;
+        }
+        for (i, n) in self.release.iter().enumerate() {
+            if i > 0 {
+                f.write_str(".")?;
+            }
+            write!(f, "{n}")?;
+        }
+        if let Some((kind, n)) = self.pre {
+            write!(f, "{kind}{n}")?;
+        }
+        if let Some(n) = self.post {
+            write!(f, ".post{n}")?;
+        }
+        if let Some(n) = self.dev {
+            write!(f, ".dev{n}")?;
+        }
+        for (i, segment) in self.local.iter().enumerate() {
+            f.write_str(if i == 0 { "+" } else { "." })?;
+            write!(f, "{segment}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for PreKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PreKind::Alpha => "a",
+            PreKind::Beta => "b",
+            PreKind::Rc => "rc",
+        })
+    }
+}
+
+impl fmt::Display for LocalSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocalSegment::Alpha(s) => f.write_str(s),
+            LocalSegment::Numeric(n) => write!(f, "{n}"),
+        }
+    }
+}
+
+/// 解析并规范化 PEP 440 版本号
+///
+/// 大小写不敏感, 允许前后空白与 `v` 前缀, 允许 `-`、`_`、`.` 作为分隔符,
+/// 允许 `alpha`、`beta`、`c`、`pre`、`preview`、`rev`、`r` 等替代拼写以及隐式的序号
+///
+/// # 示例
+/// ```
+/// use version::pep440::Version;
+///
+/// assert_eq!("1.0-RC1".parse::<Version>().unwrap().to_string(), "1.0rc1");
+/// assert_eq!("v1.0-1".parse::<Version>().unwrap().to_string(), "1.0.post1");
+/// assert_eq!("1.0.DEV".parse::<Version>().unwrap().to_string(), "1.0.dev0");
+/// assert_eq!("2.0a1+Local_7".parse::<Version>().unwrap().to_string(), "2.0a1+local.7");
+/// ```
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let invalid = || ParseError::InvalidVersion(s.to_string());
+        let normalized = s.trim().to_ascii_lowercase();
+        let rest = normalized.strip_prefix('v').unwrap_or(&normalized);
+
+        // 本地版本号
+        let (rest, local) = match rest.split_once('+') {
+            Some((rest, local)) => (rest, parse_local(local).ok_or_else(invalid)??),
+            None => (rest, Vec::new()),
+        };
+
+        // 纪元
+        let (epoch, rest) = match rest.split_once('!') {
+            Some((epoch, rest)) if is_digits(epoch) => (epoch.parse::<u64>()?, rest),
+            Some(_) => return Err(invalid()),
+            None => (0, rest),
+        };
+
+        let mut cursor = Cursor { s: rest, pos: 0 };
+
+        // 发布版本号
+        let mut release = vec![cursor.number().ok_or_else(invalid)??];
+        loop {
+            let start = cursor.pos;
+            if cursor.eat(".")
+                && let Some(n) = cursor.number()
+            {
+                release.push(n?);
+                continue
+            }
+            cursor.pos = start;
+            break
+        }
+
+        // 先行版本
+        let pre = match cursor.label(&["alpha", "a", "beta", "b", "preview", "pre", "c", "rc"]) {
+            Some(label) => {
+                let kind = match label {
+                    "alpha" | "a" => PreKind::Alpha,
+                    "beta" | "b" => PreKind::Beta,
+                    _ => PreKind::Rc,
+                };
+                Some((kind, cursor.implicit_number()?))
+            }
+            None => None,
+        };
+
+        // 后发布版本, `-1` 是 `.post1` 的简写
+        let post = {
+            let start = cursor.pos;
+            match cursor.eat("-").then(|| cursor.number()).flatten() {
+                Some(n) => Some(n?),
+                None => {
+                    cursor.pos = start;
+                    match cursor.label(&["post", "rev", "r"]) {
+                        Some(_) => Some(cursor.implicit_number()?),
+                        None => None,
+                    }
+                }
+            }
+        };
+
+        // 开发版本
+        let dev = match cursor.label(&["dev"]) {
+            Some(_) => Some(cursor.implicit_number()?),
+            None => None,
+        };
+
+        if cursor.pos != cursor.s.len() {
+            return Err(invalid())
+        }
+        Ok(Version { epoch, release, pre, post, dev, local })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+/// 将 SemVer 版本号转换为 PEP 440 版本号
+///
+/// 先行版本号只能为 `alpha.N`、`beta.N` 或 `rc.N`, 且不能带有编译信息
+///
+/// # 示例
+/// ```
+/// use version::pep440;
+///
+/// let semver: version::Version = "1.2.3-rc.1".parse().unwrap();
+/// let pep = pep440::Version::try_from(&semver).unwrap();
+/// assert_eq!(pep.to_string(), "1.2.3rc1");
+/// assert_eq!(version::Version::try_from(&pep).unwrap(), semver);
+/// ```
+impl TryFrom<&crate::Version> for Version {
+    type Error = ConversionError;
+
+    fn try_from(v: &crate::Version) -> Result<Version, ConversionError> {
+        if !v.build.is_empty() {
+            return Err(ConversionError::Local)
+        }
+        let pre = match v.pre.as_slice() {
+            [] => None,
+            [Identifier::AlphaNumeric(label), Identifier::Numeric(n)] if label == "alpha" => Some((PreKind::Alpha, *n)),
+            [Identifier::AlphaNumeric(label), Identifier::Numeric(n)] if label == "beta" => Some((PreKind::Beta, *n)),
+            [Identifier::AlphaNumeric(label), Identifier::Numeric(n)] if label == "rc" => Some((PreKind::Rc, *n)),
+            other => {
+                let pre: Vec<String> = other.iter().map(|i| i.to_string()).collect();
+                return Err(ConversionError::Prerelease(pre.join(".")))
+            }
+        };
+        Ok(Version { pre, ..Version::new([v.major, v.minor, v.patch]) })
+    }
+}
+
+/// 将 PEP 440 版本号转换为 SemVer 版本号
+///
+/// 发布版本号不足三个部分时以零补齐(PEP 440 中 `1.0 == 1.0.0`),
+/// 先行版本转换为 `alpha.N`、`beta.N` 或 `rc.N`
+impl TryFrom<&Version> for crate::Version {
+    type Error = ConversionError;
+
+    fn try_from(v: &Version) -> Result<crate::Version, ConversionError> {
+        if v.epoch != 0 {
+            return Err(ConversionError::Epoch)
+        }
+        if v.release.len() > 3 {
+            return Err(ConversionError::TooManyReleaseSegments)
+        }
+        if v.post.is_some() {
+            return Err(ConversionError::PostRelease)
+        }
+        if v.dev.is_some() {
+            return Err(ConversionError::DevRelease)
+        }
+        if !v.local.is_empty() {
+            return Err(ConversionError::Local)
+        }
+        let pre = match v.pre {
+            Some((kind, n)) => {
+                let label = match kind {
+                    PreKind::Alpha => "alpha",
+                    PreKind::Beta => "beta",
+                    PreKind::Rc => "rc",
+                };
+                vec![Identifier::AlphaNumeric(label.to_string()), Identifier::Numeric(n)]
+            }
+            None => Vec::new(),
+        };
+        let part = |i: usize| v.release.get(i).copied().unwrap_or(0);
+        Ok(crate::Version {
+            major: part(0),
+            minor: part(1),
+            patch: part(2),
+            pre,
+            build: Vec::new(),
+        })
+    }
+}
+
+/// 按字符位置逐步解析版本号剩余部分的游标
+struct Cursor<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl Cursor<'_> {
+
+    fn rest(&self) -> &str {
+        &self.s[self.pos..]
+    }
+
+    /// 若剩余部分以 `prefix` 开头则消费它
+    fn eat(&mut self, prefix: &str) -> bool {
+        let matched = self.rest().starts_with(prefix);
+        if matched {
+            self.pos += prefix.len();
+        }
+        matched
+    }
+
+    /// 消费一个可选的分隔符 `-`、`_` 或 `.`
+    fn separator(&mut self) {
+        let _ = self.eat("-") || self.eat("_") || self.eat(".");
+    }
+
+    /// 消费连续的数字
+    fn number(&mut self) -> Option<Result<u64, ParseError>> {
+        let len = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+        if len == 0 {
+            return None
+        }
+        let n = self.rest()[..len].parse::<u64>().map_err(ParseError::from);
+        self.pos += len;
+        Some(n)
+    }
+
+    /// 消费 `[分隔符]标签`, 标签按顺序尝试, 未匹配时不消费任何内容
+    fn label(&mut self, labels: &[&'static str]) -> Option<&'static str> {
+        let start = self.pos;
+        self.separator();
+        match labels.iter().find(|label| self.rest().starts_with(**label)) {
+            Some(label) => {
+                self.pos += label.len();
+                Some(label)
+            }
+            None => {
+                self.pos = start;
+                None
+            }
+        }
+    }
+
+    /// 消费 `[分隔符]数字`, 数字缺省时为 0 且不消费分隔符
+    fn implicit_number(&mut self) -> Result<u64, ParseError> {
+        let start = self.pos;
+        self.separator();
+        match self.number() {
+            Some(n) => n,
+            None => {
+                self.pos = start;
+                Ok(0)
+            }
+        }
+    }
+}
+
+fn is_digits(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// 解析以 `-`、`_`、`.` 分隔的本地版本号, 格式非法时返回 `None`
+fn parse_local(local: &str) -> Option<Result<Vec<LocalSegment>, ParseError>> {
+    let mut segments = Vec::new();
+    for part in local.split(['-', '_', '.']) {
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_alphanumeric()) {
+            return None
+        }
+        segments.push(if is_digits(part) {
+            match part.parse::<u64>() {
+                Ok(n) => LocalSegment::Numeric(n),
+                Err(e) => return Some(Err(e.into())),
+            }
+        } else {
+            LocalSegment::Alpha(part.to_string())
+        });
+    }
+    Some(Ok(segments))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConversionError, LocalSegment, PreKind, Version};
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 测试规范化结果, 取自 PEP 440 与 packaging 的测试用例
+    #[test]
+    fn test_normalize() {
+        let cases = [
+            ("1.0-RC1", "1.0rc1"),
+            ("1.0alpha1", "1.0a1"),
+            ("1.0.beta.2", "1.0b2"),
+            ("1.0c1", "1.0rc1"),
+            ("1.0pre1", "1.0rc1"),
+            ("1.0preview", "1.0rc0"),
+            ("1.0a", "1.0a0"),
+            ("1.0-1", "1.0.post1"),
+            ("1.0post", "1.0.post0"),
+            ("1.0.rev3", "1.0.post3"),
+            ("1.0r_4", "1.0.post4"),
+            ("1.0-dev", "1.0.dev0"),
+            ("1.0_dev_7", "1.0.dev7"),
+            ("v1.0", "1.0"),
+            ("  1.0\n", "1.0"),
+            ("1!2.0.post1.dev3", "1!2.0.post1.dev3"),
+            ("2.0a1+local.7", "2.0a1+local.7"),
+            ("1.0+ubuntu-1", "1.0+ubuntu.1"),
+            ("1.0+abc_007", "1.0+abc.7"),
+            ("1.01.002", "1.1.2"),
+            ("1.0a1-1", "1.0a1.post1"),
+            ("1.0rc1.post2.dev3", "1.0rc1.post2.dev3"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(v(input).to_string(), expected, "{input}");
+        }
+        let parsed = v("1!2.0rc3.post4.dev5+a.6");
+        assert_eq!(parsed.epoch(), 1);
+        assert_eq!(parsed.release(), &[2, 0]);
+        assert_eq!(parsed.pre(), Some((PreKind::Rc, 3)));
+        assert_eq!((parsed.post(), parsed.dev()), (Some(4), Some(5)));
+        assert_eq!(parsed.local(), &[LocalSegment::Alpha("a".to_string()), LocalSegment::Numeric(6)]);
+    }
+
+    /// 测试非法的版本号
+    #[test]
+    fn test_invalid() {
+        for input in ["", "1.0.", "1..0", "a1.0", "1.0+", "1.0+a..b", "1.0+a$", "1.0-", "1.0dev-", "x!1.0", "1.0 beta", "1.0foo"] {
+            assert!(input.parse::<Version>().is_err(), "{input}");
+        }
+    }
+
+    /// 测试 PEP 440 排序, 取自 PEP 440 的示例与 packaging 的测试用例
+    #[test]
+    fn test_ordering() {
+        let ordered = [
+            "1.0.dev456",
+            "1.0a1",
+            "1.0a2.dev456",
+            "1.0a12.dev456",
+            "1.0a12",
+            "1.0b1.dev456",
+            "1.0b2",
+            "1.0b2.post345.dev456",
+            "1.0b2.post345",
+            "1.0b2-346",
+            "1.0c1.dev456",
+            "1.0c1",
+            "1.0rc2",
+            "1.0c3",
+            "1.0",
+            "1.0+abc.5",
+            "1.0+abc.7",
+            "1.0+5",
+            "1.0.post456.dev34",
+            "1.0.post456",
+            "1.0.15",
+            "1.1.dev1",
+            "1!0.1",
+        ];
+        let versions: Vec<Version> = ordered.iter().map(|s| v(s)).collect();
+        for pair in versions.windows(2) {
+            assert!(pair[0] < pair[1], "{} < {}", pair[0], pair[1]);
+        }
+        assert_eq!(v("1.0"), v("1.0.0.0"));
+        assert_eq!(v("1.0a1"), v("1.0.0alpha1"));
+        assert!(v("1.0+local") > v("1.0"));
+        assert!(v("1.0+1") > v("1.0+a"));
+    }
+
+    /// 测试与 SemVer 版本号之间的无损转换
+    #[test]
+    fn test_semver_conversion() {
+        for (pep, semver) in [("1.2.3", "1.2.3"), ("1.0", "1.0.0"), ("2.0a1", "2.0.0-alpha.1"), ("3.1rc2", "3.1.0-rc.2")] {
+            let converted = crate::Version::try_from(&v(pep)).unwrap();
+            assert_eq!(converted.to_string(), semver);
+            assert_eq!(Version::try_from(&converted).unwrap(), v(pep));
+        }
+
+        let err = |s: &str| crate::Version::try_from(&v(s)).unwrap_err();
+        assert_eq!(err("1!1.0"), ConversionError::Epoch);
+        assert_eq!(err("1.2.3.4"), ConversionError::TooManyReleaseSegments);
+        assert_eq!(err("1.0.post1"), ConversionError::PostRelease);
+        assert_eq!(err("1.0.dev1"), ConversionError::DevRelease);
+        assert_eq!(err("1.0+local"), ConversionError::Local);
+
+        let semver = |s: &str| Version::try_from(&s.parse::<crate::Version>().unwrap());
+        assert!(matches!(semver("1.0.0-rc"), Err(ConversionError::Prerelease(_))));
+        assert!(matches!(semver("1.0.0-preview.1"), Err(ConversionError::Prerelease(_))));
+        assert_eq!(semver("1.0.0+build.5"), Err(ConversionError::Local));
+    }
+}
@@ -0,0 +1,504 @@
+//! PEP 440 版本说明符, 例如 `~=3.8`、`>=1.0,!=1.3.*` 与 `===foobar`
+
+use super::{ParseError, Version};
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+///
+/// 由若干个以 `,` 分隔的说明符组成的集合, 例如 `requires-python` 的取值
+///
+/// 所有说明符均满足时版本才满足集合。默认不接受先行版本,
+/// 除非某个说明符本身引用了先行版本或显式开启
+///
+/// ```
+/// use version::pep440::{SpecifierSet, Version};
+///
+/// let specs: SpecifierSet = ">=1.0,!=1.3.*".parse().unwrap();
+/// assert!(specs.contains(&"1.2".parse::<Version>().unwrap()));
+/// assert!(!specs.contains(&"1.3.4".parse::<Version>().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecifierSet {
+    specs: Vec<Specifier>,
+    prereleases: Option<bool>,
+}
+
+///
+/// 单个版本说明符, 由运算符与版本号组成
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Specifier {
+    operator: Operator,
+    /// 书写时的版本号, 不含 `.*`
+    spec: String,
+    /// 解析后的版本号, 只有 `===` 的任意字符串可能为 `None`
+    version: Option<Version>,
+    wildcard: bool,
+    prereleases: Option<bool>,
+}
+
+///
+/// 说明符的运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `~=`, 兼容版本
+    Compatible,
+    /// `==`, 支持 `.*` 前缀匹配
+    Equal,
+    /// `!=`, 支持 `.*` 前缀匹配
+    NotEqual,
+    /// `<=`
+    LessEqual,
+    /// `>=`
+    GreaterEqual,
+    /// `<`
+    Less,
+    /// `>`
+    Greater,
+    /// `===`, 按字符串进行任意相等比较
+    ArbitraryEqual,
+}
+
+impl SpecifierSet {
+
+    /// 组成集合的说明符
+    pub fn specifiers(&self) -> &[Specifier] {
+        &self.specs
+    }
+
+    /// 显式指定是否接受先行版本, 覆盖说明符隐含的设置
+    pub fn with_prereleases(mut self, prereleases: bool) -> SpecifierSet {
+        self.prereleases = Some(prereleases);
+        self
+    }
+
+    /// 是否接受先行版本
+    ///
+    /// 显式指定时返回指定的值; 否则任一说明符引用了先行版本时为 `Some(true)`, 其余情况为 `None`
+    pub fn prereleases(&self) -> Option<bool> {
+        self.prereleases.or_else(|| self.specs.iter().any(Specifier::prereleases).then_some(true))
+    }
+
+    /// 判断版本号是否满足集合中的所有说明符
+    ///
+    /// # 示例
+    /// ```
+    /// use version::pep440::{SpecifierSet, Version};
+    ///
+    /// let specs: SpecifierSet = "~=3.8".parse().unwrap();
+    /// assert!(specs.contains(&"3.12".parse::<Version>().unwrap()));
+    /// assert!(!specs.contains(&"4.0".parse::<Version>().unwrap()));
+    /// assert!(!specs.contains(&"3.13.0rc1".parse::<Version>().unwrap()));
+    /// ```
+    pub fn contains(&self, version: &Version) -> bool {
+        let prereleases = self.prereleases().unwrap_or(false);
+        if version.is_prerelease() && !prereleases {
+            return false
+        }
+        self.specs.iter().all(|spec| spec.contains_with(version, prereleases))
+    }
+
+    /// 从一组版本号中筛选出满足集合的版本号, 保持原有顺序
+    ///
+    /// 未显式禁止先行版本时, 若没有任何正式版本满足集合, 则返回满足集合的先行版本
+    ///
+    /// # 示例
+    /// ```
+    /// use version::pep440::{SpecifierSet, Version};
+    ///
+    /// let specs: SpecifierSet = ">=1.2.3".parse().unwrap();
+    /// let versions: Vec<Version> = ["1.2", "1.3", "1.5a1"].iter().map(|s| s.parse().unwrap()).collect();
+    /// let matched: Vec<&Version> = specs.filter(&versions).collect();
+    /// assert_eq!(matched, [&versions[1]]);
+    ///
+    /// let matched: Vec<&Version> = specs.filter([&versions[0], &versions[2]]).collect();
+    /// assert_eq!(matched, [&versions[2]]);
+    /// ```
+    pub fn filter<I, V>(&self, versions: I) -> impl Iterator<Item = V> + use<I, V>
+    where
+        I: IntoIterator<Item = V>,
+        V: Borrow<Version>,
+    {
+        let prereleases = self.prereleases();
+        let mut matched = Vec::new();
+        let mut found_prereleases = Vec::new();
+        for item in versions {
+            let version = item.borrow();
+            if !self.specs.iter().all(|spec| spec.contains_with(version, true)) {
+                continue
+            }
+            if version.is_prerelease() && prereleases != Some(true) {
+                found_prereleases.push(item);
+            } else {
+                matched.push(item);
+            }
+        }
+        if matched.is_empty() && prereleases.is_none() {
+            matched = found_prereleases;
+        }
+        matched.into_iter()
+    }
+}
+
+impl Specifier {
+
+    /// 运算符
+    pub fn operator(&self) -> Operator {
+        self.operator
+    }
+
+    /// 书写时的版本号, 不含 `.*`
+    pub fn version(&self) -> &str {
+        &self.spec
+    }
+
+    /// 是否为 `==1.0.*` 形式的前缀匹配
+    pub fn is_wildcard(&self) -> bool {
+        self.wildcard
+    }
+
+    /// 显式指定是否接受先行版本
+    pub fn with_prereleases(mut self, prereleases: bool) -> Specifier {
+        self.prereleases = Some(prereleases);
+        self
+    }
+
+    /// 是否接受先行版本
+    ///
+    /// 未显式指定时, `==`、`>=`、`<=`、`~=`、`===` 引用了先行版本则接受先行版本
+    pub fn prereleases(&self) -> bool {
+        self.prereleases.unwrap_or_else(|| {
+            matches!(
+                self.operator,
+                Operator::Equal | Operator::GreaterEqual | Operator::LessEqual | Operator::Compatible | Operator::ArbitraryEqual
+            ) && self.version.as_ref().is_some_and(Version::is_prerelease)
+        })
+    }
+
+    /// 判断版本号是否满足此说明符
+    pub fn contains(&self, version: &Version) -> bool {
+        self.contains_with(version, self.prereleases())
+    }
+
+    /// 判断字符串形式的版本号是否满足此说明符
+    ///
+    /// 对 `===` 按字符串比较(大小写不敏感), 因此可以匹配不符合 PEP 440 的版本号;
+    /// 其他运算符在版本号无法解析时返回 `false`
+    ///
+    /// # 示例
+    /// ```
+    /// use version::pep440::Specifier;
+    ///
+    /// let spec: Specifier = "===foobar".parse().unwrap();
+    /// assert!(spec.contains_str("FooBar"));
+    /// assert!(!spec.contains_str("1.0"));
+    /// ```
+    pub fn contains_str(&self, version: &str) -> bool {
+        if self.operator == Operator::ArbitraryEqual {
+            return version.trim().eq_ignore_ascii_case(&self.spec)
+        }
+        version.parse::<Version>().is_ok_and(|v| self.contains(&v))
+    }
+
+    fn contains_with(&self, version: &Version, prereleases: bool) -> bool {
+        if version.is_prerelease() && !prereleases {
+            return false
+        }
+        let Some(spec) = &self.version else {
+            // 只有 `===` 会出现无法解析的版本号
+            return version.to_string().eq_ignore_ascii_case(&self.spec)
+        };
+        match self.operator {
+            Operator::Compatible => {
+                // ~=V.N := >=V.N, ==V.*
+                let prefix = &spec.release[..spec.release.len() - 1];
+                version.public() >= *spec && prefix_match(version, spec.epoch, prefix)
+            }
+            Operator::Equal => self.equal(version, spec),
+            Operator::NotEqual => !self.equal(version, spec),
+            Operator::LessEqual => version.public() <= *spec,
+            Operator::GreaterEqual => version.public() >= *spec,
+            Operator::Less => {
+                // 说明符本身不是先行版本时, `<3.1` 不匹配 `3.1.dev0`
+                let excluded = !spec.is_prerelease() && version.is_prerelease();
+                version < spec && !(excluded && version.base() == spec.base())
+            }
+            Operator::Greater => {
+                // 说明符本身不是后发布版本时, `>3.1` 不匹配 `3.1.post0`, 也不匹配 `3.1+local`
+                let excluded = (!spec.is_postrelease() && version.is_postrelease()) || !version.local.is_empty();
+                version > spec && !(excluded && version.base() == spec.base())
+            }
+            Operator::ArbitraryEqual => version.to_string().eq_ignore_ascii_case(&self.spec),
+        }
+    }
+
+    fn equal(&self, version: &Version, spec: &Version) -> bool {
+        if self.wildcard {
+            prefix_match(version, spec.epoch, &spec.release)
+        } else if spec.local.is_empty() {
+            // 说明符不含本地版本号时忽略待比较版本的本地版本号
+            version.public() == *spec
+        } else {
+            version == spec
+        }
+    }
+}
+
+/// 前缀匹配: 纪元相同, 且以零补齐后的发布版本号以 `prefix` 开头
+fn prefix_match(version: &Version, epoch: u64, prefix: &[u64]) -> bool {
+    version.epoch == epoch
+        && prefix.iter().enumerate().all(|(i, n)| version.release.get(i).copied().unwrap_or(0) == *n)
+}
+
+/// 解析以 `,` 分隔的说明符集合, 空字符串表示不做任何限制
+impl FromStr for SpecifierSet {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<SpecifierSet, ParseError> {
+        let specs = s
+            .split(',')
+            .map(str::trim)
+            .filter(|spec| !spec.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<Specifier>, ParseError>>()?;
+        Ok(SpecifierSet { specs, prereleases: None })
+    }
+}
+
+/// 解析单个说明符, 运算符与版本号之间允许存在空白
+///
+/// - `~=` 的版本号至少包含两个发布版本号部分, 且不能带有 `.*` 或本地版本号
+/// - `==` 与 `!=` 可以使用 `.*` 前缀匹配, 此时版本号只能包含纪元与发布版本号
+/// - `<`、`<=`、`>`、`>=` 不能带有 `.*` 或本地版本号
+/// - `===` 可以是任意不含空白的字符串
+impl FromStr for Specifier {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Specifier, ParseError> {
+        let invalid = || ParseError::InvalidSpecifier(s.to_string());
+        let s_trimmed = s.trim();
+        let (operator, rest) = [
+            ("===", Operator::ArbitraryEqual),
+            ("~=", Operator::Compatible),
+            ("==", Operator::Equal),
+            ("!=", Operator::NotEqual),
+            ("<=", Operator::LessEqual),
+            (">=", Operator::GreaterEqual),
+            ("<", Operator::Less),
+            (">", Operator::Greater),
+        ]
+        .iter()
+        .find_map(|(prefix, operator)| s_trimmed.strip_prefix(prefix).map(|rest| (*operator, rest.trim())))
+        .ok_or_else(invalid)?;
+
+        if rest.is_empty() || rest.contains(char::is_whitespace) {
+            return Err(invalid())
+        }
+        if operator == Operator::ArbitraryEqual {
+            return Ok(Specifier {
+                operator,
+                spec: rest.to_string(),
+                version: rest.parse().ok(),
+                wildcard: false,
+                prereleases: None,
+            })
+        }
+
+        let (spec, wildcard) = match rest.strip_suffix(".*") {
+            Some(spec) => (spec, true),
+            None => (rest, false),
+        };
+        let version: Version = spec.parse()?;
+        let only_release = version.pre.is_none() && version.post.is_none() && version.dev.is_none() && version.local.is_empty();
+        let valid = match operator {
+            Operator::Equal | Operator::NotEqual => !wildcard || only_release,
+            Operator::Compatible => !wildcard && version.local.is_empty() && version.release.len() >= 2,
+            _ => !wildcard && version.local.is_empty(),
+        };
+        if !valid {
+            return Err(invalid())
+        }
+        Ok(Specifier {
+            operator,
+            spec: spec.to_string(),
+            version: Some(version),
+            wildcard,
+            prereleases: None,
+        })
+    }
+}
+
+impl fmt::Display for SpecifierSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, spec) in self.specs.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{spec}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Specifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.operator, self.spec)?;
+        if self.wildcard {
+            f.write_str(".*")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Operator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Operator::Compatible => "~=",
+            Operator::Equal => "==",
+            Operator::NotEqual => "!=",
+            Operator::LessEqual => "<=",
+            Operator::GreaterEqual => ">=",
+            Operator::Less => "<",
+            Operator::Greater => ">",
+            Operator::ArbitraryEqual => "===",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Operator, Specifier, SpecifierSet};
+    use crate::pep440::Version;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    /// 取自 packaging 测试用例的单个说明符匹配结果
+    #[test]
+    fn test_specifier_contains() {
+        let included = [
+            ("2.0", "==2"),
+            ("2.0", "==2.0"),
+            ("2.0", "==2.0.0"),
+            ("2.0+deadbeef", "==2"),
+            ("2.0+deadbeef", "==2.0.*"),
+            ("2.0+deadbeef.0", "==2.0.0+deadbeef.0"),
+            ("2.0", "==2.*"),
+            ("2.0.0", "==2.*"),
+            ("2.0", "==2.0.*"),
+            ("2.0.0", "==2.0.*"),
+            ("2.0.0", "==2.0.0.*"),
+            ("2.1+local.version", "==2.1.*"),
+            ("2.1", "!=2"),
+            ("2.1", "!=2.0.*"),
+            ("2.0", "<=2"),
+            ("2.0", ">=2.0"),
+            ("2.0.post1", ">=2"),
+            ("2.0.post1.dev1", ">=2"),
+            ("3", ">2"),
+            ("2.0.post1", "<2.1"),
+            ("1.0", "<2.0"),
+            ("2.0", "~=2.0"),
+            ("2.1", "~=2.0"),
+            ("2.0.1", "~=2.0.0"),
+            ("2.0.post1", "~=2.0"),
+            ("2.0a1", "~=2.0a1"),
+            ("1!2.0.1", "~=1!2.0"),
+            ("2.1.0b1", "<2.1.0b2"),
+            ("3.0.0a8", ">3.0.0a7"),
+        ];
+        for (version, spec) in included {
+            let spec: Specifier = spec.parse().unwrap();
+            assert!(spec.clone().with_prereleases(true).contains(&v(version)), "{spec} 应当匹配 {version}");
+        }
+
+        let excluded = [
+            ("2.1", "==2"),
+            ("2.1", "==2.0"),
+            ("2.1.0", "==2.0.0"),
+            ("2.0", "==2.0+deadbeef"),
+            ("3.0", "==2.*"),
+            ("2.1", "==2.0.*"),
+            ("2.0", "!=2"),
+            ("2.0.post1", "!=2.0.post1"),
+            ("2.0", "!=2.0.*"),
+            ("2.0+deadbeef", "!=2.0.*"),
+            ("2.1", "<=2"),
+            ("1.0", ">=2.0"),
+            ("2.0.dev1", ">=2"),
+            ("1.0", ">2.0"),
+            ("2.0.post1", ">2"),
+            ("2.0+local.version", ">2"),
+            ("2.0", "<2.0"),
+            ("2.0.dev1", "<2.0"),
+            ("2.1", "<2.0"),
+            ("1.0", "~=2.0"),
+            ("3.0", "~=2.0"),
+            ("2.1", "~=2.0.0"),
+            ("2.0a1", "~=2.0"),
+            ("2.0", "~=1!2.0"),
+        ];
+        for (version, spec) in excluded {
+            let spec: Specifier = spec.parse().unwrap();
+            assert!(!spec.clone().with_prereleases(true).contains(&v(version)), "{spec} 不应当匹配 {version}");
+        }
+    }
+
+    /// 测试说明符隐含的先行版本选择
+    #[test]
+    fn test_prereleases() {
+        assert!(!">=1.0".parse::<Specifier>().unwrap().prereleases());
+        assert!(">=1.0.dev1".parse::<Specifier>().unwrap().prereleases());
+        assert!("==1.0b1".parse::<Specifier>().unwrap().prereleases());
+        assert!(!">1.0b1".parse::<Specifier>().unwrap().prereleases());
+
+        let specs: SpecifierSet = ">=1.0".parse().unwrap();
+        assert!(!specs.contains(&v("2.0b1")));
+        assert!(specs.clone().with_prereleases(true).contains(&v("2.0b1")));
+        assert!(">=1.0,<=2.0rc1".parse::<SpecifierSet>().unwrap().contains(&v("2.0b1")));
+        assert!("".parse::<SpecifierSet>().unwrap().contains(&v("1.0")));
+        assert!(!"".parse::<SpecifierSet>().unwrap().contains(&v("1.0a1")));
+    }
+
+    /// 测试集合的筛选
+    #[test]
+    fn test_filter() {
+        let versions: Vec<Version> = ["1.2", "1.3", "1.3.5", "1.4a1", "2.0"].iter().map(|s| v(s)).collect();
+        let specs: SpecifierSet = ">=1.0,!=1.3.*".parse().unwrap();
+        let matched: Vec<String> = specs.filter(&versions).map(|v| v.to_string()).collect();
+        assert_eq!(matched, ["1.2", "2.0"]);
+
+        let specs: SpecifierSet = ">1.3.5".parse().unwrap();
+        let matched: Vec<String> = specs.filter(&versions[..4]).map(|v| v.to_string()).collect();
+        assert_eq!(matched, ["1.4a1"]);
+        let specs = specs.with_prereleases(false);
+        assert_eq!(specs.filter(&versions[..4]).count(), 0);
+
+        let matched: Vec<Version> = "~=1.3".parse::<SpecifierSet>().unwrap().filter(versions.clone()).collect();
+        assert_eq!(matched, [v("1.3"), v("1.3.5")]);
+    }
+
+    /// 测试 `===` 任意相等比较
+    #[test]
+    fn test_arbitrary_equal() {
+        let spec: Specifier = "===foobar".parse().unwrap();
+        assert_eq!(spec.operator(), Operator::ArbitraryEqual);
+        assert!(spec.contains_str("foobar"));
+        assert!(!spec.contains(&v("1.0")));
+
+        let spec: Specifier = "===1.0".parse().unwrap();
+        assert!(spec.contains(&v("1.0")));
+        assert!(!spec.contains(&v("1.0.0")));
+    }
+
+    /// 测试非法的说明符
+    #[test]
+    fn test_invalid() {
+        for spec in ["1.0", "=>1.0", "~=1", "~=1.0.*", ">=1.0.*", "<1.0+local", "==1.0a1.*", "==1.0.*.*", "== 1.0 .*", "==", "===foo bar"] {
+            assert!(spec.parse::<Specifier>().is_err(), "{spec}");
+        }
+        assert_eq!("~= 3.8".parse::<Specifier>().unwrap().to_string(), "~=3.8");
+        assert_eq!(">=1.0 , != 1.3.*".parse::<SpecifierSet>().unwrap().to_string(), ">=1.0,!=1.3.*");
+    }
+}
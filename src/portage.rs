@@ -0,0 +1,372 @@
+//! Gentoo Portage 软件包版本号, 格式为 `数字{.数字}[字母]{_后缀[数字]}[-r修订号]`
+//!
+//! 比较规则与 PMS (Package Manager Specification) 第 3.3 节一致:
+//! 第一个数字段按数值比较, 之后任意一方以 `0` 开头的数字段去掉末尾的零后按字符串比较;
+//! `_alpha` < `_beta` < `_pre` < `_rc` < 无后缀 < `_p`, 未写的后缀数字与修订号视为 0
+//!
+//! ```
+//! use version::portage::Version;
+//!
+//! let v: Version = "1.2.3b_alpha4_p2-r1".parse().unwrap();
+//! assert_eq!(v.revision(), 1);
+//! assert!(v < "1.2.3b_alpha5".parse().unwrap());
+//! assert!(v > "1.2.3b_alpha4".parse().unwrap());
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 Portage 版本号
+#[derive(Debug, Clone)]
+pub struct Version {
+    numbers: Vec<String>,
+    letter: Option<char>,
+    suffixes: Vec<(Suffix, Option<u64>)>,
+    revision: Option<u64>,
+}
+
+///
+/// 版本号后缀, 按照从旧到新的顺序排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Suffix {
+    /// `_alpha`
+    Alpha,
+    /// `_beta`
+    Beta,
+    /// `_pre`
+    Pre,
+    /// `_rc`
+    Rc,
+    /// `_p`
+    P,
+}
+
+///
+/// 解析 Portage 版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("版本号及 `.` 之后必须是数字")]
+    ExpectedDigit,
+
+    #[error("未知的后缀 \"_{0}\"")]
+    InvalidSuffix(String),
+
+    #[error("修订号必须是 `-r` 之后的数字")]
+    InvalidRevision,
+
+    #[error("数字过大, 不能超过 {}", u64::MAX)]
+    Overflow,
+
+    #[error("版本号中存在意外的字符 '{0}'")]
+    UnexpectedCharacter(char),
+}
+
+impl Suffix {
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Suffix::Alpha => "alpha",
+            Suffix::Beta => "beta",
+            Suffix::Pre => "pre",
+            Suffix::Rc => "rc",
+            Suffix::P => "p",
+        }
+    }
+}
+
+impl Version {
+
+    /// 以 `.` 分隔的数字段, 保留书写时的前导零
+    pub fn numbers(&self) -> &[String] {
+        &self.numbers
+    }
+
+    /// 数字段之后的单个字母
+    pub fn letter(&self) -> Option<char> {
+        self.letter
+    }
+
+    /// 后缀及其数字, 未写数字时为 `None`, 比较时视为 0
+    pub fn suffixes(&self) -> &[(Suffix, Option<u64>)] {
+        &self.suffixes
+    }
+
+    /// 修订号, 未指定时为 0
+    pub fn revision(&self) -> u64 {
+        self.revision.unwrap_or(0)
+    }
+
+    /// 是否带有早于正式版本的后缀
+    pub fn is_prerelease(&self) -> bool {
+        self.suffixes.iter().any(|(suffix, _)| *suffix != Suffix::P)
+    }
+
+    /// 去掉修订号后的版本号, 即 PMS 中的 `PV`
+    pub fn without_revision(&self) -> Version {
+        Version { revision: None, ..self.clone() }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// PMS 算法 3.1 至 3.7
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let numbers = cmp_integer(&self.numbers[0], &other.numbers[0]).then_with(|| {
+            self.numbers[1..].iter().zip(&other.numbers[1..])
+                .map(|(a, b)| {
+                    if a.starts_with('0') || b.starts_with('0') {
+                        a.trim_end_matches('0').cmp(b.trim_end_matches('0'))
+                    } else {
+                        cmp_integer(a, b)
+                    }
+                })
+                .find(|o| o.is_ne())
+                .unwrap_or_else(|| self.numbers.len().cmp(&other.numbers.len()))
+        });
+        numbers
+            .then_with(|| self.letter.cmp(&other.letter))
+            .then_with(|| cmp_suffixes(&self.suffixes, &other.suffixes))
+            .then_with(|| self.revision().cmp(&other.revision()))
+    }
+}
+
+/// 按数值比较任意长度的数字串
+fn cmp_integer(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.trim_start_matches('0'), b.trim_start_matches('0'));
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+/// 逐个比较后缀; 一方的后缀更多时, 多出的第一个后缀为 `_p` 则更新, 否则更旧
+fn cmp_suffixes(a: &[(Suffix, Option<u64>)], b: &[(Suffix, Option<u64>)]) -> Ordering {
+    for (x, y) in a.iter().zip(b) {
+        let ordering = x.0.cmp(&y.0).then_with(|| x.1.unwrap_or(0).cmp(&y.1.unwrap_or(0)));
+        if ordering.is_ne() {
+            return ordering
+        }
+    }
+    match (a.get(b.len()), b.get(a.len())) {
+        (Some((suffix, _)), _) => if *suffix == Suffix::P { Ordering::Greater } else { Ordering::Less },
+        (_, Some((suffix, _))) => if *suffix == Suffix::P { Ordering::Less } else { Ordering::Greater },
+        (None, None) => Ordering::Equal,
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致: 第一个数字段去掉前导零,
+        // 之后以 `0` 开头的数字段去掉末尾的零, 其余数字段原样参与
+        self.numbers[0].trim_start_matches('0').hash(state);
+        for number in &self.numbers[1..] {
+            number.starts_with('0').hash(state);
+            if number.starts_with('0') {
+                number.trim_end_matches('0').hash(state);
+            } else {
+                number.hash(state);
+            }
+        }
+        self.letter.hash(state);
+        for (suffix, number) in &self.suffixes {
+            (suffix, number.unwrap_or(0)).hash(state);
+        }
+        self.revision().hash(state);
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.numbers.join("."))?;
+        if let Some(letter) = self.letter {
+            write!(f, "{letter}")?;
+        }
+        for (suffix, number) in &self.suffixes {
+            write!(f, "_{}", suffix.as_str())?;
+            if let Some(number) = number {
+                write!(f, "{number}")?;
+            }
+        }
+        if let Some(revision) = self.revision {
+            write!(f, "-r{revision}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 Portage 版本号, 接受的格式与 PMS 第 3.2 节一致
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+
+        let (mut rest, revision) = match s.rsplit_once("-r") {
+            Some((rest, revision)) => {
+                if revision.is_empty() || !revision.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseError::InvalidRevision)
+                }
+                (rest, Some(revision.parse().map_err(|_| ParseError::Overflow)?))
+            }
+            None => (s, None),
+        };
+
+        let mut numbers = Vec::new();
+        loop {
+            let len = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+            if len == 0 {
+                return Err(ParseError::ExpectedDigit)
+            }
+            numbers.push(rest[..len].to_string());
+            rest = &rest[len..];
+            match rest.strip_prefix('.') {
+                Some(next) => rest = next,
+                None => break,
+            }
+        }
+
+        let letter = rest.chars().next().filter(char::is_ascii_lowercase);
+        if letter.is_some() {
+            rest = &rest[1..];
+        }
+
+        let mut suffixes = Vec::new();
+        while let Some(next) = rest.strip_prefix('_') {
+            let name_len = next.bytes().take_while(u8::is_ascii_lowercase).count();
+            let suffix = match &next[..name_len] {
+                "alpha" => Suffix::Alpha,
+                "beta" => Suffix::Beta,
+                "pre" => Suffix::Pre,
+                "rc" => Suffix::Rc,
+                "p" => Suffix::P,
+                name => return Err(ParseError::InvalidSuffix(name.to_string())),
+            };
+            let next = &next[name_len..];
+            let digits = next.bytes().take_while(|b| b.is_ascii_digit()).count();
+            let number = match digits {
+                0 => None,
+                _ => Some(next[..digits].parse().map_err(|_| ParseError::Overflow)?),
+            };
+            suffixes.push((suffix, number));
+            rest = &next[digits..];
+        }
+
+        if let Some(c) = rest.chars().next() {
+            return Err(ParseError::UnexpectedCharacter(c))
+        }
+        Ok(Version { numbers, letter, suffixes, revision })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Suffix, Version};
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 比较结果取自 Portage 的 test_vercmp.py
+    #[test]
+    fn test_compare_table() {
+        let cases = [
+            ("6.0", "5.0", Ordering::Greater),
+            ("5.0", "5", Ordering::Greater),
+            ("1.0-r1", "1.0-r0", Ordering::Greater),
+            ("1.0-r1", "1.0", Ordering::Greater),
+            ("999999999999999999999999999999", "999999999999999999999999999998", Ordering::Greater),
+            ("1.0.0", "1.0", Ordering::Greater),
+            ("1.0.0", "1.0b", Ordering::Greater),
+            ("1b", "1", Ordering::Greater),
+            ("1b_p1", "1_p1", Ordering::Greater),
+            ("1.1b", "1.1", Ordering::Greater),
+            ("12.2.5", "12.2b", Ordering::Greater),
+            ("4.0", "4.0", Ordering::Equal),
+            ("1.0-r0", "1.0", Ordering::Equal),
+            ("1.0_p", "1.0_p0", Ordering::Equal),
+            ("1.010", "1.01", Ordering::Equal),
+            ("1.0_pre2", "1.0_p2", Ordering::Less),
+            ("1.0_alpha2", "1.0_p2", Ordering::Less),
+            ("1.0_alpha1", "1.0_beta1", Ordering::Less),
+            ("1.0_beta3", "1.0_rc3", Ordering::Less),
+            ("1.001000000000000000001", "1.001000000000000000002", Ordering::Less),
+            ("1.00100000000", "1.0010000000000000001", Ordering::Less),
+            ("1.01", "1.1", Ordering::Less),
+            ("1.0_alpha_p", "1.0_alpha", Ordering::Greater),
+            ("1.0_alpha_beta", "1.0_alpha", Ordering::Less),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(v(a).cmp(&v(b)), expected, "{a} <=> {b}");
+            assert_eq!(v(b).cmp(&v(a)), expected.reverse(), "{b} <=> {a}");
+        }
+    }
+
+    /// 测试各个组成部分与输出
+    #[test]
+    fn test_components() {
+        let ordered = ["1.0_alpha", "1.0_beta", "1.0_pre", "1.0_rc", "1.0", "1.0_p1", "1.0a", "1.0.1"];
+        for pair in ordered.windows(2) {
+            assert!(v(pair[0]) < v(pair[1]), "{} < {}", pair[0], pair[1]);
+        }
+        let version = v("1.2.3b_alpha4_p2-r1");
+        assert_eq!(version.numbers(), ["1", "2", "3"]);
+        assert_eq!(version.letter(), Some('b'));
+        assert_eq!(version.suffixes(), [(Suffix::Alpha, Some(4)), (Suffix::P, Some(2))]);
+        assert!(version.is_prerelease());
+        assert_eq!(version.without_revision().to_string(), "1.2.3b_alpha4_p2");
+        assert_eq!(version.to_string(), "1.2.3b_alpha4_p2-r1");
+
+        let set: HashSet<Version> = ["1.010_p-r0", "01.01_p0", "1.0100_p"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    /// 测试解析错误
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            ("a1", ParseError::ExpectedDigit),
+            ("1..2", ParseError::ExpectedDigit),
+            ("1.2_git", ParseError::InvalidSuffix("git".to_string())),
+            ("1.2-r", ParseError::InvalidRevision),
+            ("1.2-r1a", ParseError::InvalidRevision),
+            ("1.2_p99999999999999999999", ParseError::Overflow),
+            ("1.2B", ParseError::UnexpectedCharacter('B')),
+            ("1.2~1", ParseError::UnexpectedCharacter('~')),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+    }
+}
@@ -0,0 +1,524 @@
+//! Cargo 风格的版本需求
+//!
+//! 支持 `^1.2`、`~1.2.3`、`>=1.0, <2.0`、`1.*`、`=1.2.3` 以及不带运算符的 `1.2` 等写法,
+//! 匹配规则与 Cargo 保持一致
+
+use crate::{cmp_pre, parse_build, parse_number, parse_pre, Component, Identifier, ParseError, Version};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 Cargo 风格的版本需求, 由若干个以 `,` 分隔的比较器组成
+///
+/// 所有比较器均满足时版本才满足需求。
+/// 带有先行版本号的版本只有在某个比较器同样带有先行版本号,
+/// 且主版本号、副版本号、补丁版本号完全相同时才可能满足需求
+///
+/// ```
+/// use version::{Version, VersionReq};
+///
+/// let req: VersionReq = ">=1.2.0, <1.8.0".parse().unwrap();
+/// assert!(req.matches(&"1.5.3".parse::<Version>().unwrap()));
+/// assert!(!req.matches(&"1.8.0".parse::<Version>().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct VersionReq {
+    comparators: Vec<Comparator>,
+}
+
+///
+/// 版本需求中的单个比较器, 例如 `>=1.2` 或 `~1.2.3-beta`
+///
+/// 缺省的副版本号与补丁版本号以 `None` 表示
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Comparator {
+    op: Op,
+    major: u64,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Vec<Identifier>,
+}
+
+///
+/// 比较器的运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `=1.2.3`
+    Exact,
+    /// `>1.2.3`
+    Greater,
+    /// `>=1.2.3`
+    GreaterEq,
+    /// `<1.2.3`
+    Less,
+    /// `<=1.2.3`
+    LessEq,
+    /// `~1.2.3`
+    Tilde,
+    /// `^1.2.3`, 不带运算符时的默认值
+    Caret,
+    /// `1.*` 或 `1.2.*`
+    Wildcard,
+}
+
+///
+/// 解析版本需求时可能发生的错误
+#[derive(Error, Debug)]
+pub enum ReqParseError {
+
+    #[error("版本号解析失败: {0}")]
+    Version(#[from] ParseError),
+
+    #[error("存在空的比较器")]
+    EmptyComparator,
+
+    #[error("非法的运算符 \"{0}\"")]
+    InvalidOp(String),
+
+    #[error("比较器中的版本号最多包含三个部分")]
+    TooManyComponents,
+
+    #[error("通配符 `*` 之后不能再出现具体的版本号")]
+    UnexpectedAfterWildcard,
+
+    #[error("`*` 不能与其他比较器同时使用")]
+    UnexpectedStar,
+
+    #[error("只有完整的 主.副.补丁 版本号才能带有先行版本号")]
+    PrereleaseOnPartial,
+}
+
+impl VersionReq {
+
+    /// 匹配任意正式版本的需求, 等价于 `*`
+    pub const STAR: VersionReq = VersionReq { comparators: Vec::new() };
+
+    /// 判断版本号是否满足此需求
+    ///
+    /// # 参数
+    /// - `version` - 要判断的版本号
+    ///
+    /// # 示例
+    /// ```
+    /// use version::{Version, VersionReq};
+    ///
+    /// let req: VersionReq = "^1.2.3".parse().unwrap();
+    /// assert!(req.matches(&"1.9.0".parse::<Version>().unwrap()));
+    /// assert!(!req.matches(&"2.0.0".parse::<Version>().unwrap()));
+    /// // 先行版本需要显式选择
+    /// assert!(!req.matches(&"1.9.0-rc.1".parse::<Version>().unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        if !self.comparators.iter().all(|cmp| cmp.matches_impl(version)) {
+            return false
+        }
+        if version.pre.is_empty() {
+            return true
+        }
+        // 先行版本只有在某个比较器针对同一个 主.副.补丁 版本号并带有先行版本号时才会匹配
+        self.comparators.iter().any(|cmp| cmp.pre_is_compatible(version))
+    }
+
+    /// 组成需求的比较器列表
+    pub fn comparators(&self) -> &[Comparator] {
+        &self.comparators
+    }
+}
+
+impl Default for VersionReq {
+    fn default() -> Self {
+        VersionReq::STAR
+    }
+}
+
+impl Comparator {
+
+    /// 判断版本号是否满足此比较器, 不考虑先行版本的选择规则
+    ///
+    /// # 示例
+    /// ```
+    /// use version::{Comparator, Version};
+    ///
+    /// let cmp: Comparator = "~1.2".parse().unwrap();
+    /// assert!(cmp.matches(&"1.2.9".parse::<Version>().unwrap()));
+    /// assert!(!cmp.matches(&"1.3.0".parse::<Version>().unwrap()));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        self.matches_impl(version) && (version.pre.is_empty() || self.pre_is_compatible(version))
+    }
+
+    /// 运算符
+    pub fn op(&self) -> Op {
+        self.op
+    }
+
+    /// 主版本号
+    pub fn major(&self) -> u64 {
+        self.major
+    }
+
+    /// 副版本号, 缺省时为 `None`
+    pub fn minor(&self) -> Option<u64> {
+        self.minor
+    }
+
+    /// 补丁版本号, 缺省时为 `None`
+    pub fn patch(&self) -> Option<u64> {
+        self.patch
+    }
+
+    /// 先行版本号, 不存在时为空
+    pub fn pre(&self) -> &[Identifier] {
+        &self.pre
+    }
+
+    fn matches_impl(&self, version: &Version) -> bool {
+        match self.op {
+            Op::Exact | Op::Wildcard => self.matches_exact(version),
+            Op::Greater => self.matches_greater(version),
+            Op::GreaterEq => !self.matches_less(version),
+            Op::Less => self.matches_less(version),
+            Op::LessEq => !self.matches_greater(version),
+            Op::Tilde => self.matches_tilde(version),
+            Op::Caret => self.matches_caret(version),
+        }
+    }
+
+    fn matches_exact(&self, version: &Version) -> bool {
+        version.major == self.major
+            && self.minor.is_none_or(|minor| version.minor == minor)
+            && self.patch.is_none_or(|patch| version.patch == patch)
+            && (self.op == Op::Wildcard || version.pre == self.pre)
+    }
+
+    fn matches_greater(&self, version: &Version) -> bool {
+        if version.major != self.major {
+            return version.major > self.major
+        }
+        // 缺省的部分视为通配, 例如 `>1` 表示 `>=2.0.0`
+        let Some(minor) = self.minor else { return false };
+        if version.minor != minor {
+            return version.minor > minor
+        }
+        let Some(patch) = self.patch else { return false };
+        if version.patch != patch {
+            return version.patch > patch
+        }
+        cmp_pre(&version.pre, &self.pre).is_gt()
+    }
+
+    fn matches_less(&self, version: &Version) -> bool {
+        if version.major != self.major {
+            return version.major < self.major
+        }
+        let Some(minor) = self.minor else { return false };
+        if version.minor != minor {
+            return version.minor < minor
+        }
+        let Some(patch) = self.patch else { return false };
+        if version.patch != patch {
+            return version.patch < patch
+        }
+        cmp_pre(&version.pre, &self.pre).is_lt()
+    }
+
+    fn matches_tilde(&self, version: &Version) -> bool {
+        // ~1.2.3 := >=1.2.3, <1.3.0; ~1.2 := >=1.2.0, <1.3.0; ~1 := >=1.0.0, <2.0.0
+        if version.major != self.major {
+            return false
+        }
+        if self.minor.is_some_and(|minor| version.minor != minor) {
+            return false
+        }
+        if let Some(patch) = self.patch
+            && version.patch != patch
+        {
+            return version.patch > patch
+        }
+        cmp_pre(&version.pre, &self.pre).is_ge()
+    }
+
+    fn matches_caret(&self, version: &Version) -> bool {
+        // 从左往右第一个非零的部分不能改变
+        if version.major != self.major {
+            return false
+        }
+        let Some(minor) = self.minor else { return true };
+        let Some(patch) = self.patch else {
+            return if self.major > 0 { version.minor >= minor } else { version.minor == minor }
+        };
+
+        if self.major > 0 {
+            if version.minor != minor {
+                return version.minor > minor
+            } else if version.patch != patch {
+                return version.patch > patch
+            }
+        } else if minor > 0 {
+            if version.minor != minor {
+                return false
+            } else if version.patch != patch {
+                return version.patch > patch
+            }
+        } else if version.minor != minor || version.patch != patch {
+            return false
+        }
+        cmp_pre(&version.pre, &self.pre).is_ge()
+    }
+
+    fn pre_is_compatible(&self, version: &Version) -> bool {
+        self.major == version.major
+            && self.minor == Some(version.minor)
+            && self.patch == Some(version.patch)
+            && !self.pre.is_empty()
+    }
+}
+
+/// 解析版本需求字符串
+///
+/// # 参数
+/// `s` - 以 `,` 分隔的比较器, 每个比较器由可选的运算符与 1 到 3 个部分的版本号组成,
+/// 不带运算符时视为 `^`, `*`、`x`、`X` 表示通配
+///
+/// # 示例
+/// ```
+/// use version::VersionReq;
+///
+/// let req: VersionReq = ">= 1.0, < 2.0".parse().unwrap();
+/// assert_eq!(req.to_string(), ">=1.0, <2.0");
+/// assert_eq!("1.x".parse::<VersionReq>().unwrap().to_string(), "1.*");
+/// assert_eq!("*".parse::<VersionReq>().unwrap(), VersionReq::STAR);
+/// ```
+impl FromStr for VersionReq {
+    type Err = ReqParseError;
+
+    fn from_str(s: &str) -> Result<VersionReq, ReqParseError> {
+        let s = s.trim();
+        if s == "*" {
+            return Ok(VersionReq::STAR)
+        }
+        let comparators = s.split(',')
+            .map(|part| {
+                if is_star(part.trim()) {
+                    return Err(ReqParseError::UnexpectedStar)
+                }
+                part.parse::<Comparator>()
+            })
+            .collect::<Result<Vec<Comparator>, ReqParseError>>()?;
+        Ok(VersionReq { comparators })
+    }
+}
+
+impl FromStr for Comparator {
+    type Err = ReqParseError;
+
+    fn from_str(s: &str) -> Result<Comparator, ReqParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ReqParseError::EmptyComparator)
+        }
+
+        // 解析运算符
+        let op_len = s.find(|c: char| c.is_ascii_alphanumeric() || c == '*').unwrap_or(s.len());
+        let (op, rest) = s.split_at(op_len);
+        let op = match op.trim() {
+            "" => None,
+            "=" => Some(Op::Exact),
+            ">" => Some(Op::Greater),
+            ">=" => Some(Op::GreaterEq),
+            "<" => Some(Op::Less),
+            "<=" => Some(Op::LessEq),
+            "~" => Some(Op::Tilde),
+            "^" => Some(Op::Caret),
+            other => return Err(ReqParseError::InvalidOp(other.to_string())),
+        };
+
+        // 编译信息不参与匹配, 直接忽略
+        let rest = match rest.split_once('+') {
+            Some((rest, build)) => {
+                parse_build(build)?;
+                rest
+            }
+            None => rest,
+        };
+        let (core, pre) = match rest.split_once('-') {
+            Some((core, pre)) => (core, parse_pre(pre)?),
+            None => (rest, Vec::new()),
+        };
+
+        let parts: Vec<&str> = core.split('.').collect();
+        if parts.len() > 3 {
+            return Err(ReqParseError::TooManyComponents)
+        }
+        let components = [Component::Major, Component::Minor, Component::Patch];
+        let mut numbers: Vec<u64> = Vec::with_capacity(3);
+        let mut wildcard = false;
+        for (part, component) in parts.iter().zip(components) {
+            if is_star(part) {
+                wildcard = true;
+            } else if wildcard {
+                return Err(ReqParseError::UnexpectedAfterWildcard)
+            } else {
+                numbers.push(parse_number(part, component)?);
+            }
+        }
+        if numbers.is_empty() {
+            // 只有通配符的比较器只能单独出现, 在 VersionReq 中处理
+            return Err(ReqParseError::UnexpectedStar)
+        }
+        if !pre.is_empty() && numbers.len() < 3 {
+            return Err(ReqParseError::PrereleaseOnPartial)
+        }
+
+        // 不带运算符时, 含通配符的为 Wildcard, 否则为 Caret
+        let op = match op {
+            Some(op) => op,
+            None if wildcard => Op::Wildcard,
+            None => Op::Caret,
+        };
+        Ok(Comparator {
+            op,
+            major: numbers[0],
+            minor: numbers.get(1).copied(),
+            patch: numbers.get(2).copied(),
+            pre,
+        })
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.comparators.is_empty() {
+            return f.write_str("*")
+        }
+        for (i, cmp) in self.comparators.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{cmp}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self.op {
+            Op::Exact => "=",
+            Op::Greater => ">",
+            Op::GreaterEq => ">=",
+            Op::Less => "<",
+            Op::LessEq => "<=",
+            Op::Tilde => "~",
+            Op::Caret => "^",
+            Op::Wildcard => "",
+        })?;
+        write!(f, "{}", self.major)?;
+        match self.minor {
+            Some(minor) => write!(f, ".{minor}")?,
+            None if self.op == Op::Wildcard => return f.write_str(".*"),
+            None => return Ok(()),
+        }
+        match self.patch {
+            Some(patch) => write!(f, ".{patch}")?,
+            None if self.op == Op::Wildcard => return f.write_str(".*"),
+            None => return Ok(()),
+        }
+        for (i, identifier) in self.pre.iter().enumerate() {
+            f.write_str(if i == 0 { "-" } else { "." })?;
+            write!(f, "{identifier}")?;
+        }
+        Ok(())
+    }
+}
+
+/// 判断是否为通配符 `*`、`x` 或 `X`
+fn is_star(s: &str) -> bool {
+    matches!(s, "*" | "x" | "X")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Op, ReqParseError, Version, VersionReq};
+
+    fn req(s: &str) -> VersionReq {
+        s.parse().unwrap()
+    }
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    fn assert_match(r: &str, yes: &[&str], no: &[&str]) {
+        let r = req(r);
+        for s in yes {
+            assert!(r.matches(&v(s)), "{r} 应当匹配 {s}");
+        }
+        for s in no {
+            assert!(!r.matches(&v(s)), "{r} 不应当匹配 {s}");
+        }
+    }
+
+    /// 测试 ^ 运算符与不带运算符的写法
+    #[test]
+    fn test_caret() {
+        assert_match("^1.2.3", &["1.2.3", "1.2.4", "1.9.0"], &["1.2.2", "2.0.0", "1.2.4-rc.1"]);
+        assert_match("1.2", &["1.2.0", "1.9.9"], &["1.1.9", "2.0.0"]);
+        assert_match("^0.2.3", &["0.2.3", "0.2.9"], &["0.3.0", "0.2.2"]);
+        assert_match("^0.0.3", &["0.0.3"], &["0.0.4", "0.1.0"]);
+        assert_match("^0.0", &["0.0.0", "0.0.9"], &["0.1.0"]);
+        assert_match("^0", &["0.0.1", "0.9.9"], &["1.0.0"]);
+    }
+
+    /// 测试 ~ 运算符
+    #[test]
+    fn test_tilde() {
+        assert_match("~1.2.3", &["1.2.3", "1.2.9"], &["1.3.0", "1.2.2"]);
+        assert_match("~1.2", &["1.2.0", "1.2.9"], &["1.3.0", "1.1.0"]);
+        assert_match("~1", &["1.0.0", "1.9.9"], &["2.0.0", "0.9.0"]);
+    }
+
+    /// 测试比较运算符与多个比较器
+    #[test]
+    fn test_comparison() {
+        assert_match(">=1.0, <2.0", &["1.0.0", "1.9.9"], &["0.9.9", "2.0.0"]);
+        assert_match(">1", &["2.0.0"], &["1.9.9"]);
+        assert_match("<=1.2", &["1.2.9", "0.1.0"], &["1.3.0"]);
+        assert_match("=1.2.3", &["1.2.3"], &["1.2.4"]);
+        assert_match("=1.2", &["1.2.0", "1.2.7"], &["1.3.0"]);
+    }
+
+    /// 测试通配符
+    #[test]
+    fn test_wildcard() {
+        assert_match("1.*", &["1.0.0", "1.9.9"], &["2.0.0", "1.0.0-rc.1"]);
+        assert_match("1.2.x", &["1.2.0", "1.2.9"], &["1.3.0"]);
+        assert_match("*", &["0.0.1", "99.0.0"], &["1.0.0-alpha"]);
+        assert_eq!(req("1.*").comparators()[0].op(), Op::Wildcard);
+    }
+
+    /// 测试先行版本需要显式选择
+    #[test]
+    fn test_prerelease_opt_in() {
+        assert_match(
+            ">=1.2.3-alpha.3",
+            &["1.2.3-alpha.3", "1.2.3-alpha.7", "1.2.3", "3.4.5"],
+            &["1.2.3-alpha.2", "3.4.5-alpha.9"],
+        );
+        assert_match("<1.0.0", &["0.9.0"], &["1.0.0-rc.1"]);
+    }
+
+    /// 测试非法的版本需求
+    #[test]
+    fn test_invalid() {
+        assert!(matches!("".parse::<VersionReq>(), Err(ReqParseError::EmptyComparator)));
+        assert!(matches!(">=1.0,".parse::<VersionReq>(), Err(ReqParseError::EmptyComparator)));
+        assert!(matches!("!1.0".parse::<VersionReq>(), Err(ReqParseError::InvalidOp(_))));
+        assert!(matches!("1.2.3.4".parse::<VersionReq>(), Err(ReqParseError::TooManyComponents)));
+        assert!(matches!("1.*.3".parse::<VersionReq>(), Err(ReqParseError::UnexpectedAfterWildcard)));
+        assert!(matches!("*, <2".parse::<VersionReq>(), Err(ReqParseError::UnexpectedStar)));
+        assert!(matches!("^1.2-beta".parse::<VersionReq>(), Err(ReqParseError::PrereleaseOnPartial)));
+        assert!(matches!("^1.y".parse::<VersionReq>(), Err(ReqParseError::Version(_))));
+    }
+}
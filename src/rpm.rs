@@ -0,0 +1,398 @@
+//! RPM 软件包版本号, 格式为 `[epoch:]version[-release]`
+//!
+//! 版本与发布号都按照 rpm 的 `rpmvercmp` 算法比较:
+//! 字母与数字分段比较, 数字段总是比字母段新, `~` 比一切都旧, `^` 比一切都新(但比更长的数字段旧),
+//! 其余符号只作为分隔符
+//!
+//! ```
+//! use version::rpm::Version;
+//!
+//! let installed: Version = "1:2.0~rc1-3.fc40".parse().unwrap();
+//! let candidate: Version = "1:2.0-1.fc40".parse().unwrap();
+//! // 只有更新的版本才会被当作升级
+//! assert!(candidate > installed);
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 RPM 版本号
+///
+/// 未指定纪元时视为 0, 未指定发布号时视为空字符串, 因此 `1.0` 旧于 `1.0-1`
+#[derive(Debug, Clone)]
+pub struct Version {
+    epoch: u32,
+    version: String,
+    release: String,
+}
+
+///
+/// 解析 RPM 版本号时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("版本号为空")]
+    Empty,
+
+    #[error("纪元必须是不超过 {} 的数字", u32::MAX)]
+    InvalidEpoch,
+
+    #[error("版本部分为空")]
+    VersionEmpty,
+
+    #[error("发布号为空")]
+    ReleaseEmpty,
+
+    #[error("版本号中存在非法字符 '{0}'")]
+    InvalidCharacter(char),
+}
+
+impl Version {
+
+    /// 纪元, 未指定时为 0
+    pub fn epoch(&self) -> u32 {
+        self.epoch
+    }
+
+    /// 版本部分
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// 发布号, 未指定时为空字符串
+    pub fn release(&self) -> &str {
+        &self.release
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.epoch.cmp(&other.epoch)
+            .then_with(|| rpmvercmp(&self.version, &other.version))
+            .then_with(|| rpmvercmp(&self.release, &other.release))
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        // 与 Eq 保持一致: 忽略分隔符与数字的前导零
+        self.epoch.hash(state);
+        segments(&self.version).hash(state);
+        segments(&self.release).hash(state);
+    }
+}
+
+/// 以 `[epoch:]version[-release]` 形式输出, 纪元为 0 或发布号为空时省略
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.epoch != 0 {
+            write!(f, "{}:", self.epoch)?;
+        }
+        f.write_str(&self.version)?;
+        if !self.release.is_empty() {
+            write!(f, "-{}", self.release)?;
+        }
+        Ok(())
+    }
+}
+
+/// 解析 RPM 版本号
+///
+/// 第一个 `:` 之前为纪元, 最后一个 `-` 之后为发布号,
+/// 版本与发布号只能包含字母数字与 `._+~^`
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(ParseError::Empty)
+        }
+
+        let (epoch, rest) = match s.split_once(':') {
+            Some((epoch, rest)) => {
+                if epoch.is_empty() || !epoch.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(ParseError::InvalidEpoch)
+                }
+                (epoch.parse().map_err(|_| ParseError::InvalidEpoch)?, rest)
+            }
+            None => (0, s),
+        };
+
+        let (version, release) = match rest.rsplit_once('-') {
+            Some((_, "")) => return Err(ParseError::ReleaseEmpty),
+            Some((version, release)) => (version, release),
+            None => (rest, ""),
+        };
+        if version.is_empty() {
+            return Err(ParseError::VersionEmpty)
+        }
+        if let Some(c) = version.chars().chain(release.chars()).find(|c| !c.is_ascii_alphanumeric() && !"._+~^".contains(*c)) {
+            return Err(ParseError::InvalidCharacter(c))
+        }
+
+        Ok(Version {
+            epoch,
+            version: version.to_string(),
+            release: release.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+///
+/// rpm 的 `rpmvercmp`, 比较单独的版本或发布号字符串
+///
+/// ```
+/// use std::cmp::Ordering;
+/// use version::rpm::rpmvercmp;
+///
+/// assert_eq!(rpmvercmp("1.0~rc1", "1.0"), Ordering::Less);
+/// assert_eq!(rpmvercmp("1.0^git1", "1.0"), Ordering::Greater);
+/// assert_eq!(rpmvercmp("2.0", "2_0"), Ordering::Equal);
+/// ```
+pub fn rpmvercmp(a: &str, b: &str) -> Ordering {
+    if a == b {
+        return Ordering::Equal
+    }
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let (mut i, mut j) = (0, 0);
+    let is_sep = |c: u8| !c.is_ascii_alphanumeric() && c != b'~' && c != b'^';
+
+    while i < a.len() || j < b.len() {
+        while i < a.len() && is_sep(a[i]) {
+            i += 1;
+        }
+        while j < b.len() && is_sep(b[j]) {
+            j += 1;
+        }
+        let (one, two) = (a.get(i).copied(), b.get(j).copied());
+
+        // `~` 排在一切之前
+        if one == Some(b'~') || two == Some(b'~') {
+            if one != Some(b'~') {
+                return Ordering::Greater
+            }
+            if two != Some(b'~') {
+                return Ordering::Less
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        // `^` 与 `~` 类似, 但任何一方结束时, 另一方更新
+        if one == Some(b'^') || two == Some(b'^') {
+            if one.is_none() {
+                return Ordering::Less
+            }
+            if two.is_none() {
+                return Ordering::Greater
+            }
+            if one != Some(b'^') {
+                return Ordering::Greater
+            }
+            if two != Some(b'^') {
+                return Ordering::Less
+            }
+            i += 1;
+            j += 1;
+            continue;
+        }
+
+        let (Some(first), Some(_)) = (one, two) else {
+            break
+        };
+
+        // 取出完整的数字段或字母段
+        let is_num = first.is_ascii_digit();
+        let class = |c: &u8| if is_num { c.is_ascii_digit() } else { c.is_ascii_alphabetic() };
+        let seg_a = &a[i..i + a[i..].iter().take_while(|c| class(c)).count()];
+        let seg_b = &b[j..j + b[j..].iter().take_while(|c| class(c)).count()];
+        i += seg_a.len();
+        j += seg_b.len();
+
+        // 类型不同时数字段更新
+        if seg_b.is_empty() {
+            return if is_num { Ordering::Greater } else { Ordering::Less }
+        }
+
+        let ordering = if is_num {
+            let seg_a = trim_zeros(seg_a);
+            let seg_b = trim_zeros(seg_b);
+            seg_a.len().cmp(&seg_b.len()).then_with(|| seg_a.cmp(seg_b))
+        } else {
+            seg_a.cmp(seg_b)
+        };
+        if ordering != Ordering::Equal {
+            return ordering
+        }
+    }
+
+    // 所有段都相同时, 还有剩余内容的一方更新
+    match (i >= a.len(), j >= b.len()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Less,
+        (false, _) => Ordering::Greater,
+    }
+}
+
+fn trim_zeros(s: &[u8]) -> &[u8] {
+    let zeros = s.iter().take_while(|c| **c == b'0').count();
+    &s[zeros..]
+}
+
+/// 参与比较的分段, 用于生成与 `rpmvercmp` 相等关系一致的哈希
+#[derive(Hash)]
+enum Segment<'a> {
+    Tilde,
+    Caret,
+    Numeric(&'a [u8]),
+    Alpha(&'a [u8]),
+}
+
+fn segments(s: &str) -> Vec<Segment<'_>> {
+    let s = s.as_bytes();
+    let mut segments = Vec::new();
+    let mut i = 0;
+    while i < s.len() {
+        let c = s[i];
+        if c == b'~' {
+            segments.push(Segment::Tilde);
+            i += 1;
+        } else if c == b'^' {
+            segments.push(Segment::Caret);
+            i += 1;
+        } else if c.is_ascii_digit() {
+            let len = s[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+            segments.push(Segment::Numeric(trim_zeros(&s[i..i + len])));
+            i += len;
+        } else if c.is_ascii_alphabetic() {
+            let len = s[i..].iter().take_while(|c| c.is_ascii_alphabetic()).count();
+            segments.push(Segment::Alpha(&s[i..i + len]));
+            i += len;
+        } else {
+            i += 1;
+        }
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{rpmvercmp, ParseError, Version};
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 比较结果取自 rpm 的 tests/rpmvercmp.at
+    #[test]
+    fn test_rpmvercmp_table() {
+        let cases = [
+            ("1.0", "1.0", 0), ("1.0", "2.0", -1), ("2.0", "1.0", 1),
+            ("2.0.1", "2.0.1", 0), ("2.0", "2.0.1", -1), ("2.0.1", "2.0", 1),
+            ("2.0.1a", "2.0.1a", 0), ("2.0.1a", "2.0.1", 1), ("2.0.1", "2.0.1a", -1),
+            ("5.5p1", "5.5p1", 0), ("5.5p1", "5.5p2", -1), ("5.5p2", "5.5p1", 1),
+            ("5.5p10", "5.5p10", 0), ("5.5p1", "5.5p10", -1), ("5.5p10", "5.5p1", 1),
+            ("10xyz", "10.1xyz", -1), ("10.1xyz", "10xyz", 1),
+            ("xyz10", "xyz10", 0), ("xyz10", "xyz10.1", -1), ("xyz10.1", "xyz10", 1),
+            ("xyz.4", "xyz.4", 0), ("xyz.4", "8", -1), ("8", "xyz.4", 1),
+            ("xyz.4", "2", -1), ("2", "xyz.4", 1),
+            ("5.5p2", "5.6p1", -1), ("5.6p1", "5.5p2", 1),
+            ("5.6p1", "6.5p1", -1), ("6.5p1", "5.6p1", 1),
+            ("6.0.rc1", "6.0", 1), ("6.0", "6.0.rc1", -1),
+            ("10b2", "10a1", 1), ("10a2", "10b2", -1),
+            ("1.0aa", "1.0aa", 0), ("1.0a", "1.0aa", -1), ("1.0aa", "1.0a", 1),
+            ("10.0001", "10.0001", 0), ("10.0001", "10.1", 0), ("10.1", "10.0001", 0),
+            ("10.0001", "10.0039", -1), ("10.0039", "10.0001", 1),
+            ("4.999.9", "5.0", -1), ("5.0", "4.999.9", 1),
+            ("20101121", "20101121", 0), ("20101121", "20101122", -1), ("20101122", "20101121", 1),
+            ("2_0", "2_0", 0), ("2.0", "2_0", 0), ("2_0", "2.0", 0),
+            ("a", "a", 0), ("a+", "a+", 0), ("a+", "a_", 0), ("a_", "a+", 0),
+            ("+a", "+a", 0), ("+a", "_a", 0), ("_a", "+a", 0),
+            ("+_", "+_", 0), ("_+", "+_", 0), ("_+", "_", 0), ("+", "_", 0), ("_", "+", 0),
+            ("1.0~rc1", "1.0~rc1", 0), ("1.0~rc1", "1.0", -1), ("1.0", "1.0~rc1", 1),
+            ("1.0~rc1", "1.0~rc2", -1), ("1.0~rc2", "1.0~rc1", 1),
+            ("1.0~rc1~git123", "1.0~rc1~git123", 0), ("1.0~rc1~git123", "1.0~rc1", -1),
+            ("1.0~rc1", "1.0~rc1~git123", 1),
+            ("1.0^", "1.0^", 0), ("1.0^", "1.0", 1), ("1.0", "1.0^", -1),
+            ("1.0^git1", "1.0^git1", 0), ("1.0^git1", "1.0", 1), ("1.0", "1.0^git1", -1),
+            ("1.0^git1", "1.0^git2", -1), ("1.0^git2", "1.0^git1", 1),
+            ("1.0^git1", "1.01", -1), ("1.01", "1.0^git1", 1),
+            ("1.0^20160101", "1.0^20160101", 0), ("1.0^20160101", "1.0.1", -1),
+            ("1.0.1", "1.0^20160101", 1),
+            ("1.0^20160101^git1", "1.0^20160101^git1", 0),
+            ("1.0^20160102", "1.0^20160101^git1", 1), ("1.0^20160101^git1", "1.0^20160102", -1),
+            ("1.0~rc1^git1", "1.0~rc1^git1", 0), ("1.0~rc1^git1", "1.0~rc1", 1),
+            ("1.0~rc1", "1.0~rc1^git1", -1),
+            ("1.0^git1~pre", "1.0^git1~pre", 0), ("1.0^git1", "1.0^git1~pre", 1),
+            ("1.0^git1~pre", "1.0^git1", -1),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(rpmvercmp(a, b), expected.cmp(&0), "{a} <=> {b}");
+        }
+    }
+
+    /// 测试纪元与发布号参与比较
+    #[test]
+    fn test_evr() {
+        let v1 = v("1.0-1");
+        assert_eq!((v1.epoch(), v1.version(), v1.release()), (0, "1.0", "1"));
+        assert!(v("1:0.1-1") > v("9.9-9"));
+        assert!(v("1.0-2.el9") > v("1.0-1.el9"));
+        assert!(v("1.0-1") > v("1.0"));
+        assert!(v("1.0~rc1-5") < v("1.0-1"));
+        assert_eq!(v("0:1.0-1"), v("1.0-1"));
+        assert_eq!(v("0:1.0-1").to_string(), "1.0-1");
+        assert_eq!(v("3:1.0^git1-1.fc40").to_string(), "3:1.0^git1-1.fc40");
+
+        let set: HashSet<Version> = ["1.0-1", "1_0-01", "1.00-1", "0:1..0-1"].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    /// 测试解析错误
+    #[test]
+    fn test_parse_errors() {
+        let cases = [
+            ("", ParseError::Empty),
+            (":1.0", ParseError::InvalidEpoch),
+            ("x:1.0", ParseError::InvalidEpoch),
+            ("99999999999:1.0", ParseError::InvalidEpoch),
+            ("1:", ParseError::VersionEmpty),
+            ("-1", ParseError::VersionEmpty),
+            ("1.0-", ParseError::ReleaseEmpty),
+            ("1.0 2", ParseError::InvalidCharacter(' ')),
+            ("1.0-1-2", ParseError::InvalidCharacter('-')),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<Version>().unwrap_err(), expected, "{input}");
+        }
+    }
+}
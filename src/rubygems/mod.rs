@@ -0,0 +1,332 @@
+//! RubyGems 使用的版本号, 规则与 `Gem::Version` 一致
+//!
+//! 版本号由 `.` 分隔的数字与字母段组成, 数字与字母相连时也会拆成不同的段,
+//! 任何字母都会使版本号成为先行版本, 例如 `2.7.0.rc1`、`1.0.a`。
+//! `-` 等同于 `.pre.`, 因此 `1.0-1` 规范化为 `1.0.pre.1`。
+//! [`Requirement`] 用于判断版本号是否满足 `~> 1.4`、`>= 1.0, != 1.3` 这样的需求
+//!
+//! ```
+//! use version::rubygems::Version;
+//!
+//! let v = |s: &str| s.parse::<Version>().unwrap();
+//! assert!(v("2.7.0.rc1").is_prerelease());
+//! assert!(v("2.7.0.rc1") < v("2.7.0"));
+//! assert_eq!(v("5.2.4.a10").bump(), Some(v("5.3")));
+//! assert_eq!(v("1.1.rc10").release(), v("1.1"));
+//! ```
+
+mod requirement;
+
+pub use requirement::{Op, Requirement};
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::num::ParseIntError;
+use std::str::FromStr;
+use thiserror::Error;
+
+///
+/// 表示一个 RubyGems 版本号
+///
+/// 比较时忽略数字部分与字母部分各自末尾的零, 因此 `1.0 == 1.0.0`、`0.beta.1 == 0.0.beta.1`
+#[derive(Debug, Clone)]
+pub struct Version {
+    version: String,
+    segments: Vec<Segment>,
+}
+
+///
+/// 版本号中的一段
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// 数字段
+    Numeric(u64),
+    /// 字母段, 保留原始大小写
+    Alpha(String),
+}
+
+///
+/// 解析 RubyGems 版本号或需求时可能发生的错误
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ParseError {
+
+    #[error("解析数字失败: {0}")]
+    IntError(#[from] ParseIntError),
+
+    #[error("非法的 RubyGems 版本号 \"{0}\"")]
+    InvalidVersion(String),
+
+    #[error("非法的版本需求 \"{0}\"")]
+    InvalidRequirement(String),
+}
+
+impl Version {
+
+    /// 所有段
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// 是否为先行版本, 即是否包含字母
+    pub fn is_prerelease(&self) -> bool {
+        self.version.bytes().any(|b| b.is_ascii_alphabetic())
+    }
+
+    /// 去掉先行部分后的正式版本, 与 `Gem::Version#release` 一致
+    ///
+    /// 从末尾开始去掉段, 直到不再包含字母段
+    pub fn release(&self) -> Version {
+        if !self.is_prerelease() {
+            return self.clone()
+        }
+        Version::from_segments(self.numeric_prefix().to_vec())
+    }
+
+    /// 下一个需要升级的版本, 与 `Gem::Version#bump` 一致
+    ///
+    /// 去掉先行部分与最后一段后, 将新的最后一段加一, 例如 `5.2.4` 升级为 `5.3`;
+    /// 该段已经是 `u64::MAX` 时向前一段进位(`1.18446744073709551615.3` 升级为 `2`, 两者作为上限等价),
+    /// 所有段都是 `u64::MAX` 时没有更大的版本, 返回 `None`
+    pub fn bump(&self) -> Option<Version> {
+        let mut segments = self.numeric_prefix().to_vec();
+        if segments.len() > 1 {
+            segments.pop();
+        }
+        while let Some(Segment::Numeric(last)) = segments.last_mut() {
+            match last.checked_add(1) {
+                Some(next) => {
+                    *last = next;
+                    return Some(Version::from_segments(segments))
+                }
+                None => {
+                    segments.pop();
+                }
+            }
+        }
+        if segments.is_empty() && !self.numeric_prefix().is_empty() {
+            return None
+        }
+        Some(Version::from_segments(segments))
+    }
+
+    /// 第一个字母段之前的所有段
+    fn numeric_prefix(&self) -> &[Segment] {
+        let end = self.segments.iter().position(|s| matches!(s, Segment::Alpha(_))).unwrap_or(self.segments.len());
+        &self.segments[..end]
+    }
+
+    fn from_segments(segments: Vec<Segment>) -> Version {
+        let version = segments.iter().map(ToString::to_string).collect::<Vec<_>>().join(".");
+        Version { version, segments }
+    }
+
+    /// 数字部分与字母部分分别去掉末尾的零后拼接, 与 `canonical_segments` 一致
+    fn canonical_segments(&self) -> Vec<&Segment> {
+        let prefix = self.numeric_prefix();
+        let rest = &self.segments[prefix.len()..];
+        trim_zeros(prefix).iter().chain(trim_zeros(rest)).collect()
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Numeric(n) => write!(f, "{n}"),
+            Segment::Alpha(s) => f.write_str(s),
+        }
+    }
+}
+
+impl PartialEq for Version {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Version {}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 逐段比较, 缺失的段视为 0, 字母段低于数字段, 字母段之间按字节比较
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let (lhs, rhs) = (self.canonical_segments(), other.canonical_segments());
+        let zero = Segment::Numeric(0);
+        for i in 0..lhs.len().max(rhs.len()) {
+            let (l, r) = (lhs.get(i).copied().unwrap_or(&zero), rhs.get(i).copied().unwrap_or(&zero));
+            let ordering = match (l, r) {
+                (Segment::Numeric(a), Segment::Numeric(b)) => a.cmp(b),
+                (Segment::Alpha(a), Segment::Alpha(b)) => a.cmp(b),
+                (Segment::Alpha(_), Segment::Numeric(_)) => Ordering::Less,
+                (Segment::Numeric(_), Segment::Alpha(_)) => Ordering::Greater,
+            };
+            if ordering.is_ne() {
+                return ordering
+            }
+        }
+        Ordering::Equal
+    }
+}
+
+impl Hash for Version {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_segments().hash(state);
+    }
+}
+
+/// 输出规范化后的字符串, `-` 已替换为 `.pre.`
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.version)
+    }
+}
+
+/// 解析 RubyGems 版本号
+///
+/// 首段必须是数字, 之后的段由字母数字组成; 可以带有以 `-` 开始的后缀。
+/// 忽略首尾空白, 空字符串视为 `0`
+impl FromStr for Version {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Version, ParseError> {
+        let trimmed = s.trim();
+        let version = if trimmed.is_empty() { "0" } else { trimmed };
+        if !is_correct(version) {
+            return Err(ParseError::InvalidVersion(s.to_string()))
+        }
+        let version = version.replace('-', ".pre.");
+
+        let mut segments = Vec::new();
+        let mut rest = version.as_str();
+        while !rest.is_empty() {
+            let first = rest.as_bytes()[0];
+            let len = if first.is_ascii_digit() {
+                rest.bytes().take_while(u8::is_ascii_digit).count()
+            } else if first.is_ascii_alphabetic() {
+                rest.bytes().take_while(u8::is_ascii_alphabetic).count()
+            } else {
+                rest = &rest[1..];
+                continue;
+            };
+            let (segment, tail) = rest.split_at(len);
+            segments.push(if first.is_ascii_digit() {
+                Segment::Numeric(segment.parse()?)
+            } else {
+                Segment::Alpha(segment.to_string())
+            });
+            rest = tail;
+        }
+
+        Ok(Version { version, segments })
+    }
+}
+
+impl TryFrom<&str> for Version {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Version, ParseError> {
+        s.parse()
+    }
+}
+
+/// 去掉末尾值为 0 的数字段
+fn trim_zeros(segments: &[Segment]) -> &[Segment] {
+    let len = segments.iter().rposition(|s| *s != Segment::Numeric(0)).map_or(0, |i| i + 1);
+    &segments[..len]
+}
+
+/// 对应 `Gem::Version::VERSION_PATTERN`:
+/// `[0-9]+(\.[0-9a-zA-Z]+)*(-[0-9A-Za-z-]+(\.[0-9A-Za-z-]+)*)?`
+fn is_correct(s: &str) -> bool {
+    let (main, pre) = match s.split_once('-') {
+        Some((main, pre)) => (main, Some(pre)),
+        None => (s, None),
+    };
+    let mut parts = main.split('.');
+    let first = parts.next().unwrap_or_default();
+    !first.is_empty()
+        && first.bytes().all(|b| b.is_ascii_digit())
+        && parts.all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_alphanumeric()))
+        && pre.is_none_or(|pre| pre.split('.').all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParseError, Segment, Version};
+    use std::cmp::Ordering;
+    use std::collections::HashSet;
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap_or_else(|e| panic!("{s}: {e}"))
+    }
+
+    /// 用例取自 RubyGems 的 test_gem_version.rb
+    #[test]
+    fn test_compare() {
+        let cases = [
+            ("1.0", "1.0.0", Ordering::Equal),
+            ("1.0", "1.0.a", Ordering::Greater),
+            ("1.8.2", "0.0.0", Ordering::Greater),
+            ("1.8.2", "1.8.2.a", Ordering::Greater),
+            ("1.8.2.b", "1.8.2.a", Ordering::Greater),
+            ("1.8.2.a", "1.8.2", Ordering::Less),
+            ("1.8.2.a10", "1.8.2.a9", Ordering::Greater),
+            ("", "0", Ordering::Equal),
+            ("0.beta.1", "0.0.beta.1", Ordering::Equal),
+            ("0.0.beta", "0.0.beta.1", Ordering::Less),
+            ("0.0.beta", "0.beta.1", Ordering::Less),
+            ("5.a", "5.0.0.rc2", Ordering::Less),
+            ("5.x", "5.0.0.rc2", Ordering::Greater),
+            ("1.9.3", "1.9.3.1", Ordering::Less),
+            ("1.0.0-1", "1.0.0.pre.1", Ordering::Equal),
+            ("2.7.0.rc1", "2.7.0", Ordering::Less),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(v(a).cmp(&v(b)), expected, "{a} <=> {b}");
+        }
+        let set: HashSet<Version> = ["1", "1.0", "1.0.0", " 1.0 "].iter().map(|s| v(s)).collect();
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(v("1.0-beta-2").to_string(), "1.0.pre.beta.pre.2");
+        assert_eq!(v("").to_string(), "0");
+        assert_eq!(v("1.2.0.rc1").segments(), [
+            Segment::Numeric(1), Segment::Numeric(2), Segment::Numeric(0), Segment::Alpha("rc".to_string()), Segment::Numeric(1),
+        ]);
+        for input in ["junk", "1.0\n2.0", "1..2", "1.2.", "1.2 3", ".1", "1-", "1.0-a_b", "-1"] {
+            assert_eq!(input.parse::<Version>().unwrap_err(), ParseError::InvalidVersion(input.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_prerelease() {
+        for input in ["1.2.0.a", "2.9.b", "22.1.50.0.d", "1.2.d.42", "1.A", "1-1", "1-a", "1.0.a"] {
+            assert!(v(input).is_prerelease(), "{input}");
+        }
+        for input in ["1.2.0", "2.9", "22.1.50.0"] {
+            assert!(!v(input).is_prerelease(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_bump_and_release() {
+        let bumps = [("5.2.4", "5.3"), ("5.2.4.a", "5.3"), ("5.2.4.a10", "5.3"), ("5.0.0", "5.1"), ("5", "6")];
+        for (input, expected) in bumps {
+            assert_eq!(v(input).bump().unwrap().to_string(), expected, "{input}");
+        }
+        assert_eq!(v("18446744073709551615").bump(), None);
+        assert_eq!(v("1.18446744073709551615.3").bump(), Some(v("2")));
+        assert_eq!(v("18446744073709551615.18446744073709551615.3").bump(), None);
+        let releases = [("1.2.0.a", "1.2.0"), ("1.1.rc10", "1.1"), ("1.9.3.alpha.5", "1.9.3"), ("1.9.3", "1.9.3")];
+        for (input, expected) in releases {
+            assert_eq!(v(input).release().to_string(), expected, "{input}");
+        }
+    }
+}
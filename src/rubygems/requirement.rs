@@ -0,0 +1,221 @@
+//! RubyGems 版本需求, 例如 Gemfile 中的 `~> 1.4`、`>= 1.0, != 1.3`
+
+use super::{ParseError, Version};
+use std::fmt;
+use std::str::FromStr;
+
+///
+/// 由若干个以 `,` 分隔的条件组成的版本需求, 与 `Gem::Requirement` 一致
+///
+/// 所有条件均满足时版本才满足需求, 没有任何条件时等同于 `>= 0`。
+/// 与 `Gem::Requirement#satisfied_by?` 相同, 先行版本不会被特殊排除
+///
+/// ```
+/// use version::rubygems::{Requirement, Version};
+///
+/// let req: Requirement = "~> 1.4, != 1.4.2".parse().unwrap();
+/// assert!(req.is_satisfied_by(&"1.9".parse::<Version>().unwrap()));
+/// assert!(!req.is_satisfied_by(&"1.4.2".parse::<Version>().unwrap()));
+/// assert!(!req.is_satisfied_by(&"2.0".parse::<Version>().unwrap()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Requirement {
+    requirements: Vec<(Op, Version)>,
+}
+
+///
+/// 条件的运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `=`, 未写运算符时的默认值
+    Equal,
+    /// `!=`
+    NotEqual,
+    /// `>`
+    Greater,
+    /// `<`
+    Less,
+    /// `>=`
+    GreaterEqual,
+    /// `<=`
+    LessEqual,
+    /// `~>`, 悲观约束: 不低于给定版本, 且低于它的 [`Version::bump`]
+    Pessimistic,
+}
+
+impl Requirement {
+
+    /// 组成需求的所有条件
+    pub fn requirements(&self) -> &[(Op, Version)] {
+        &self.requirements
+    }
+
+    /// 是否有条件引用了先行版本
+    pub fn is_prerelease(&self) -> bool {
+        self.requirements.iter().any(|(_, v)| v.is_prerelease())
+    }
+
+    /// 判断版本号是否满足所有条件
+    pub fn is_satisfied_by(&self, version: &Version) -> bool {
+        self.requirements.iter().all(|(op, required)| op.matches(version, required))
+    }
+}
+
+impl Op {
+
+    /// 判断 `version` 与条件中的版本号 `required` 是否满足该运算符
+    ///
+    /// `~>` 比较的是 `version` 的正式版本, 因此 `~> 1.4` 不接受 `2.0.a`;
+    /// `required` 的各段都是 `u64::MAX`、无法升级时没有上限
+    pub fn matches(self, version: &Version, required: &Version) -> bool {
+        match self {
+            Op::Equal => version == required,
+            Op::NotEqual => version != required,
+            Op::Greater => version > required,
+            Op::Less => version < required,
+            Op::GreaterEqual => version >= required,
+            Op::LessEqual => version <= required,
+            Op::Pessimistic => version >= required && required.bump().is_none_or(|upper| version.release() < upper),
+        }
+    }
+}
+
+/// 等同于 `>= 0`, 接受任何版本
+impl Default for Requirement {
+    fn default() -> Requirement {
+        Requirement { requirements: vec![(Op::GreaterEqual, Version::from_segments(vec![super::Segment::Numeric(0)]))] }
+    }
+}
+
+/// 解析以 `,` 分隔的条件, 每个条件由可选的运算符与版本号组成, 两者之间允许存在空白
+impl FromStr for Requirement {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Requirement, ParseError> {
+        let invalid = || ParseError::InvalidRequirement(s.to_string());
+        let requirements = s.split(',').map(|part| {
+            let part = part.trim();
+            let (op, rest) = [
+                ("~>", Op::Pessimistic),
+                (">=", Op::GreaterEqual),
+                ("<=", Op::LessEqual),
+                ("!=", Op::NotEqual),
+                ("=", Op::Equal),
+                (">", Op::Greater),
+                ("<", Op::Less),
+            ]
+            .iter()
+            .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|rest| (*op, rest.trim_start())))
+            .unwrap_or((Op::Equal, part));
+            // 版本号本身允许空字符串, 但在需求中必须写出
+            if rest.is_empty() {
+                return Err(invalid())
+            }
+            let version = rest.parse().map_err(|e| match e {
+                ParseError::InvalidVersion(_) => invalid(),
+                e => e,
+            })?;
+            Ok((op, version))
+        }).collect::<Result<Vec<_>, ParseError>>()?;
+        Ok(Requirement { requirements })
+    }
+}
+
+impl TryFrom<&str> for Requirement {
+    type Error = ParseError;
+
+    fn try_from(s: &str) -> Result<Requirement, ParseError> {
+        s.parse()
+    }
+}
+
+impl fmt::Display for Requirement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (op, version)) in self.requirements.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{op} {version}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Op::Equal => "=",
+            Op::NotEqual => "!=",
+            Op::Greater => ">",
+            Op::Less => "<",
+            Op::GreaterEqual => ">=",
+            Op::LessEqual => "<=",
+            Op::Pessimistic => "~>",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Op, Requirement};
+    use crate::rubygems::{ParseError, Version};
+
+    fn v(s: &str) -> Version {
+        s.parse().unwrap()
+    }
+
+    /// 用例取自 RubyGems 的 test_gem_requirement.rb
+    #[test]
+    fn test_satisfied_by() {
+        let satisfied = [
+            ("~> 1.4", "1.4"),
+            ("~> 1.4", "1.5"),
+            ("~> 1.4.4", "1.4.5"),
+            ("~> 1", "1.9"),
+            ("~> 1.0.a", "1.0.0.b"),
+            ("= 1.0", "1.0.0"),
+            ("1.0", "1.0"),
+            ("!= 1.2", "1.3"),
+            ("> 0.a", "0"),
+            ("< 2.0.a", "1.9"),
+            (">= 1.0, < 2", "1.5"),
+            ("> 1.1, < 2, != 1.5", "1.6.a"),
+            ("<= 1.0.a", "1.0.a"),
+            ("~> 18446744073709551615", "18446744073709551615.1"),
+            ("~> 1.18446744073709551615.3", "1.18446744073709551615.4"),
+        ];
+        for (req, version) in satisfied {
+            assert!(req.parse::<Requirement>().unwrap().is_satisfied_by(&v(version)), "{req} 应当接受 {version}");
+        }
+
+        let unsatisfied = [
+            ("~> 1.4", "2.0"),
+            ("~> 1.4.4", "1.5"),
+            ("~> 1.4.4", "1.4.3"),
+            ("~> 1", "2.0"),
+            ("~> 2.0.0", "2.0.0.a"),
+            ("~> 1.4", "2.0.a"),
+            ("!= 1.2", "1.2.0"),
+            ("> 1.1, < 2, != 1.5", "1.5"),
+            ("< 1.0", "1.0.0"),
+            ("~> 1.18446744073709551615.3", "2.0"),
+        ];
+        for (req, version) in unsatisfied {
+            assert!(!req.parse::<Requirement>().unwrap().is_satisfied_by(&v(version)), "{req} 不应接受 {version}");
+        }
+        assert!(Requirement::default().is_satisfied_by(&v("0.0.1.a")));
+    }
+
+    #[test]
+    fn test_parse() {
+        let req: Requirement = "~>1.4 ,  >=1.4.2".parse().unwrap();
+        assert_eq!(req.requirements(), [(Op::Pessimistic, v("1.4")), (Op::GreaterEqual, v("1.4.2"))]);
+        assert_eq!(req.to_string(), "~> 1.4, >= 1.4.2");
+        assert_eq!(Requirement::default().to_string(), ">= 0");
+        assert!("~> 2.7.0.rc1".parse::<Requirement>().unwrap().is_prerelease());
+
+        for input in ["", "~>", ">= 1.0,", "=> 1.0", "~> junk", "1.0 2.0"] {
+            assert_eq!(input.parse::<Requirement>().unwrap_err(), ParseError::InvalidRequirement(input.to_string()), "{input}");
+        }
+    }
+}
@@ -0,0 +1,283 @@
+//! 统一的版本号方案接口
+//!
+//! [`VersionScheme`] 描述一种版本号的解析、比较与规范化, 每种方案对应一个零大小的类型, 例如 [`Debian`]、[`Apk`]。
+//! [`DynVersionScheme`] 只通过字符串交互, 可以作为 trait 对象使用,
+//! [`by_ecosystem`] 根据软件包生态的名称(例如 purl 的类型)选择对应的方案
+//!
+//! ```
+//! use std::cmp::Ordering;
+//! use version::scheme;
+//!
+//! let alpine = scheme::by_ecosystem("alpine").unwrap();
+//! assert_eq!(alpine.compare_str("1.2.3_rc1-r4", "1.2.3-r0").unwrap(), Ordering::Less);
+//!
+//! let debian = scheme::by_ecosystem("deb").unwrap();
+//! assert_eq!(debian.normalize_str("0:1.0-1").unwrap(), "1.0-1");
+//! assert!(debian.compare_str("1.0", "not a version").is_err());
+//! ```
+
+use std::cmp::Ordering;
+use std::error::Error as StdError;
+use std::fmt;
+use thiserror::Error;
+
+///
+/// 一种版本号方案
+///
+/// ```
+/// use version::scheme::{Portage, VersionScheme};
+///
+/// fn newest<S: VersionScheme>(scheme: &S, versions: &[&str]) -> Option<S::Version> {
+///     versions.iter().filter_map(|s| scheme.parse(s).ok()).max_by(|a, b| scheme.compare(a, b))
+/// }
+///
+/// let newest = newest(&Portage, &["1.0_rc1", "1.0", "1.0_p1", "1.0_beta"]).unwrap();
+/// assert_eq!(newest.to_string(), "1.0_p1");
+/// ```
+pub trait VersionScheme {
+    /// 方案的名称
+    const NAME: &'static str;
+
+    /// 解析得到的版本号
+    type Version: Ord + fmt::Display;
+
+    /// 解析失败时的错误
+    type Error: StdError + Send + Sync + 'static;
+
+    /// 解析版本号
+    fn parse(&self, s: &str) -> Result<Self::Version, Self::Error>;
+
+    /// 比较两个版本号, 默认使用版本号自身的顺序
+    fn compare(&self, a: &Self::Version, b: &Self::Version) -> Ordering {
+        a.cmp(b)
+    }
+
+    /// 解析版本号并输出其规范形式, 默认使用版本号的 `Display`
+    ///
+    /// 规范形式去掉了首尾空白与可以省略的默认值, 但比较结果相等的版本号不一定具有相同的规范形式
+    fn normalize(&self, s: &str) -> Result<String, Self::Error> {
+        self.parse(s).map(|v| v.to_string())
+    }
+}
+
+///
+/// 只通过字符串交互的版本号方案, 所有 [`VersionScheme`] 都自动实现了它
+pub trait DynVersionScheme: Send + Sync {
+
+    /// 方案的名称
+    fn name(&self) -> &'static str;
+
+    /// 判断字符串是否为这种方案下合法的版本号
+    fn is_valid(&self, s: &str) -> bool;
+
+    /// 解析并比较两个版本号
+    fn compare_str(&self, a: &str, b: &str) -> Result<Ordering, SchemeError>;
+
+    /// 解析版本号并输出其规范形式
+    fn normalize_str(&self, s: &str) -> Result<String, SchemeError>;
+}
+
+///
+/// 通过 [`DynVersionScheme`] 解析版本号失败时的错误
+#[derive(Error, Debug)]
+#[error("\"{input}\" 不是合法的 {scheme} 版本号: {source}")]
+pub struct SchemeError {
+    scheme: &'static str,
+    input: String,
+    source: Box<dyn StdError + Send + Sync>,
+}
+
+impl SchemeError {
+
+    /// 方案的名称
+    pub fn scheme(&self) -> &'static str {
+        self.scheme
+    }
+
+    /// 解析失败的输入
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl<S: VersionScheme + Send + Sync> DynVersionScheme for S {
+    fn name(&self) -> &'static str {
+        S::NAME
+    }
+
+    fn is_valid(&self, s: &str) -> bool {
+        self.parse(s).is_ok()
+    }
+
+    fn compare_str(&self, a: &str, b: &str) -> Result<Ordering, SchemeError> {
+        Ok(self.compare(&parse(self, a)?, &parse(self, b)?))
+    }
+
+    fn normalize_str(&self, s: &str) -> Result<String, SchemeError> {
+        self.normalize(s).map_err(|e| error::<S>(s, e))
+    }
+}
+
+fn parse<S: VersionScheme>(scheme: &S, s: &str) -> Result<S::Version, SchemeError> {
+    scheme.parse(s).map_err(|e| error::<S>(s, e))
+}
+
+fn error<S: VersionScheme>(input: &str, source: S::Error) -> SchemeError {
+    SchemeError { scheme: S::NAME, input: input.to_string(), source: Box::new(source) }
+}
+
+/// 本库的语义化版本号 [`crate::Version`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Semver;
+
+/// Debian 软件包版本号 [`crate::debian::Version`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Debian;
+
+/// RPM 软件包版本号 [`crate::rpm::Version`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rpm;
+
+/// Alpine 软件包版本号 [`crate::apk::Version`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Apk;
+
+/// Arch Linux 软件包版本号 [`crate::pacman::Version`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Pacman;
+
+/// Gentoo 软件包版本号 [`crate::portage::Version`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Portage;
+
+impl VersionScheme for Semver {
+    const NAME: &'static str = "semver";
+    type Version = crate::Version;
+    type Error = crate::ParseError;
+
+    fn parse(&self, s: &str) -> Result<crate::Version, crate::ParseError> {
+        s.trim().parse()
+    }
+}
+
+impl VersionScheme for Debian {
+    const NAME: &'static str = "debian";
+    type Version = crate::debian::Version;
+    type Error = crate::debian::ParseError;
+
+    /// 与 `dpkg --compare-versions` 一样, 接受 dpkg 只给出警告的版本号
+    fn parse(&self, s: &str) -> Result<crate::debian::Version, crate::debian::ParseError> {
+        crate::debian::Version::parse_lenient(s)
+    }
+}
+
+impl VersionScheme for Rpm {
+    const NAME: &'static str = "rpm";
+    type Version = crate::rpm::Version;
+    type Error = crate::rpm::ParseError;
+
+    fn parse(&self, s: &str) -> Result<crate::rpm::Version, crate::rpm::ParseError> {
+        s.parse()
+    }
+}
+
+impl VersionScheme for Apk {
+    const NAME: &'static str = "apk";
+    type Version = crate::apk::Version;
+    type Error = crate::apk::ParseError;
+
+    fn parse(&self, s: &str) -> Result<crate::apk::Version, crate::apk::ParseError> {
+        s.parse()
+    }
+}
+
+impl VersionScheme for Pacman {
+    const NAME: &'static str = "pacman";
+    type Version = crate::pacman::Version;
+    type Error = crate::pacman::ParseError;
+
+    fn parse(&self, s: &str) -> Result<crate::pacman::Version, crate::pacman::ParseError> {
+        s.parse()
+    }
+
+    /// 与 pacman 的 `vercmp` 一致, 一方没有发布号时不比较发布号
+    fn compare(&self, a: &crate::pacman::Version, b: &crate::pacman::Version) -> Ordering {
+        a.vercmp(b)
+    }
+}
+
+impl VersionScheme for Portage {
+    const NAME: &'static str = "portage";
+    type Version = crate::portage::Version;
+    type Error = crate::portage::ParseError;
+
+    fn parse(&self, s: &str) -> Result<crate::portage::Version, crate::portage::ParseError> {
+        s.parse()
+    }
+}
+
+/// 根据软件包生态的名称选择版本号方案, 不区分大小写
+///
+/// 可以使用 purl 的类型(`deb`、`rpm`、`apk`、`alpm`、`cargo`)、发行版名称或方案名称
+///
+/// ```
+/// use version::scheme::by_ecosystem;
+///
+/// assert_eq!(by_ecosystem("Ubuntu").unwrap().name(), "debian");
+/// assert_eq!(by_ecosystem("alpm").unwrap().name(), "pacman");
+/// assert!(by_ecosystem("npm").is_none());
+/// ```
+pub fn by_ecosystem(ecosystem: &str) -> Option<&'static dyn DynVersionScheme> {
+    let scheme: &'static dyn DynVersionScheme = match ecosystem.trim().to_ascii_lowercase().as_str() {
+        "semver" | "cargo" => &Semver,
+        "debian" | "deb" | "dpkg" | "ubuntu" => &Debian,
+        "rpm" | "fedora" | "centos" | "rhel" | "redhat" | "rocky" | "almalinux" | "opensuse" | "suse" => &Rpm,
+        "apk" | "alpine" | "wolfi" => &Apk,
+        "pacman" | "alpm" | "arch" | "archlinux" => &Pacman,
+        "portage" | "gentoo" | "ebuild" => &Portage,
+        _ => return None,
+    };
+    Some(scheme)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{by_ecosystem, Apk, Debian, Semver, VersionScheme};
+    use std::cmp::Ordering;
+
+    /// 测试各个方案通过统一接口比较
+    #[test]
+    fn test_compare() {
+        let cases = [
+            ("cargo", "1.0.0-rc.1", "1.0.0", Ordering::Less),
+            ("deb", "1.0~rc1-1", "1.0-1", Ordering::Less),
+            ("deb", "1.0_beta-1", "1.0-1", Ordering::Greater),
+            ("rpm", "1.0^git1-1", "1.0-1", Ordering::Greater),
+            ("apk", "1.0_p1-r0", "1.0-r9", Ordering::Greater),
+            ("alpm", "1:0.1-1", "9.9-1", Ordering::Greater),
+            ("arch", "1.5", "1.5-1", Ordering::Equal),
+            ("arch", "1.5-1", "1.5-2", Ordering::Less),
+            ("gentoo", "1.0_pre1", "1.0", Ordering::Less),
+        ];
+        for (ecosystem, a, b, expected) in cases {
+            let scheme = by_ecosystem(ecosystem).unwrap();
+            assert_eq!(scheme.compare_str(a, b).unwrap(), expected, "{ecosystem}: {a} <=> {b}");
+            assert!(scheme.is_valid(a), "{ecosystem}: {a}");
+        }
+    }
+
+    /// 测试规范化与错误信息
+    #[test]
+    fn test_normalize_and_errors() {
+        assert_eq!(Semver.normalize(" 1.2 ").unwrap(), "1.2.0");
+        assert_eq!(Debian.normalize("0:1.0-1").unwrap(), "1.0-1");
+        assert_eq!(Apk.normalize("1.0-r01").unwrap(), "1.0-r1");
+        assert_eq!(Apk.compare(&Apk.parse("1.0").unwrap(), &Apk.parse("1.0-r0").unwrap()), Ordering::Less);
+
+        let apk = by_ecosystem("APK").unwrap();
+        let error = apk.compare_str("1.0", "1.0_foo").unwrap_err();
+        assert_eq!((error.scheme(), error.input()), ("apk", "1.0_foo"));
+        assert_eq!(error.to_string(), "\"1.0_foo\" 不是合法的 apk 版本号: 未知的后缀 \"_foo\"");
+        assert!(by_ecosystem("pypi").is_none());
+    }
+}
@@ -0,0 +1,185 @@
+//! `serde` 特性下 [`Version`] 与 [`ParseError`] 的序列化实现
+//!
+//! 默认情况下 [`Version`] 序列化为完整的版本号字符串, 例如 `"1.0.0-rc.1+build.5"`;
+//! 若需要结构化的形式, 可以通过 [`structured`] 模块配合 `#[serde(with = "...")]` 使用
+
+use crate::{Component, ParseError, ParseErrorKind, Version};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+use std::fmt;
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{self:#}"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VersionVisitor;
+
+        impl Visitor<'_> for VersionVisitor {
+            type Value = Version;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("SemVer 版本号字符串")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Version, E> {
+                // 将 ParseError 的详细信息透传给 serde 的错误类型
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+/// 解析错误序列化为 `{ "kind": 种类, "message": 错误信息, "input": 输入, "span": [起始, 结束], "component": 组成部分, "suggestion": 修正建议 }`
+/// 的形式, 便于放入 API 响应中; 没有组成部分或修正建议时对应的字段为 `null`
+impl Serialize for ParseError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let kind = match self.kind() {
+            ParseErrorKind::IntError(_) => "IntError",
+            ParseErrorKind::LengthError => "LengthError",
+            ParseErrorKind::EmptyIdentifier => "EmptyIdentifier",
+            ParseErrorKind::InvalidCharacter(_) => "InvalidCharacter",
+            ParseErrorKind::LeadingZero(_) => "LeadingZero",
+            ParseErrorKind::Overflow { .. } => "Overflow",
+        };
+        let component = self.component().map(|component| match component {
+            Component::Major => "major",
+            Component::Minor => "minor",
+            Component::Patch => "patch",
+            Component::Pre => "pre",
+            Component::Build => "build",
+        });
+        let span = self.span();
+        let mut state = serializer.serialize_struct("ParseError", 6)?;
+        state.serialize_field("kind", kind)?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("input", self.input())?;
+        state.serialize_field("span", &[span.start, span.end])?;
+        state.serialize_field("component", &component)?;
+        state.serialize_field("suggestion", &self.suggestion())?;
+        state.end()
+    }
+}
+
+/// 以 `{major, minor, patch, pre, build}` 结构序列化 [`Version`]
+///
+/// `pre` 与 `build` 为以 `.` 连接的字符串, 不存在时为空字符串
+///
+/// # 示例
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use version::Version;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Package {
+///     #[serde(with = "version::structured")]
+///     version: Version,
+/// }
+///
+/// let package = Package { version: "1.2.3-rc.1".parse().unwrap() };
+/// let json = serde_json::to_string(&package).unwrap();
+/// assert_eq!(json, r#"{"version":{"major":1,"minor":2,"patch":3,"pre":"rc.1","build":""}}"#);
+/// ```
+pub mod structured {
+    use crate::{parse_build, parse_pre, Version};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Structured {
+        major: u64,
+        minor: u64,
+        patch: u64,
+        #[serde(default)]
+        pre: String,
+        #[serde(default)]
+        build: String,
+    }
+
+    /// 将版本号序列化为结构化形式
+    pub fn serialize<S: Serializer>(version: &Version, serializer: S) -> Result<S::Ok, S::Error> {
+        let pre: Vec<String> = version.pre.iter().map(|i| i.to_string()).collect();
+        Structured {
+            major: version.major,
+            minor: version.minor,
+            patch: version.patch,
+            pre: pre.join("."),
+            build: version.build.join("."),
+        }
+        .serialize(serializer)
+    }
+
+    /// 从结构化形式反序列化版本号, `pre` 与 `build` 按 SemVer 语法校验
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Version, D::Error> {
+        let s = Structured::deserialize(deserializer)?;
+        let pre = if s.pre.is_empty() { Vec::new() } else { parse_pre(&s.pre).map_err(D::Error::custom)? };
+        let build = if s.build.is_empty() { Vec::new() } else { parse_build(&s.build).map_err(D::Error::custom)? };
+        Ok(Version {
+            major: s.major,
+            minor: s.minor,
+            patch: s.patch,
+            pre,
+            build,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Version;
+    use serde::{Deserialize, Serialize};
+
+    /// 测试默认以字符串形式序列化
+    #[test]
+    fn test_string_form() {
+        let v: Version = "1.0.0-rc.1+build.5".parse().unwrap();
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, r#""1.0.0-rc.1+build.5""#);
+
+        let back: Version = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.to_string(), "1.0.0-rc.1+build.5");
+    }
+
+    /// 测试反序列化时透传解析错误
+    #[test]
+    fn test_error_detail() {
+        let err = serde_json::from_str::<Version>(r#""1.0.0-rc.01""#).unwrap_err();
+        assert!(err.to_string().contains("前导零"), "{err}");
+
+        let json = serde_json::to_string(&"1.x".parse::<Version>().unwrap_err()).unwrap();
+        assert_eq!(json, concat!(
+            r#"{"kind":"IntError","message":"副版本号 \"x\" 不是合法的数字","input":"1.x","span":[2,3],"#,
+            r#""component":"minor","suggestion":"通配符 \"x\" 只能用于版本需求, 请改为具体的数字"}"#,
+        ));
+        let json = serde_json::to_string(&"1".parse::<Version>().unwrap_err()).unwrap();
+        assert!(json.contains(r#""span":[0,1],"component":null"#), "{json}");
+    }
+
+    /// 测试结构化形式的往返转换
+    #[test]
+    fn test_structured_form() {
+        #[derive(Serialize, Deserialize)]
+        struct Package {
+            #[serde(with = "crate::structured")]
+            version: Version,
+        }
+
+        let package = Package { version: "2026.10.18-hotfix.2+sha.5114f85".parse().unwrap() };
+        let json = serde_json::to_string(&package).unwrap();
+        assert_eq!(
+            json,
+            r#"{"version":{"major":2026,"minor":10,"patch":18,"pre":"hotfix.2","build":"sha.5114f85"}}"#
+        );
+        let back: Package = serde_json::from_str(&json).unwrap();
+        assert_eq!(format!("{:#}", back.version), "2026.10.18-hotfix.2+sha.5114f85");
+
+        let minimal: Package = serde_json::from_str(r#"{"version":{"major":1,"minor":0,"patch":0}}"#).unwrap();
+        assert_eq!(minimal.version, Version::new(1, 0, 0));
+        assert!(serde_json::from_str::<Package>(r#"{"version":{"major":1,"minor":0,"patch":0,"pre":"a..b"}}"#).is_err());
+    }
+}